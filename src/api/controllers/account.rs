@@ -1,25 +1,42 @@
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
-use crate::api::middlewares::validate::Json;
+use crate::api::middlewares::auth::{
+    RequireJsonWebToken, authorization_cookie, removal_authorization_cookie, require_session,
+};
+use crate::api::middlewares::csrf::{csrf_cookie, removal_csrf_cookie};
+use crate::api::middlewares::validate::{Json, Query};
 use crate::domain::error::AppError;
 use crate::domain::services::account::AccountService;
+use crate::domain::services::account_status::AccountStatusService;
+use crate::domain::services::email_verification::EmailVerificationService;
 
-use crate::api::dto::account::{AccessTokenDTO, AccountDTO, CreateAccountDTO, CredentialsDTO};
+use crate::api::dto::account::{
+    AccessTokenDTO, AccountDTO, ChangePasswordDTO, CreateAccountDTO, CredentialsDTO,
+    RefreshTokenDTO, ResendVerificationDTO, UpdateAccountDTO, VerifyEmailDTO,
+};
+use crate::api::dto::mfa::MfaPendingDTO;
 use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::refresh_token::RefreshTokenService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::totp::TotpService;
 
-use actix_web::{
-    HttpResponse,
-    cookie::time::OffsetDateTime,
-    cookie::{Cookie, SameSite},
-    post,
-    web::Data as State,
-};
+use actix_web::{HttpRequest, HttpResponse, delete, get, patch, post, web::Data as State};
 
 use utoipa_actix_web::service_config::ServiceConfig;
 
 pub fn routes(cfg: &mut ServiceConfig) {
-    cfg.service(signup).service(signin);
+    cfg.service(signup)
+        .service(signin)
+        .service(verify_email)
+        .service(resend_verification)
+        .service(signout)
+        .service(refresh)
+        .service(profile)
+        .service(update_profile)
+        .service(delete_profile)
+        .service(deactivate_profile)
+        .service(change_password);
 }
 
 #[utoipa::path(
@@ -39,19 +56,33 @@ pub fn routes(cfg: &mut ServiceConfig) {
 pub async fn signup(
     payload: Json<CreateAccountDTO>,
     account_service: State<Arc<dyn AccountService>>,
+    email_verification_service: State<Arc<dyn EmailVerificationService>>,
 ) -> ApiResult {
     let account_dto = payload.into_inner();
 
     let created_account = account_service.signup(account_dto.into()).await?;
 
+    // The account is already saved, a failed mail is logged and can be requested again through
+    // the resend endpoint instead of failing a signup that cannot be retried
+    if let Err(err) = email_verification_service.send(&created_account).await {
+        tracing::warn!(error = ?err, "verification email failed");
+    }
+
     Ok(HttpResponse::Ok().json(AccountDTO::from(created_account)))
 }
 
 #[utoipa::path(
     responses(
         (status = 200, body = AccessTokenDTO),
+        (status = 202, body = MfaPendingDTO, description = "Second Factor Required"),
         (status = 400, body = AppError, example = json!(AppError::example_400())),
         (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 410, body = AppError, example = json!(AppError::example_410())),
+        (status = 423, body = AppError, example = json!(AppError::example_423())),
+        (status = 429, body = AppError,
+            headers(("Retry-After" = u64, description = "Seconds until signin is allowed again")),
+            example = json!(AppError::example_429())),
         (status = 500, body = AppError, example = json!(AppError::example_500())),
         (status = 503, body = AppError, example = json!(AppError::example_503()))
     ),
@@ -60,33 +91,355 @@ pub async fn signup(
 )]
 #[post("/signin")]
 pub async fn signin(
+    req: HttpRequest,
     payload: Json<CredentialsDTO>,
     account_service: State<Arc<dyn AccountService>>,
+    totp_service: State<Arc<dyn TotpService>>,
     jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
 ) -> ApiResult {
     let credentials_dto = payload.into_inner();
 
-    let account = account_service.signin(credentials_dto.into()).await?;
+    let client_ip = req.peer_addr().map(|addr| addr.ip().to_string());
+
+    let account = account_service
+        .signin(credentials_dto.into(), client_ip.as_deref())
+        .await?;
+
+    if totp_service.is_enabled(&account.id).await? {
+        let mfa_token = jsonwebtoken_service.generate_mfa_token(&account)?;
+
+        return Ok(HttpResponse::Accepted().json(MfaPendingDTO::from(mfa_token)));
+    }
+
+    let session = session_service
+        .create((&req, account.id.as_str()).into())
+        .await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &session.id)?;
+    let refresh_token = refresh_token_service.issue(account.id, session.id).await?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Email Verified"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(VerifyEmailDTO),
+    tag = "Account"
+)]
+#[get("/verify-email")]
+pub async fn verify_email(
+    query: Query<VerifyEmailDTO>,
+    email_verification_service: State<Arc<dyn EmailVerificationService>>,
+) -> ApiResult {
+    let verify_dto = query.into_inner();
+
+    email_verification_service.verify(&verify_dto.token).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 202, description = "Verification Email Sent If The Account Is Unverified"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 422, body = AppError, example = json!(AppError::example_422()))
+    ),
+    request_body = ResendVerificationDTO,
+    tag = "Account"
+)]
+#[post("/verify-email/resend")]
+pub async fn resend_verification(
+    payload: Json<ResendVerificationDTO>,
+    email_verification_service: State<Arc<dyn EmailVerificationService>>,
+) -> ApiResult {
+    let resend_dto = payload.into_inner();
+    let email_verification_service = email_verification_service.get_ref().clone();
+
+    // Answered before the lookup and the mail, so the response time does not reveal which
+    // accounts exist or are verified
+    actix_web::rt::spawn(async move {
+        if let Err(err) = email_verification_service.resend(&resend_dto.email).await {
+            tracing::warn!(error = ?err, "verification email resend failed");
+        }
+    });
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Signed Out"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[post("/signout")]
+pub async fn signout(
+    auth: RequireJsonWebToken,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    // Revocations are kept until the token expires and API keys may not, they are revoked through
+    // /me/api-keys instead
+    if auth.api_key.is_some() {
+        return Err(AppError::Forbidden());
+    }
+
+    jsonwebtoken_service.revoke_token(&auth.claims).await?;
+
+    if let Some(session) = &auth.claims.sid {
+        session_service.revoke(&auth.claims.sub, session).await?;
+    }
+
+    Ok(HttpResponse::NoContent()
+        .cookie(removal_authorization_cookie())
+        .cookie(removal_csrf_cookie())
+        .finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccessTokenDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 410, body = AppError, example = json!(AppError::example_410())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 423, body = AppError, example = json!(AppError::example_423())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = RefreshTokenDTO,
+    tag = "Account"
+)]
+#[post("/token/refresh")]
+pub async fn refresh(
+    payload: Json<RefreshTokenDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+) -> ApiResult {
+    let refresh_token_dto = payload.into_inner();
+
+    let refresh_token = refresh_token_service
+        .rotate(&refresh_token_dto.refresh_token)
+        .await?;
+
+    let account = account_service.find_by_id(&refresh_token.account).await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &refresh_token.session)?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccountDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[get("/me")]
+pub async fn profile(
+    auth: RequireJsonWebToken,
+    account_service: State<Arc<dyn AccountService>>,
+) -> ApiResult {
+    let account = account_service.find_by_id(&auth.claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(AccountDTO::from(account)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccountDTO, description = "Account Updated"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = UpdateAccountDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[patch("/me")]
+pub async fn update_profile(
+    auth: RequireJsonWebToken,
+    payload: Json<UpdateAccountDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    email_verification_service: State<Arc<dyn EmailVerificationService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let account_dto = payload.into_inner();
+    let email_changed = account_dto.email.is_some();
+
+    let account = account_service
+        .update(&auth.claims.sub, account_dto.into())
+        .await?;
+
+    // A changed email comes back unverified, its owner gets a link like after a signup
+    if email_changed && account.email_verified_at.is_none() {
+        email_verification_service
+            .send(&account)
+            .await
+            .unwrap_or_else(|err| tracing::warn!(error = ?err, "verification email failed"));
+    }
+
+    Ok(HttpResponse::Ok().json(AccountDTO::from(account)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Account Deleted"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[delete("/me")]
+pub async fn delete_profile(
+    auth: RequireJsonWebToken,
+    account_service: State<Arc<dyn AccountService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    account_service.delete(&auth.claims.sub).await?;
+
+    // The account can still be restored, so nothing issued before the deletion may outlive it
+    session_service.revoke_account(&auth.claims.sub).await?;
+
+    jsonwebtoken_service.revoke_token(&auth.claims).await?;
+
+    Ok(HttpResponse::NoContent()
+        .cookie(removal_authorization_cookie())
+        .cookie(removal_csrf_cookie())
+        .finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Account Deactivated"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[post("/me/deactivate")]
+pub async fn deactivate_profile(
+    auth: RequireJsonWebToken,
+    account_status_service: State<Arc<dyn AccountStatusService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    account_status_service.deactivate(&auth.claims.sub).await?;
+
+    session_service.revoke_account(&auth.claims.sub).await?;
+
+    jsonwebtoken_service.revoke_token(&auth.claims).await?;
 
-    let access_token = jsonwebtoken_service.generate_token(account.id)?;
+    Ok(HttpResponse::NoContent()
+        .cookie(removal_authorization_cookie())
+        .cookie(removal_csrf_cookie())
+        .finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccessTokenDTO, description = "Password Changed"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 429, body = AppError,
+            headers(("Retry-After" = u64, description = "Seconds until the password can be checked again")),
+            example = json!(AppError::example_429())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = ChangePasswordDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "Account"
+)]
+#[post("/me/password")]
+pub async fn change_password(
+    req: HttpRequest,
+    auth: RequireJsonWebToken,
+    payload: Json<ChangePasswordDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let passwords_dto = payload.into_inner();
+
+    let account = account_service
+        .change_password(&auth.claims.sub, passwords_dto.into())
+        .await?;
+
+    // Also revokes every refresh token of the account
+    session_service.revoke_account(&account.id).await?;
 
-    let cookie = Cookie::build("Authorization", &access_token.token)
-        .http_only(true)
-        .secure(true)
-        .path("/api")
-        .same_site(SameSite::Strict)
-        .expires(OffsetDateTime::from_unix_timestamp(access_token.expiration).unwrap())
-        .finish();
+    // `iat` has second precision: revoke the current token explicitly in case it shares the second
+    jsonwebtoken_service.revoke_token(&auth.claims).await?;
 
-    Ok(HttpResponse::Ok().cookie(cookie).json(AccessTokenDTO {
-        token: access_token.token,
-        expires_at: access_token.expiration,
-    }))
+    if let Some(password_changed_at) = account.password_changed_at {
+        jsonwebtoken_service.revoke_issued_before(&account.id, password_changed_at);
+    }
+
+    let session = session_service
+        .create((&req, account.id.as_str()).into())
+        .await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &session.id)?;
+    let refresh_token = refresh_token_service.issue(account.id, session.id).await?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
 }
 
 #[cfg(test)]
 mod tests {
 
+    use actix_web::web;
     use actix_web::{
         App,
         dev::ServiceResponse,
@@ -95,9 +448,17 @@ mod tests {
     };
     use serde::{Deserialize, Serialize};
     use serde_json::{Value, json};
+    use tokio::sync::Mutex;
     use utoipa_actix_web::AppExt;
 
     use super::*;
+    use crate::domain::models::account::{Account, AccountStatus, Role};
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
 
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct Error {
@@ -116,6 +477,38 @@ mod tests {
             .await
     }
 
+    async fn verify_email(query: &str) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::get()
+            .uri(&format!("/verify-email{query}"))
+            .send_request(&app)
+            .await
+    }
+
+    async fn resend_verification(data: Value) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri("/verify-email/resend")
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
+    async fn refresh(data: Value) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri("/token/refresh")
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
     #[actix_web::test]
     async fn test_signup_invalid_email_format() {
         let payload = json!({
@@ -249,4 +642,218 @@ mod tests {
                 .contains("Email must contain between 3 and 255 characters")
         );
     }
+
+    #[actix_web::test]
+    async fn test_refresh_missing_token() {
+        let res = refresh(json!({})).await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 400);
+    }
+
+    #[actix_web::test]
+    async fn test_refresh_empty_token() {
+        let res = refresh(json!({ "refresh_token": "" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Refresh token must not be empty"));
+    }
+
+    fn jsonwebtoken_service() -> Arc<dyn JsonWebTokenService> {
+        let repo = Arc::new(RevokedTokenRepositoryImpl {
+            tokens: Mutex::new(vec![]),
+        });
+
+        Arc::new(JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            repo,
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![]),
+            }),
+            Arc::new(SessionRepositoryImpl {
+                sessions: Mutex::new(vec![]),
+            }),
+        ))
+    }
+
+    fn account() -> Account {
+        Account {
+            id: "ajk".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    async fn update_profile(data: Value, token: Option<String>) -> ServiceResponse {
+        let jsonwebtoken_service = jsonwebtoken_service();
+
+        let token = token.unwrap_or_else(|| {
+            jsonwebtoken_service
+                .generate_token(&account(), "session")
+                .unwrap()
+                .token
+        });
+
+        let app = test::init_service(
+            App::new()
+                .into_utoipa_app()
+                .configure(routes)
+                .into_app()
+                .app_data(web::Data::new(jsonwebtoken_service)),
+        )
+        .await;
+
+        TestRequest::patch()
+            .uri("/me")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_signout_missing_token() {
+        let jsonwebtoken_service = jsonwebtoken_service();
+
+        let app = test::init_service(
+            App::new()
+                .into_utoipa_app()
+                .configure(routes)
+                .into_app()
+                .app_data(web::Data::new(jsonwebtoken_service)),
+        )
+        .await;
+
+        let res = TestRequest::post().uri("/signout").send_request(&app).await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 401);
+    }
+
+    #[actix_web::test]
+    async fn test_profile_missing_token() {
+        let app = test::init_service(
+            App::new()
+                .into_utoipa_app()
+                .configure(routes)
+                .into_app()
+                .app_data(web::Data::new(jsonwebtoken_service())),
+        )
+        .await;
+
+        let res = TestRequest::get().uri("/me").send_request(&app).await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 401);
+    }
+
+    #[actix_web::test]
+    async fn test_update_profile_invalid_token() {
+        let res =
+            update_profile(json!({ "name": "Valid Name" }), Some("invalid".to_string())).await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 401);
+    }
+
+    #[actix_web::test]
+    async fn test_update_profile_invalid_email_format() {
+        let res = update_profile(json!({ "email": "not-an-email" }), None).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Invalid email format"));
+    }
+
+    #[actix_web::test]
+    async fn test_update_profile_short_name() {
+        let res = update_profile(json!({ "name": "Ab" }), None).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Name must have at least 3 characters"));
+    }
+
+    #[actix_web::test]
+    async fn test_change_password_weak_password() {
+        let jsonwebtoken_service = jsonwebtoken_service();
+
+        let token = jsonwebtoken_service
+            .generate_token(&account(), "session")
+            .unwrap()
+            .token;
+
+        let app = test::init_service(
+            App::new()
+                .into_utoipa_app()
+                .configure(routes)
+                .into_app()
+                .app_data(web::Data::new(jsonwebtoken_service)),
+        )
+        .await;
+
+        let res = TestRequest::post()
+            .uri("/me/password")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(json!({
+                "current_password": "stR0ngP4ssw0rd!",
+                "new_password": "weak",
+            }))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(
+            err.message
+                .contains("Password must contain between 8 and 72 characters")
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_verify_email_missing_token() {
+        let res = verify_email("").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 400);
+    }
+
+    #[actix_web::test]
+    async fn test_verify_email_empty_token() {
+        let res = verify_email("?token=").await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Verification token must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_resend_verification_invalid_email_format() {
+        let res = resend_verification(json!({ "email": "not-an-email" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Invalid email format"));
+    }
 }
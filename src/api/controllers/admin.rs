@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use crate::api::dto::admin::{AccountPageDTO, AdminAccountDTO, ListAccountsDTO, SuspendAccountDTO};
+use crate::api::error::ApiResult;
+use crate::api::middlewares::rbac::{AccountsRead, AccountsWrite, RequirePermission};
+use crate::api::middlewares::validate::{Json, Query};
+use crate::domain::error::AppError;
+use crate::domain::services::account::AccountService;
+use crate::domain::services::account_deletion::AccountDeletionService;
+use crate::domain::services::account_status::AccountStatusService;
+
+use actix_web::{
+    HttpResponse, get, post,
+    web::{Data as State, Path},
+};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(list_accounts)
+        .service(get_account)
+        .service(suspend_account)
+        .service(reactivate_account)
+        .service(restore_account);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccountPageDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(ListAccountsDTO),
+    security(("jsonwebtoken" = [])),
+    tag = "Admin"
+)]
+#[get("/admin/accounts")]
+pub async fn list_accounts(
+    _: RequirePermission<AccountsRead>,
+    query: Query<ListAccountsDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+) -> ApiResult {
+    let (filter, page) = query.into_inner().into();
+
+    let page = account_service.list(filter, page).await?;
+
+    Ok(HttpResponse::Ok().json(AccountPageDTO::from(page)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AdminAccountDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "Account identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "Admin"
+)]
+#[get("/admin/accounts/{id}")]
+pub async fn get_account(
+    _: RequirePermission<AccountsRead>,
+    id: Path<String>,
+    account_service: State<Arc<dyn AccountService>>,
+) -> ApiResult {
+    let account = account_service.find(&id).await?;
+
+    Ok(HttpResponse::Ok().json(AdminAccountDTO::from(account)))
+}
+
+#[utoipa::path(
+    request_body = SuspendAccountDTO,
+    responses(
+        (status = 200, body = AdminAccountDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "Account identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "Admin"
+)]
+#[post("/admin/accounts/{id}/suspend")]
+pub async fn suspend_account(
+    auth: RequirePermission<AccountsWrite>,
+    id: Path<String>,
+    payload: Json<SuspendAccountDTO>,
+    account_status_service: State<Arc<dyn AccountStatusService>>,
+) -> ApiResult {
+    let suspend_dto = payload.into_inner();
+
+    let account = account_status_service
+        .suspend(&id, &auth.claims.sub, suspend_dto.reason)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AdminAccountDTO::from(account)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AdminAccountDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "Account identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "Admin"
+)]
+#[post("/admin/accounts/{id}/reactivate")]
+pub async fn reactivate_account(
+    auth: RequirePermission<AccountsWrite>,
+    id: Path<String>,
+    account_status_service: State<Arc<dyn AccountStatusService>>,
+) -> ApiResult {
+    let account = account_status_service
+        .reactivate(&id, &auth.claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AdminAccountDTO::from(account)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AdminAccountDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "Account identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "Admin"
+)]
+#[post("/admin/accounts/{id}/restore")]
+pub async fn restore_account(
+    _: RequirePermission<AccountsWrite>,
+    id: Path<String>,
+    account_deletion_service: State<Arc<dyn AccountDeletionService>>,
+) -> ApiResult {
+    let account = account_deletion_service.restore(&id).await?;
+
+    Ok(HttpResponse::Ok().json(AdminAccountDTO::from(account)))
+}
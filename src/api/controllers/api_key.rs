@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::api::dto::api_key::{ApiKeyDTO, CreateApiKeyDTO, CreatedApiKeyDTO};
+use crate::api::error::ApiResult;
+use crate::api::middlewares::auth::{RequireJsonWebToken, require_session};
+use crate::api::middlewares::validate::Json;
+use crate::domain::error::AppError;
+use crate::domain::services::account::AccountService;
+use crate::domain::services::api_key::ApiKeyService;
+
+use actix_web::{
+    HttpResponse, delete, get, post,
+    web::{Data as State, Path},
+};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(create_api_key)
+        .service(list_api_keys)
+        .service(revoke_api_key);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, body = CreatedApiKeyDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = CreateApiKeyDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "API Keys"
+)]
+#[post("/me/api-keys")]
+pub async fn create_api_key(
+    auth: RequireJsonWebToken,
+    payload: Json<CreateApiKeyDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    api_key_service: State<Arc<dyn ApiKeyService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let account = account_service.find_by_id(&auth.claims.sub).await?;
+
+    let issued = api_key_service
+        .create(&account, payload.into_inner().into())
+        .await?;
+
+    Ok(HttpResponse::Created().json(CreatedApiKeyDTO::from(issued)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = Vec<ApiKeyDTO>),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "API Keys"
+)]
+#[get("/me/api-keys")]
+pub async fn list_api_keys(
+    auth: RequireJsonWebToken,
+    api_key_service: State<Arc<dyn ApiKeyService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let api_keys: Vec<ApiKeyDTO> = api_key_service
+        .list(&auth.claims.sub)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(api_keys))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "API Key Revoked"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "API key identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "API Keys"
+)]
+#[delete("/me/api-keys/{id}")]
+pub async fn revoke_api_key(
+    auth: RequireJsonWebToken,
+    id: Path<String>,
+    api_key_service: State<Arc<dyn ApiKeyService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    api_key_service.revoke(&auth.claims.sub, &id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+
+use actix_web::{
+    HttpResponse, get,
+    http::header::{CacheControl, CacheDirective},
+    web::Data as State,
+};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(jwks);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "JSON Web Key Set used to verify access tokens"),
+    ),
+    tag = "JsonWebToken"
+)]
+#[get("/.well-known/jwks.json")]
+pub async fn jwks(jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>) -> ApiResult {
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(300),
+        ]))
+        .json(jsonwebtoken_service.jwks()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        http::StatusCode,
+        test::{self, TestRequest},
+        web,
+    };
+    use jsonwebtoken::DecodingKey;
+    use jsonwebtoken::jwk::JwkSet;
+    use tokio::sync::Mutex;
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
+
+    #[actix_web::test]
+    async fn test_jwks() {
+        let repo = Arc::new(RevokedTokenRepositoryImpl {
+            tokens: Mutex::new(vec![]),
+        });
+        let jsonwebtoken_service: Arc<dyn JsonWebTokenService> =
+            Arc::new(JsonWebTokenServiceImpl::new(
+                generate_keyring(),
+                &jsonwebtoken_config(),
+                repo,
+                Arc::new(AccountRepositoryImpl {
+                    accounts: Mutex::new(vec![]),
+                }),
+                Arc::new(SessionRepositoryImpl {
+                    sessions: Mutex::new(vec![]),
+                }),
+            ));
+
+        let app = test::init_service(
+            App::new()
+                .into_utoipa_app()
+                .configure(routes)
+                .into_app()
+                .app_data(web::Data::new(jsonwebtoken_service)),
+        )
+        .await;
+
+        let res = TestRequest::get()
+            .uri("/.well-known/jwks.json")
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let key_set: JwkSet = test::read_body_json(res).await;
+        let jwk = key_set.find("test").unwrap();
+
+        assert!(DecodingKey::from_jwk(jwk).is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use crate::api::dto::account::AccessTokenDTO;
+use crate::api::dto::mfa::{MfaSigninDTO, TotpCodeDTO, TotpEnrollmentDTO};
+use crate::api::error::ApiResult;
+use crate::api::middlewares::auth::{RequireJsonWebToken, authorization_cookie, require_session};
+use crate::api::middlewares::csrf::csrf_cookie;
+use crate::api::middlewares::validate::Json;
+use crate::domain::error::AppError;
+use crate::domain::services::account::AccountService;
+use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::refresh_token::RefreshTokenService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::totp::TotpService;
+
+use actix_web::{HttpRequest, HttpResponse, post, web::Data as State};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(signin_mfa)
+        .service(enroll_totp)
+        .service(confirm_totp);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccessTokenDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 429, body = AppError, example = json!(AppError::example_429())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = MfaSigninDTO,
+    tag = "MFA"
+)]
+#[post("/signin/mfa")]
+pub async fn signin_mfa(
+    req: HttpRequest,
+    payload: Json<MfaSigninDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    totp_service: State<Arc<dyn TotpService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    let signin_dto = payload.into_inner();
+
+    let claims = jsonwebtoken_service
+        .validate_mfa_token(&signin_dto.mfa_token)
+        .await?;
+
+    let client_ip = req.peer_addr().map(|addr| addr.ip().to_string());
+
+    // Once the failures lock the account out, the pending token is spent along with them
+    if let Err(err) = totp_service
+        .verify(&claims.sub, &signin_dto.code, client_ip.as_deref())
+        .await
+    {
+        if err.code == 429 {
+            jsonwebtoken_service.revoke_token(&claims).await?;
+        }
+
+        return Err(err);
+    }
+
+    // The pending token is single-use once the second factor has been accepted
+    jsonwebtoken_service.revoke_token(&claims).await?;
+
+    let account = account_service.find_by_id(&claims.sub).await?;
+
+    let session = session_service
+        .create((&req, account.id.as_str()).into())
+        .await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &session.id)?;
+    let refresh_token = refresh_token_service.issue(account.id, session.id).await?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = TotpEnrollmentDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "MFA"
+)]
+#[post("/me/mfa/totp")]
+pub async fn enroll_totp(
+    auth: RequireJsonWebToken,
+    account_service: State<Arc<dyn AccountService>>,
+    totp_service: State<Arc<dyn TotpService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let account = account_service.find_by_id(&auth.claims.sub).await?;
+
+    let enrollment = totp_service.enroll(&account).await?;
+
+    Ok(HttpResponse::Ok().json(TotpEnrollmentDTO::from(enrollment)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Two-Factor Authentication Enabled"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = TotpCodeDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "MFA"
+)]
+#[post("/me/mfa/totp/confirm")]
+pub async fn confirm_totp(
+    auth: RequireJsonWebToken,
+    payload: Json<TotpCodeDTO>,
+    totp_service: State<Arc<dyn TotpService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let code_dto = payload.into_inner();
+
+    totp_service
+        .confirm(&auth.claims.sub, &code_dto.code)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        dev::ServiceResponse,
+        http::StatusCode,
+        test::{self, TestRequest},
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Value, json};
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Error {
+        code: u16,
+        message: String,
+    }
+
+    async fn signin_mfa(data: Value) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri("/signin/mfa")
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_signin_mfa_empty_token() {
+        let res = signin_mfa(json!({ "mfa_token": "", "code": "123456" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("MFA token must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_signin_mfa_short_code() {
+        let res = signin_mfa(json!({ "mfa_token": "token", "code": "123" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(
+            err.message
+                .contains("Code must contain between 6 and 32 characters")
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_signin_mfa_missing_code() {
+        let res = signin_mfa(json!({ "mfa_token": "token" })).await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 400);
+    }
+}
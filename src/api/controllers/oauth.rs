@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use crate::api::dto::oauth::{
+    AuthorizationRedirectDTO, AuthorizeDTO, AuthorizeDecisionDTO, ConsentDTO, CreateOAuthClientDTO,
+    IntrospectionDTO, OAuthClientDTO, RegisteredOAuthClientDTO, TokenDTO, TokenRequestDTO,
+    TokenResponseDTO,
+};
+use crate::api::error::{ApiResult, OAuthError, OAuthResult};
+use crate::api::middlewares::auth::{RequireJsonWebToken, require_session};
+use crate::api::middlewares::rbac::{Admin, RequireRole};
+use crate::api::middlewares::validate::{Form, Json, Query};
+use crate::domain::error::AppError;
+use crate::domain::models::oauth::{AuthorizationRequest, ClientCredentials, TokenGrant};
+use crate::domain::services::account::AccountService;
+use crate::domain::services::oauth::OAuthService;
+
+use actix_web::{
+    HttpRequest, HttpResponse, delete, get,
+    http::header::{AUTHORIZATION, CACHE_CONTROL},
+    post,
+    web::{Data as State, Path},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(create_client)
+        .service(list_clients)
+        .service(delete_client)
+        .service(consent)
+        .service(authorize)
+        .service(token)
+        .service(introspect)
+        .service(revoke);
+}
+
+// Basic credentials are form-urlencoded before being joined with a colon (RFC 6749, 2.3.1)
+fn form_urldecode(value: &str) -> Option<String> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            byte => decoded.push(byte),
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+// Clients may authenticate with HTTP Basic or with credentials in the form body (RFC 6749, 2.3.1)
+fn client_credentials(
+    req: &HttpRequest,
+    client_id: Option<&str>,
+    client_secret: Option<&str>,
+) -> Result<ClientCredentials, AppError> {
+    if let Some(header) = req.headers().get(AUTHORIZATION) {
+        let decoded = header
+            .to_str()
+            .ok()
+            .and_then(|header| header.strip_prefix("Basic "))
+            .and_then(|encoded| STANDARD.decode(encoded.trim()).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let (client_id, client_secret) =
+            decoded.split_once(':').ok_or_else(AppError::Unauthorized)?;
+
+        return Ok(ClientCredentials {
+            client_id: form_urldecode(client_id).ok_or_else(AppError::Unauthorized)?,
+            client_secret: Some(form_urldecode(client_secret).ok_or_else(AppError::Unauthorized)?),
+        });
+    }
+
+    Ok(ClientCredentials {
+        client_id: client_id.ok_or_else(AppError::Unauthorized)?.to_owned(),
+        client_secret: client_secret.map(ToOwned::to_owned),
+    })
+}
+
+#[utoipa::path(
+    responses(
+        (status = 201, body = RegisteredOAuthClientDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = CreateOAuthClientDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "OAuth"
+)]
+#[post("/oauth/clients")]
+pub async fn create_client(
+    _: RequireRole<Admin>,
+    auth: RequireJsonWebToken,
+    payload: Json<CreateOAuthClientDTO>,
+    account_service: State<Arc<dyn AccountService>>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> ApiResult {
+    // Client secrets are credentials too, so they are never minted through an API key
+    require_session(&auth)?;
+
+    let account = account_service.find_by_id(&auth.claims.sub).await?;
+
+    let issued = oauth_service
+        .register_client(&account, payload.into_inner().into())
+        .await?;
+
+    Ok(HttpResponse::Created().json(RegisteredOAuthClientDTO::from(issued)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = Vec<OAuthClientDTO>),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "OAuth"
+)]
+#[get("/oauth/clients")]
+pub async fn list_clients(
+    auth: RequireRole<Admin>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> ApiResult {
+    let clients: Vec<OAuthClientDTO> = oauth_service
+        .list_clients(&auth.claims.sub)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(clients))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "OAuth Client Deleted"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "OAuth client identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "OAuth"
+)]
+#[delete("/oauth/clients/{id}")]
+pub async fn delete_client(
+    auth: RequireRole<Admin>,
+    id: Path<String>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> ApiResult {
+    oauth_service.delete_client(&auth.claims.sub, &id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = ConsentDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(AuthorizeDTO),
+    security(("jsonwebtoken" = [])),
+    tag = "OAuth"
+)]
+#[get("/oauth/authorize")]
+pub async fn consent(
+    query: Query<AuthorizeDTO>,
+    auth: RequireJsonWebToken,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let request = AuthorizationRequest::try_from(query.into_inner())?;
+
+    let client = oauth_service.validate_authorization(&request).await?;
+
+    Ok(HttpResponse::Ok().json(ConsentDTO::from((client, request))))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AuthorizationRedirectDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = AuthorizeDecisionDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "OAuth"
+)]
+#[post("/oauth/authorize")]
+pub async fn authorize(
+    payload: Json<AuthorizeDecisionDTO>,
+    auth: RequireJsonWebToken,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let decision = payload.into_inner();
+
+    let url = oauth_service
+        .authorize(
+            &auth.claims.sub,
+            decision.request.try_into()?,
+            decision.approved,
+        )
+        .await?;
+
+    // The consent page is a script, so it navigates to the client itself rather than following a redirect
+    Ok(HttpResponse::Ok().json(AuthorizationRedirectDTO { redirect_uri: url }))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = TokenResponseDTO),
+        (status = 400, body = OAuthError, example = json!(OAuthError::example_400())),
+        (status = 401, body = OAuthError, example = json!(OAuthError::example_401())),
+        (status = 500, body = OAuthError, example = json!(OAuthError::example_500())),
+        (status = 503, body = OAuthError, example = json!(OAuthError::example_503()))
+    ),
+    request_body(content = TokenRequestDTO, content_type = "application/x-www-form-urlencoded"),
+    tag = "OAuth"
+)]
+#[post("/oauth/token")]
+pub async fn token(
+    req: HttpRequest,
+    form: Result<Form<TokenRequestDTO>, AppError>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> OAuthResult {
+    let token_dto = form?.into_inner();
+
+    let grant = TokenGrant::try_from(&token_dto)?;
+    let credentials = client_credentials(
+        &req,
+        token_dto.client_id.as_deref(),
+        token_dto.client_secret.as_deref(),
+    )?;
+
+    let token = oauth_service.token(credentials, grant).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .json(TokenResponseDTO::from(token)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = IntrospectionDTO),
+        (status = 400, body = OAuthError, example = json!(OAuthError::example_400())),
+        (status = 401, body = OAuthError, example = json!(OAuthError::example_401())),
+        (status = 500, body = OAuthError, example = json!(OAuthError::example_500())),
+        (status = 503, body = OAuthError, example = json!(OAuthError::example_503()))
+    ),
+    request_body(content = TokenDTO, content_type = "application/x-www-form-urlencoded"),
+    tag = "OAuth"
+)]
+#[post("/oauth/introspect")]
+pub async fn introspect(
+    req: HttpRequest,
+    form: Result<Form<TokenDTO>, AppError>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> OAuthResult {
+    let token_dto = form?.into_inner();
+
+    let credentials = client_credentials(
+        &req,
+        token_dto.client_id.as_deref(),
+        token_dto.client_secret.as_deref(),
+    )?;
+
+    let introspection = oauth_service
+        .introspect(credentials, &token_dto.token)
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .json(IntrospectionDTO::from(introspection)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Token Revoked"),
+        (status = 400, body = OAuthError, example = json!(OAuthError::example_400())),
+        (status = 401, body = OAuthError, example = json!(OAuthError::example_401())),
+        (status = 500, body = OAuthError, example = json!(OAuthError::example_500())),
+        (status = 503, body = OAuthError, example = json!(OAuthError::example_503()))
+    ),
+    request_body(content = TokenDTO, content_type = "application/x-www-form-urlencoded"),
+    tag = "OAuth"
+)]
+#[post("/oauth/revoke")]
+pub async fn revoke(
+    req: HttpRequest,
+    form: Result<Form<TokenDTO>, AppError>,
+    oauth_service: State<Arc<dyn OAuthService>>,
+) -> OAuthResult {
+    let token_dto = form?.into_inner();
+
+    let credentials = client_credentials(
+        &req,
+        token_dto.client_id.as_deref(),
+        token_dto.client_secret.as_deref(),
+    )?;
+
+    oauth_service.revoke(credentials, &token_dto.token).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        dev::ServiceResponse,
+        http::{StatusCode, header::ContentType},
+        test::{self, TestRequest},
+    };
+    use serde::{Deserialize, Serialize};
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Error {
+        error: String,
+        error_description: String,
+    }
+
+    async fn post_form(uri: &str, body: &'static str) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri(uri)
+            .insert_header(ContentType::form_url_encoded())
+            .set_payload(body)
+            .send_request(&app)
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_token_missing_grant_type() {
+        let res = post_form("/oauth/token", "code=abc").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.error, "invalid_request");
+    }
+
+    #[actix_web::test]
+    async fn test_token_unsupported_grant_type() {
+        let res = post_form("/oauth/token", "grant_type=password").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.error, "unsupported_grant_type");
+    }
+
+    #[actix_web::test]
+    async fn test_introspect_empty_token() {
+        let res = post_form("/oauth/introspect", "token=").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.error, "invalid_request");
+        assert!(err.error_description.contains("Token must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_revoke_empty_token() {
+        let res = post_form("/oauth/revoke", "token=").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.error, "invalid_request");
+    }
+
+    #[test]
+    fn test_basic_credentials_decoded() {
+        let req = TestRequest::default()
+            .insert_header((
+                AUTHORIZATION,
+                format!("Basic {}", STANDARD.encode("my+client:s%3Acr%25t")),
+            ))
+            .to_http_request();
+
+        let credentials = client_credentials(&req, None, None).unwrap();
+
+        assert_eq!(credentials.client_id, "my client");
+        assert_eq!(credentials.client_secret.as_deref(), Some("s:cr%t"));
+    }
+
+    #[test]
+    fn test_basic_credentials_malformed() {
+        let req = TestRequest::default()
+            .insert_header((
+                AUTHORIZATION,
+                format!("Basic {}", STANDARD.encode("client:secret%zz")),
+            ))
+            .to_http_request();
+
+        assert_eq!(
+            client_credentials(&req, None, None).err(),
+            Some(AppError::Unauthorized())
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use crate::api::dto::account::AccessTokenDTO;
+use crate::api::dto::mfa::MfaPendingDTO;
+use crate::api::dto::oidc::OidcCallbackDTO;
+use crate::api::error::ApiResult;
+use crate::api::middlewares::auth::{RequireJsonWebToken, authorization_cookie, require_session};
+use crate::api::middlewares::csrf::csrf_cookie;
+use crate::api::middlewares::validate::Query;
+use crate::domain::error::AppError;
+use crate::domain::models::oidc::OidcAuthorization;
+use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::oidc::OidcService;
+use crate::domain::services::refresh_token::RefreshTokenService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::totp::TotpService;
+
+use actix_web::{
+    HttpRequest, HttpResponse,
+    cookie::{Cookie, SameSite, time::OffsetDateTime},
+    get,
+    http::header::LOCATION,
+    web::{Data as State, Path},
+};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+const OIDC_STATE_COOKIE: &str = "oidc_state";
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(authorize).service(link).service(callback);
+}
+
+// Ties the callback to the browser that started the flow, Lax so the provider redirect carries it
+fn state_cookie(authorization: &OidcAuthorization) -> Cookie<'static> {
+    Cookie::build(OIDC_STATE_COOKIE, authorization.state.clone())
+        .http_only(true)
+        .secure(true)
+        .path("/api/v1/oidc")
+        .same_site(SameSite::Lax)
+        .expires(OffsetDateTime::from_unix_timestamp(authorization.expiration).unwrap())
+        .finish()
+}
+
+fn removal_state_cookie() -> Cookie<'static> {
+    let mut cookie = Cookie::build(OIDC_STATE_COOKIE, "")
+        .http_only(true)
+        .secure(true)
+        .path("/api/v1/oidc")
+        .same_site(SameSite::Lax)
+        .finish();
+
+    cookie.make_removal();
+
+    cookie
+}
+
+fn redirect(authorization: OidcAuthorization) -> HttpResponse {
+    HttpResponse::Found()
+        .cookie(state_cookie(&authorization))
+        .insert_header((LOCATION, authorization.url))
+        .finish()
+}
+
+#[utoipa::path(
+    responses(
+        (status = 302, description = "Redirect to Identity Provider"),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("provider" = String, Path, description = "Identity provider name")),
+    tag = "OIDC"
+)]
+#[get("/oidc/{provider}/authorize")]
+pub async fn authorize(
+    provider: Path<String>,
+    oidc_service: State<Arc<dyn OidcService>>,
+) -> ApiResult {
+    let authorization = oidc_service.authorize(&provider, None).await?;
+
+    Ok(redirect(authorization))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 302, description = "Redirect to Identity Provider"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("provider" = String, Path, description = "Identity provider name")),
+    security(("jsonwebtoken" = [])),
+    tag = "OIDC"
+)]
+#[get("/me/oidc/{provider}/link")]
+pub async fn link(
+    auth: RequireJsonWebToken,
+    provider: Path<String>,
+    oidc_service: State<Arc<dyn OidcService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let authorization = oidc_service
+        .authorize(&provider, Some(&auth.claims.sub))
+        .await?;
+
+    Ok(redirect(authorization))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccessTokenDTO),
+        (status = 202, body = MfaPendingDTO, description = "Second Factor Required"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 410, body = AppError, example = json!(AppError::example_410())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 423, body = AppError, example = json!(AppError::example_423())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("provider" = String, Path, description = "Identity provider name"), OidcCallbackDTO),
+    tag = "OIDC"
+)]
+#[get("/oidc/{provider}/callback")]
+pub async fn callback(
+    req: HttpRequest,
+    provider: Path<String>,
+    query: Query<OidcCallbackDTO>,
+    oidc_service: State<Arc<dyn OidcService>>,
+    totp_service: State<Arc<dyn TotpService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    let callback_dto = query.into_inner();
+
+    // A state replayed from another browser has no matching cookie
+    if req
+        .cookie(OIDC_STATE_COOKIE)
+        .is_none_or(|cookie| cookie.value() != callback_dto.state)
+    {
+        return Err(AppError::Unauthorized());
+    }
+
+    let account = oidc_service
+        .callback(&provider, &callback_dto.code, &callback_dto.state)
+        .await?;
+
+    if totp_service.is_enabled(&account.id).await? {
+        let mfa_token = jsonwebtoken_service.generate_mfa_token(&account)?;
+
+        return Ok(HttpResponse::Accepted()
+            .cookie(removal_state_cookie())
+            .json(MfaPendingDTO::from(mfa_token)));
+    }
+
+    let session = session_service
+        .create((&req, account.id.as_str()).into())
+        .await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &session.id)?;
+    let refresh_token = refresh_token_service.issue(account.id, session.id).await?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(removal_state_cookie())
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        dev::ServiceResponse,
+        http::StatusCode,
+        test::{self, TestRequest},
+    };
+    use serde::{Deserialize, Serialize};
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Error {
+        code: u16,
+        message: String,
+    }
+
+    async fn get(uri: &str) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::get().uri(uri).send_request(&app).await
+    }
+
+    #[actix_web::test]
+    async fn test_callback_empty_code() {
+        let res = get("/oidc/google/callback?code=&state=abc").await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Authorization code must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_callback_missing_state() {
+        let res = get("/oidc/google/callback?code=abc").await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 400);
+    }
+}
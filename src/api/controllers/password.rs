@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use crate::api::dto::password::{ForgotPasswordDTO, ResetPasswordDTO};
+use crate::api::error::ApiResult;
+use crate::api::middlewares::validate::Json;
+use crate::domain::error::AppError;
+use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::password_reset::PasswordResetService;
+use crate::domain::services::session::SessionService;
+
+use actix_web::{HttpResponse, post, web::Data as State};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(forgot).service(reset);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 202, description = "Reset Email Sent If The Account Exists"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 422, body = AppError, example = json!(AppError::example_422()))
+    ),
+    request_body = ForgotPasswordDTO,
+    tag = "Password"
+)]
+#[post("/password/forgot")]
+pub async fn forgot(
+    payload: Json<ForgotPasswordDTO>,
+    password_reset_service: State<Arc<dyn PasswordResetService>>,
+) -> ApiResult {
+    let forgot_dto = payload.into_inner();
+    let password_reset_service = password_reset_service.get_ref().clone();
+
+    // Known emails cost a token write and a mail, answering before that work keeps the response
+    // time from revealing which accounts exist
+    actix_web::rt::spawn(async move {
+        if let Err(err) = password_reset_service.forgot(&forgot_dto.email).await {
+            tracing::warn!(error = ?err, "password reset request failed");
+        }
+    });
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Password Reset"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = ResetPasswordDTO,
+    tag = "Password"
+)]
+#[post("/password/reset")]
+pub async fn reset(
+    payload: Json<ResetPasswordDTO>,
+    password_reset_service: State<Arc<dyn PasswordResetService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    let reset_dto = payload.into_inner();
+
+    let account = password_reset_service.reset(reset_dto.into()).await?;
+
+    session_service.revoke_account(&account.id).await?;
+
+    if let Some(password_changed_at) = account.password_changed_at {
+        jsonwebtoken_service.revoke_issued_before(&account.id, password_changed_at);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        dev::ServiceResponse,
+        http::StatusCode,
+        test::{self, TestRequest},
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Value, json};
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Error {
+        code: u16,
+        message: String,
+    }
+
+    async fn post(uri: &str, data: Value) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri(uri)
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_forgot_invalid_email_format() {
+        let res = post("/password/forgot", json!({ "email": "not-an-email" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Invalid email format"));
+    }
+
+    #[actix_web::test]
+    async fn test_reset_empty_token() {
+        let res = post(
+            "/password/reset",
+            json!({ "token": "", "password": "stR0ngP4ssw0rd!" }),
+        )
+        .await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Reset token must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_reset_weak_password() {
+        let res = post(
+            "/password/reset",
+            json!({ "token": "token", "password": "weak" }),
+        )
+        .await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(
+            err.message
+                .contains("Password must contain between 8 and 72 characters")
+        );
+    }
+}
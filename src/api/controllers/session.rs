@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::api::dto::session::SessionDTO;
+use crate::api::error::ApiResult;
+use crate::api::middlewares::auth::{RequireJsonWebToken, require_session};
+use crate::domain::error::AppError;
+use crate::domain::services::session::SessionService;
+
+use actix_web::{
+    HttpResponse, delete, get,
+    web::{Data as State, Path},
+};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(list_sessions).service(revoke_session);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = Vec<SessionDTO>),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "Sessions"
+)]
+#[get("/me/sessions")]
+pub async fn list_sessions(
+    auth: RequireJsonWebToken,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let sessions: Vec<SessionDTO> = session_service
+        .list(&auth.claims.sub)
+        .await?
+        .into_iter()
+        .map(|session| {
+            let current = auth.claims.sid.as_ref() == Some(&session.id);
+
+            SessionDTO::from((session, current))
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Session Revoked"),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 404, body = AppError, example = json!(AppError::example_404())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    params(("id" = String, Path, description = "Session identifier")),
+    security(("jsonwebtoken" = [])),
+    tag = "Sessions"
+)]
+#[delete("/me/sessions/{id}")]
+pub async fn revoke_session(
+    auth: RequireJsonWebToken,
+    id: Path<String>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    session_service.revoke(&auth.claims.sub, &id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
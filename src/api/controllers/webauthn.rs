@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use crate::api::dto::account::AccessTokenDTO;
+use crate::api::dto::mfa::MfaPendingDTO;
+use crate::api::dto::webauthn::{
+    AssertCredentialDTO, AuthenticationOptionsDTO, AuthenticationOptionsRequestDTO,
+    RegisterCredentialDTO, RegistrationOptionsDTO,
+};
+use crate::api::error::ApiResult;
+use crate::api::middlewares::auth::{RequireJsonWebToken, authorization_cookie, require_session};
+use crate::api::middlewares::csrf::csrf_cookie;
+use crate::api::middlewares::validate::Json;
+use crate::domain::error::AppError;
+use crate::domain::services::account::AccountService;
+use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::refresh_token::RefreshTokenService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::totp::TotpService;
+use crate::domain::services::webauthn::WebAuthnService;
+
+use actix_web::{HttpRequest, HttpResponse, post, web::Data as State};
+
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn routes(cfg: &mut ServiceConfig) {
+    cfg.service(registration_options)
+        .service(register)
+        .service(authentication_options)
+        .service(authenticate);
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = RegistrationOptionsDTO),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    security(("jsonwebtoken" = [])),
+    tag = "WebAuthn"
+)]
+#[post("/me/webauthn/register/options")]
+pub async fn registration_options(
+    auth: RequireJsonWebToken,
+    account_service: State<Arc<dyn AccountService>>,
+    webauthn_service: State<Arc<dyn WebAuthnService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let account = account_service.find_by_id(&auth.claims.sub).await?;
+
+    let options = webauthn_service.start_registration(&account).await?;
+
+    Ok(HttpResponse::Ok().json(RegistrationOptionsDTO::from(options)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 204, description = "Passkey Registered"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 409, body = AppError, example = json!(AppError::example_409())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = RegisterCredentialDTO,
+    security(("jsonwebtoken" = [])),
+    tag = "WebAuthn"
+)]
+#[post("/me/webauthn/register")]
+pub async fn register(
+    auth: RequireJsonWebToken,
+    payload: Json<RegisterCredentialDTO>,
+    webauthn_service: State<Arc<dyn WebAuthnService>>,
+) -> ApiResult {
+    require_session(&auth)?;
+
+    let credential_dto = payload.into_inner();
+
+    webauthn_service
+        .finish_registration(&auth.claims.sub, credential_dto.into())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AuthenticationOptionsDTO),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = AuthenticationOptionsRequestDTO,
+    tag = "WebAuthn"
+)]
+#[post("/webauthn/login/options")]
+pub async fn authentication_options(
+    payload: Json<AuthenticationOptionsRequestDTO>,
+    webauthn_service: State<Arc<dyn WebAuthnService>>,
+) -> ApiResult {
+    let options_dto = payload.into_inner();
+
+    let options = webauthn_service
+        .start_authentication(options_dto.email.as_deref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(AuthenticationOptionsDTO::from(options)))
+}
+
+#[utoipa::path(
+    responses(
+        (status = 200, body = AccessTokenDTO),
+        (status = 202, body = MfaPendingDTO, description = "Second Factor Required"),
+        (status = 400, body = AppError, example = json!(AppError::example_400())),
+        (status = 401, body = AppError, example = json!(AppError::example_401())),
+        (status = 403, body = AppError, example = json!(AppError::example_403())),
+        (status = 410, body = AppError, example = json!(AppError::example_410())),
+        (status = 422, body = AppError, example = json!(AppError::example_422())),
+        (status = 423, body = AppError, example = json!(AppError::example_423())),
+        (status = 500, body = AppError, example = json!(AppError::example_500())),
+        (status = 503, body = AppError, example = json!(AppError::example_503()))
+    ),
+    request_body = AssertCredentialDTO,
+    tag = "WebAuthn"
+)]
+#[post("/webauthn/login")]
+pub async fn authenticate(
+    req: HttpRequest,
+    payload: Json<AssertCredentialDTO>,
+    webauthn_service: State<Arc<dyn WebAuthnService>>,
+    totp_service: State<Arc<dyn TotpService>>,
+    jsonwebtoken_service: State<Arc<dyn JsonWebTokenService>>,
+    refresh_token_service: State<Arc<dyn RefreshTokenService>>,
+    session_service: State<Arc<dyn SessionService>>,
+) -> ApiResult {
+    let credential_dto = payload.into_inner();
+
+    let account = webauthn_service
+        .finish_authentication(credential_dto.into())
+        .await?;
+
+    // User verification is not required from the authenticator, so a passkey only proves possession
+    if totp_service.is_enabled(&account.id).await? {
+        let mfa_token = jsonwebtoken_service.generate_mfa_token(&account)?;
+
+        return Ok(HttpResponse::Accepted().json(MfaPendingDTO::from(mfa_token)));
+    }
+
+    let session = session_service
+        .create((&req, account.id.as_str()).into())
+        .await?;
+
+    let access_token = jsonwebtoken_service.generate_token(&account, &session.id)?;
+    let refresh_token = refresh_token_service.issue(account.id, session.id).await?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(authorization_cookie(&access_token))
+        .cookie(csrf_cookie(&access_token))
+        .json(AccessTokenDTO::from((access_token, refresh_token))))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App,
+        dev::ServiceResponse,
+        http::StatusCode,
+        test::{self, TestRequest},
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Value, json};
+    use utoipa_actix_web::AppExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Error {
+        code: u16,
+        message: String,
+    }
+
+    async fn post(uri: &str, data: Value) -> ServiceResponse {
+        let app =
+            test::init_service(App::new().into_utoipa_app().configure(routes).into_app()).await;
+
+        TestRequest::post()
+            .uri(uri)
+            .set_json(data)
+            .send_request(&app)
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_authentication_options_invalid_email() {
+        let res = post("/webauthn/login/options", json!({ "email": "email" })).await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+    }
+
+    #[actix_web::test]
+    async fn test_authenticate_empty_signature() {
+        let res = post(
+            "/webauthn/login",
+            json!({
+                "id": "q1ZbA6lDgIhH3Y0Zl2s7_w",
+                "response": {
+                    "clientDataJSON": "e30",
+                    "authenticatorData": "AAAA",
+                    "signature": ""
+                }
+            }),
+        )
+        .await;
+
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Signature must not be empty"));
+    }
+
+    #[actix_web::test]
+    async fn test_authenticate_missing_response() {
+        let res = post("/webauthn/login", json!({ "id": "q1ZbA6lDgIhH3Y0Zl2s7_w" })).await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 400);
+    }
+}
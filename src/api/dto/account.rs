@@ -1,9 +1,11 @@
 use crate::api::dto::validation::{is_email, is_name, is_password};
-use crate::domain::models::account::CreateAccount;
 use crate::domain::models::account::{Account, Credentials};
+use crate::domain::models::account::{ChangePassword, CreateAccount, UpdateAccount};
+use crate::domain::models::jsonwebtoken::AccessToken;
+use crate::domain::models::refresh_token::RefreshToken;
 use serde::Deserialize;
 use serde::Serialize;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -28,6 +30,41 @@ pub struct CreateAccountDTO {
     pub password: String,
 }
 
+#[derive(Debug, Validate, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailDTO {
+    #[validate(length(min = 1, message = "Verification token must not be empty"))]
+    pub token: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct ResendVerificationDTO {
+    #[validate(custom(function = "is_email"))]
+    #[schema(examples("your@email.com"))]
+    pub email: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct UpdateAccountDTO {
+    #[validate(custom(function = "is_name"))]
+    #[schema(examples("your_name"))]
+    pub name: Option<String>,
+
+    #[validate(custom(function = "is_email"))]
+    #[schema(examples("your@email.com"))]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct ChangePasswordDTO {
+    #[schema(examples("stR0ngP4ssw0rd!"))]
+    pub current_password: String,
+
+    #[validate(custom(function = "is_password"))]
+    #[schema(examples("n3wStR0ngP4ssw0rd!"))]
+    pub new_password: String,
+}
+
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CredentialsDTO {
     #[validate(custom(function = "is_email"))]
@@ -38,12 +75,23 @@ pub struct CredentialsDTO {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct RefreshTokenDTO {
+    #[validate(length(min = 1, message = "Refresh token must not be empty"))]
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    pub refresh_token: String,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AccessTokenDTO {
     #[schema(examples("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9"))]
     pub token: String,
     #[schema(examples(1385903))]
     pub expires_at: i64,
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    pub refresh_token: String,
+    #[schema(examples(3977903))]
+    pub refresh_expires_at: i64,
 }
 
 impl From<Account> for AccountDTO {
@@ -56,6 +104,17 @@ impl From<Account> for AccountDTO {
     }
 }
 
+impl From<(AccessToken, RefreshToken)> for AccessTokenDTO {
+    fn from((access_token, refresh_token): (AccessToken, RefreshToken)) -> Self {
+        AccessTokenDTO {
+            token: access_token.token,
+            expires_at: access_token.expiration,
+            refresh_token: refresh_token.token,
+            refresh_expires_at: refresh_token.expiration,
+        }
+    }
+}
+
 impl From<CreateAccountDTO> for CreateAccount {
     fn from(create_account: CreateAccountDTO) -> Self {
         CreateAccount {
@@ -66,6 +125,24 @@ impl From<CreateAccountDTO> for CreateAccount {
     }
 }
 
+impl From<UpdateAccountDTO> for UpdateAccount {
+    fn from(update_account: UpdateAccountDTO) -> Self {
+        UpdateAccount {
+            name: update_account.name,
+            email: update_account.email,
+        }
+    }
+}
+
+impl From<ChangePasswordDTO> for ChangePassword {
+    fn from(passwords: ChangePasswordDTO) -> Self {
+        ChangePassword {
+            current_password: passwords.current_password,
+            new_password: passwords.new_password,
+        }
+    }
+}
+
 impl From<CredentialsDTO> for Credentials {
     fn from(credentials: CredentialsDTO) -> Self {
         Credentials {
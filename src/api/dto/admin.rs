@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::models::account::{
+    Account, AccountFilter, AccountSortField, AccountStatus, Permission, Role,
+};
+use crate::domain::models::pagination::{
+    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, Page, PageRequest, Sort, SortOrder,
+};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountSortDTO {
+    #[default]
+    CreatedAt,
+    Email,
+    Name,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrderDTO {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Validate, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAccountsDTO {
+    // Id of the last account of the previous page
+    pub cursor: Option<String>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE))]
+    #[param(example = 20)]
+    pub limit: Option<usize>,
+
+    #[param(example = "spacecraft.com")]
+    pub email: Option<String>,
+
+    #[param(example = "robert")]
+    pub name: Option<String>,
+
+    #[param(example = 1385903)]
+    pub created_after: Option<i64>,
+
+    #[param(example = 3977903)]
+    pub created_before: Option<i64>,
+
+    #[serde(default)]
+    #[param(example = false)]
+    pub include_deleted: bool,
+
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: AccountSortDTO,
+
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrderDTO,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct SuspendAccountDTO {
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Reason must contain between 1 and 500 characters"
+    ))]
+    #[schema(examples("Repeated spam reported by other users"))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminAccountDTO {
+    #[schema(examples("8f3k2m9x1q7z"))]
+    id: String,
+    #[schema(examples("your_name"))]
+    name: String,
+    #[schema(examples("your@email.com"))]
+    email: String,
+    #[schema(value_type = Vec<String>, examples(json!(["user"])))]
+    roles: Vec<Role>,
+    #[schema(value_type = Vec<String>, examples(json!(["accounts:read"])))]
+    permissions: Vec<Permission>,
+    #[schema(examples(1385903))]
+    email_verified_at: Option<i64>,
+    #[schema(value_type = String, examples("active"))]
+    status: AccountStatus,
+    #[schema(examples(1385903))]
+    deleted_at: Option<i64>,
+    #[schema(examples(1385903))]
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountPageDTO {
+    items: Vec<AdminAccountDTO>,
+    // Passed back as `cursor` to fetch the next page, missing on the last one
+    #[schema(examples("8f3k2m9x1q7z"))]
+    next_cursor: Option<String>,
+}
+
+impl From<Account> for AdminAccountDTO {
+    fn from(account: Account) -> Self {
+        AdminAccountDTO {
+            id: account.id,
+            name: account.name,
+            email: account.email,
+            roles: account.roles,
+            permissions: account.permissions,
+            email_verified_at: account.email_verified_at,
+            status: account.status,
+            deleted_at: account.deleted_at,
+            created_at: account.created_at,
+        }
+    }
+}
+
+impl From<Page<Account>> for AccountPageDTO {
+    fn from(page: Page<Account>) -> Self {
+        AccountPageDTO {
+            items: page.items.into_iter().map(Into::into).collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+impl From<AccountSortDTO> for AccountSortField {
+    fn from(sort: AccountSortDTO) -> Self {
+        match sort {
+            AccountSortDTO::CreatedAt => AccountSortField::CreatedAt,
+            AccountSortDTO::Email => AccountSortField::Email,
+            AccountSortDTO::Name => AccountSortField::Name,
+        }
+    }
+}
+
+impl From<SortOrderDTO> for SortOrder {
+    fn from(order: SortOrderDTO) -> Self {
+        match order {
+            SortOrderDTO::Asc => SortOrder::Asc,
+            SortOrderDTO::Desc => SortOrder::Desc,
+        }
+    }
+}
+
+impl From<ListAccountsDTO> for (AccountFilter, PageRequest<AccountSortField>) {
+    fn from(query: ListAccountsDTO) -> Self {
+        (
+            AccountFilter {
+                email: query.email,
+                name: query.name,
+                created_after: query.created_after,
+                created_before: query.created_before,
+                include_deleted: query.include_deleted,
+            },
+            PageRequest {
+                cursor: query.cursor,
+                limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+                sort: Sort {
+                    field: query.sort.into(),
+                    order: query.order.into(),
+                },
+            },
+        )
+    }
+}
@@ -0,0 +1,81 @@
+use crate::domain::models::account::Permission;
+use crate::domain::models::api_key::{ApiKey, IssuedApiKey, NewApiKey};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct CreateApiKeyDTO {
+    #[validate(length(
+        min = 1,
+        max = 64,
+        message = "Name must contain between 1 and 64 characters"
+    ))]
+    #[schema(examples("deploy-bot"))]
+    pub name: String,
+
+    #[schema(value_type = Option<Vec<String>>, examples(json!(["accounts:read"])))]
+    pub scopes: Option<Vec<Permission>>,
+
+    #[schema(examples(1893456000))]
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeyDTO {
+    #[schema(examples("4k9w2m7x1q8z"))]
+    id: String,
+    #[schema(examples("deploy-bot"))]
+    name: String,
+    #[schema(examples("sak_0n7cQ7"))]
+    prefix: String,
+    #[schema(value_type = Option<Vec<String>>, examples(json!(["accounts:read"])))]
+    scopes: Option<Vec<Permission>>,
+    #[schema(examples(1893456000))]
+    expires_at: Option<i64>,
+    #[schema(examples(1385903))]
+    last_used_at: Option<i64>,
+    #[schema(examples(1385903))]
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKeyDTO {
+    #[schema(examples("sak_0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    key: String,
+    #[serde(flatten)]
+    api_key: ApiKeyDTO,
+}
+
+impl From<ApiKey> for ApiKeyDTO {
+    fn from(api_key: ApiKey) -> Self {
+        ApiKeyDTO {
+            id: api_key.id,
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes,
+            expires_at: api_key.expiration,
+            last_used_at: api_key.last_used_at,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+impl From<IssuedApiKey> for CreatedApiKeyDTO {
+    fn from(issued: IssuedApiKey) -> Self {
+        CreatedApiKeyDTO {
+            key: issued.key,
+            api_key: issued.api_key.into(),
+        }
+    }
+}
+
+impl From<CreateApiKeyDTO> for NewApiKey {
+    fn from(create_api_key: CreateApiKeyDTO) -> Self {
+        NewApiKey {
+            name: create_api_key.name,
+            scopes: create_api_key.scopes,
+            expiration: create_api_key.expires_at,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::domain::models::jsonwebtoken::AccessToken;
+use crate::domain::models::totp::TotpEnrollment;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaPendingDTO {
+    #[schema(examples("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9"))]
+    pub mfa_token: String,
+    #[schema(examples(1385903))]
+    pub expires_at: i64,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct MfaSigninDTO {
+    #[validate(length(min = 1, message = "MFA token must not be empty"))]
+    #[schema(examples("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9"))]
+    pub mfa_token: String,
+
+    #[validate(length(
+        min = 6,
+        max = 32,
+        message = "Code must contain between 6 and 32 characters"
+    ))]
+    #[schema(examples("123456"))]
+    pub code: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct TotpCodeDTO {
+    #[validate(length(equal = 6, message = "Code must contain 6 digits"))]
+    #[schema(examples("123456"))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TotpEnrollmentDTO {
+    #[schema(examples("JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP"))]
+    pub secret: String,
+    #[schema(examples(
+        "otpauth://totp/surreal-actix:your%40email.com?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=surreal-actix"
+    ))]
+    pub uri: String,
+    #[schema(examples(json!(["3f9a1-c07d2", "8b2e4-51a9f"])))]
+    pub recovery_codes: Vec<String>,
+}
+
+impl From<AccessToken> for MfaPendingDTO {
+    fn from(mfa_token: AccessToken) -> Self {
+        MfaPendingDTO {
+            mfa_token: mfa_token.token,
+            expires_at: mfa_token.expiration,
+        }
+    }
+}
+
+impl From<TotpEnrollment> for TotpEnrollmentDTO {
+    fn from(enrollment: TotpEnrollment) -> Self {
+        TotpEnrollmentDTO {
+            secret: enrollment.secret,
+            uri: enrollment.uri,
+            recovery_codes: enrollment.recovery_codes,
+        }
+    }
+}
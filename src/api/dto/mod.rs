@@ -1,2 +1,10 @@
 pub mod account;
+pub mod admin;
+pub mod api_key;
+pub mod mfa;
+pub mod oauth;
+pub mod oidc;
+pub mod password;
+pub mod session;
 pub mod validation;
+pub mod webauthn;
@@ -0,0 +1,339 @@
+use std::borrow::Cow;
+
+use crate::domain::error::AppError;
+use crate::domain::models::account::Permission;
+use crate::domain::models::oauth::{
+    AuthorizationRequest, GrantType, IssuedOAuthClient, NewOAuthClient, OAuthClient, OAuthToken,
+    TokenGrant, TokenIntrospection, TokenType, format_scope, parse_scope,
+};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct CreateOAuthClientDTO {
+    #[validate(length(
+        min = 1,
+        max = 64,
+        message = "Name must contain between 1 and 64 characters"
+    ))]
+    #[schema(examples("billing-dashboard"))]
+    pub name: String,
+
+    #[schema(examples(true))]
+    pub confidential: bool,
+
+    #[validate(custom(function = "is_redirect_uris"))]
+    #[schema(examples(json!(["https://billing.example.com/callback"])))]
+    pub redirect_uris: Vec<String>,
+
+    #[validate(length(min = 1, message = "At least one grant type is required"))]
+    #[schema(value_type = Vec<String>, examples(json!(["authorization_code", "refresh_token"])))]
+    pub grant_types: Vec<GrantType>,
+
+    #[schema(value_type = Vec<String>, examples(json!(["accounts:read"])))]
+    pub scopes: Vec<Permission>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OAuthClientDTO {
+    #[schema(examples("4k9w2m7x1q8z"))]
+    client_id: String,
+    #[schema(examples("billing-dashboard"))]
+    name: String,
+    #[schema(examples(true))]
+    confidential: bool,
+    #[schema(examples(json!(["https://billing.example.com/callback"])))]
+    redirect_uris: Vec<String>,
+    #[schema(value_type = Vec<String>, examples(json!(["authorization_code", "refresh_token"])))]
+    grant_types: Vec<GrantType>,
+    #[schema(value_type = Vec<String>, examples(json!(["accounts:read"])))]
+    scopes: Vec<Permission>,
+    #[schema(examples(1385903))]
+    created_at: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegisteredOAuthClientDTO {
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    client_secret: Option<String>,
+    #[serde(flatten)]
+    client: OAuthClientDTO,
+}
+
+#[derive(Debug, Validate, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizeDTO {
+    #[validate(custom(function = "is_code_response_type"))]
+    #[schema(examples("code"))]
+    pub response_type: String,
+
+    #[schema(examples("4k9w2m7x1q8z"))]
+    pub client_id: String,
+
+    #[schema(examples("https://billing.example.com/callback"))]
+    pub redirect_uri: String,
+
+    #[schema(examples("accounts:read"))]
+    pub scope: Option<String>,
+
+    #[schema(examples("af0ifjsldkj"))]
+    pub state: Option<String>,
+
+    #[validate(length(
+        min = 43,
+        max = 128,
+        message = "Code challenge must contain between 43 and 128 characters"
+    ))]
+    #[schema(examples("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"))]
+    pub code_challenge: String,
+
+    #[schema(examples("S256"))]
+    pub code_challenge_method: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct AuthorizeDecisionDTO {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub request: AuthorizeDTO,
+
+    #[schema(examples(true))]
+    pub approved: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConsentDTO {
+    #[schema(examples("4k9w2m7x1q8z"))]
+    client_id: String,
+    #[schema(examples("billing-dashboard"))]
+    client_name: String,
+    #[schema(examples("https://billing.example.com/callback"))]
+    redirect_uri: String,
+    #[schema(value_type = Vec<String>, examples(json!(["accounts:read"])))]
+    scopes: Vec<Permission>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorizationRedirectDTO {
+    #[schema(examples(
+        "https://billing.example.com/callback?code=SplxlOBeZQQYbYS6WxSbIA&state=af0ifjsldkj"
+    ))]
+    pub redirect_uri: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct TokenRequestDTO {
+    #[schema(examples("authorization_code"))]
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponseDTO {
+    #[schema(examples("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9"))]
+    access_token: String,
+    #[schema(examples("Bearer"))]
+    token_type: String,
+    #[schema(examples(3600))]
+    expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    refresh_token: Option<String>,
+    #[schema(examples("accounts:read"))]
+    scope: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct TokenDTO {
+    #[validate(length(min = 1, message = "Token must not be empty"))]
+    #[schema(examples("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9"))]
+    pub token: String,
+    #[schema(examples("access_token"))]
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct IntrospectionDTO {
+    #[schema(examples(true))]
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, examples("access_token"))]
+    token_type: Option<TokenType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("accounts:read"))]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("4k9w2m7x1q8z"))]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("rsvx3vtf4z3xx5cpnhxuz"))]
+    sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples(1385903))]
+    exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples(1382303))]
+    iat: Option<i64>,
+}
+
+fn is_code_response_type(response_type: &str) -> Result<(), ValidationError> {
+    if response_type != "code" {
+        return Err(ValidationError::new("0")
+            .with_message(Cow::from("Only the code response type is supported")));
+    }
+
+    Ok(())
+}
+
+// Codes travel in the query string, so only TLS or loopback redirects are accepted
+fn is_redirect_uris(redirect_uris: &[String]) -> Result<(), ValidationError> {
+    let valid = redirect_uris.iter().all(|uri| {
+        Url::parse(uri).is_ok_and(|url| {
+            url.fragment().is_none()
+                && match url.scheme() {
+                    "https" => true,
+                    "http" => matches!(url.host_str(), Some("localhost" | "127.0.0.1")),
+                    _ => false,
+                }
+        })
+    });
+
+    if !valid {
+        return Err(ValidationError::new("0").with_message(Cow::from(
+            "Redirect URIs must be absolute HTTPS or loopback URLs without a fragment",
+        )));
+    }
+
+    Ok(())
+}
+
+fn scopes(scope: Option<&str>) -> Result<Option<Vec<Permission>>, AppError> {
+    scope
+        .map(|scope| parse_scope(scope).ok_or(AppError::BadRequest("invalid_scope")))
+        .transpose()
+}
+
+impl From<OAuthClient> for OAuthClientDTO {
+    fn from(client: OAuthClient) -> Self {
+        OAuthClientDTO {
+            client_id: client.id,
+            name: client.name,
+            confidential: client.secret_hash.is_some(),
+            redirect_uris: client.redirect_uris,
+            grant_types: client.grant_types,
+            scopes: client.scopes,
+            created_at: client.created_at,
+        }
+    }
+}
+
+impl From<IssuedOAuthClient> for RegisteredOAuthClientDTO {
+    fn from(issued: IssuedOAuthClient) -> Self {
+        RegisteredOAuthClientDTO {
+            client_secret: issued.client_secret,
+            client: issued.client.into(),
+        }
+    }
+}
+
+impl From<CreateOAuthClientDTO> for NewOAuthClient {
+    fn from(client: CreateOAuthClientDTO) -> Self {
+        NewOAuthClient {
+            name: client.name,
+            confidential: client.confidential,
+            redirect_uris: client.redirect_uris,
+            grant_types: client.grant_types,
+            scopes: client.scopes,
+        }
+    }
+}
+
+impl TryFrom<AuthorizeDTO> for AuthorizationRequest {
+    type Error = AppError;
+
+    fn try_from(authorize: AuthorizeDTO) -> Result<Self, Self::Error> {
+        Ok(AuthorizationRequest {
+            client_id: authorize.client_id,
+            redirect_uri: authorize.redirect_uri,
+            scopes: scopes(authorize.scope.as_deref())?.unwrap_or_default(),
+            state: authorize.state,
+            code_challenge: authorize.code_challenge,
+            code_challenge_method: authorize.code_challenge_method,
+        })
+    }
+}
+
+impl From<(OAuthClient, AuthorizationRequest)> for ConsentDTO {
+    fn from((client, request): (OAuthClient, AuthorizationRequest)) -> Self {
+        ConsentDTO {
+            client_id: client.id,
+            client_name: client.name,
+            redirect_uri: request.redirect_uri,
+            scopes: request.scopes,
+        }
+    }
+}
+
+impl TryFrom<&TokenRequestDTO> for TokenGrant {
+    type Error = AppError;
+
+    fn try_from(token: &TokenRequestDTO) -> Result<Self, Self::Error> {
+        let required =
+            |value: &Option<String>| value.clone().ok_or(AppError::BadRequest("invalid_request"));
+
+        match token.grant_type.as_str() {
+            "authorization_code" => Ok(TokenGrant::AuthorizationCode {
+                code: required(&token.code)?,
+                redirect_uri: required(&token.redirect_uri)?,
+                code_verifier: required(&token.code_verifier)?,
+            }),
+            "client_credentials" => Ok(TokenGrant::ClientCredentials {
+                scopes: scopes(token.scope.as_deref())?,
+            }),
+            "refresh_token" => Ok(TokenGrant::RefreshToken {
+                refresh_token: required(&token.refresh_token)?,
+                scopes: scopes(token.scope.as_deref())?,
+            }),
+            _ => Err(AppError::BadRequest("unsupported_grant_type")),
+        }
+    }
+}
+
+impl From<OAuthToken> for TokenResponseDTO {
+    fn from(token: OAuthToken) -> Self {
+        TokenResponseDTO {
+            access_token: token.access_token.token,
+            token_type: "Bearer".to_string(),
+            expires_in: token.access_token.expiration - chrono::Utc::now().timestamp(),
+            refresh_token: token.refresh_token,
+            scope: format_scope(&token.scopes),
+        }
+    }
+}
+
+impl From<Option<TokenIntrospection>> for IntrospectionDTO {
+    fn from(introspection: Option<TokenIntrospection>) -> Self {
+        match introspection {
+            None => IntrospectionDTO::default(),
+            Some(introspection) => IntrospectionDTO {
+                active: true,
+                token_type: Some(introspection.token_type),
+                scope: Some(format_scope(&introspection.scopes)),
+                client_id: introspection.client_id,
+                sub: Some(introspection.sub),
+                exp: introspection.expiration,
+                iat: introspection.issued_at,
+            },
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use validator::Validate;
+
+#[derive(Debug, Validate, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OidcCallbackDTO {
+    #[validate(length(min = 1, message = "Authorization code must not be empty"))]
+    pub code: String,
+
+    #[validate(length(min = 1, message = "State must not be empty"))]
+    pub state: String,
+}
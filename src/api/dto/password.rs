@@ -0,0 +1,32 @@
+use crate::api::dto::validation::{is_email, is_password};
+use crate::domain::models::password_reset::ResetPassword;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct ForgotPasswordDTO {
+    #[validate(custom(function = "is_email"))]
+    #[schema(examples("your@email.com"))]
+    pub email: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct ResetPasswordDTO {
+    #[validate(length(min = 1, message = "Reset token must not be empty"))]
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    pub token: String,
+
+    #[validate(custom(function = "is_password"))]
+    #[schema(examples("n3wStR0ngP4ssw0rd!"))]
+    pub password: String,
+}
+
+impl From<ResetPasswordDTO> for ResetPassword {
+    fn from(reset: ResetPasswordDTO) -> Self {
+        ResetPassword {
+            token: reset.token,
+            password: reset.password,
+        }
+    }
+}
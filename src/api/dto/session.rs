@@ -0,0 +1,50 @@
+use actix_web::HttpRequest;
+use actix_web::http::header::USER_AGENT;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::models::session::{CreateSession, Session};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionDTO {
+    #[schema(examples("8f3k2m9x1q7z"))]
+    id: String,
+    #[schema(examples("Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0"))]
+    user_agent: Option<String>,
+    #[schema(examples("203.0.113.7"))]
+    ip: Option<String>,
+    #[schema(examples(1385903))]
+    created_at: i64,
+    #[schema(examples(1385903))]
+    last_seen_at: i64,
+    // Whether the request listing the sessions was made with this one
+    #[schema(examples(true))]
+    current: bool,
+}
+
+impl From<(Session, bool)> for SessionDTO {
+    fn from((session, current): (Session, bool)) -> Self {
+        SessionDTO {
+            id: session.id,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            current,
+        }
+    }
+}
+
+impl From<(&HttpRequest, &str)> for CreateSession {
+    fn from((req, account): (&HttpRequest, &str)) -> Self {
+        CreateSession {
+            account: account.to_owned(),
+            user_agent: req
+                .headers()
+                .get(USER_AGENT)
+                .and_then(|header| header.to_str().ok())
+                .map(ToOwned::to_owned),
+            ip: req.peer_addr().map(|addr| addr.ip().to_string()),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::api::dto::validation::is_email;
+use crate::domain::models::webauthn::{
+    AssertCredential, AuthenticationOptions, RegisterCredential, RegistrationOptions,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+const PUBLIC_KEY: &str = "public-key";
+const ES256: i64 = -7;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RelyingPartyDTO {
+    #[schema(examples("localhost"))]
+    pub id: String,
+    #[schema(examples("surreal-actix"))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntityDTO {
+    #[schema(examples("cmdzN3Z0ZjR6M3h4NWNwbmh4dXo"))]
+    pub id: String,
+    #[schema(examples("your@email.com"))]
+    pub name: String,
+    #[schema(examples("Your Name"))]
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CredentialParameterDTO {
+    #[serde(rename = "type")]
+    #[schema(examples("public-key"))]
+    pub kind: String,
+    #[schema(examples(-7))]
+    pub alg: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CredentialDescriptorDTO {
+    #[serde(rename = "type")]
+    #[schema(examples("public-key"))]
+    pub kind: String,
+    #[schema(examples("q1ZbA6lDgIhH3Y0Zl2s7_w"))]
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelectionDTO {
+    #[schema(examples("preferred"))]
+    pub resident_key: String,
+    #[schema(examples("required"))]
+    pub user_verification: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationOptionsDTO {
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    pub challenge: String,
+    pub rp: RelyingPartyDTO,
+    pub user: UserEntityDTO,
+    pub pub_key_cred_params: Vec<CredentialParameterDTO>,
+    #[schema(examples(300000))]
+    pub timeout: i64,
+    #[schema(examples("none"))]
+    pub attestation: String,
+    pub exclude_credentials: Vec<CredentialDescriptorDTO>,
+    pub authenticator_selection: AuthenticatorSelectionDTO,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationOptionsDTO {
+    #[schema(examples("0n7cQ7xH2t1Uq5oEwSxg8nqkRn1cV5YtZ0W0bKk3v6A"))]
+    pub challenge: String,
+    #[schema(examples("localhost"))]
+    pub rp_id: String,
+    #[schema(examples(300000))]
+    pub timeout: i64,
+    pub allow_credentials: Vec<CredentialDescriptorDTO>,
+    #[schema(examples("required"))]
+    pub user_verification: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct AuthenticationOptionsRequestDTO {
+    #[validate(custom(function = "is_email"))]
+    #[schema(examples("your@email.com"))]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponseDTO {
+    #[serde(rename = "clientDataJSON")]
+    #[validate(length(min = 1, message = "Client data must not be empty"))]
+    pub client_data_json: String,
+
+    #[validate(length(min = 1, message = "Attestation object must not be empty"))]
+    pub attestation_object: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct RegisterCredentialDTO {
+    #[validate(length(min = 1, message = "Credential id must not be empty"))]
+    #[schema(examples("q1ZbA6lDgIhH3Y0Zl2s7_w"))]
+    pub id: String,
+
+    #[validate(nested)]
+    pub response: AttestationResponseDTO,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponseDTO {
+    #[serde(rename = "clientDataJSON")]
+    #[validate(length(min = 1, message = "Client data must not be empty"))]
+    pub client_data_json: String,
+
+    #[validate(length(min = 1, message = "Authenticator data must not be empty"))]
+    pub authenticator_data: String,
+
+    #[validate(length(min = 1, message = "Signature must not be empty"))]
+    pub signature: String,
+
+    pub user_handle: Option<String>,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct AssertCredentialDTO {
+    #[validate(length(min = 1, message = "Credential id must not be empty"))]
+    #[schema(examples("q1ZbA6lDgIhH3Y0Zl2s7_w"))]
+    pub id: String,
+
+    #[validate(nested)]
+    pub response: AssertionResponseDTO,
+}
+
+fn descriptors(credentials: Vec<String>) -> Vec<CredentialDescriptorDTO> {
+    credentials
+        .into_iter()
+        .map(|id| CredentialDescriptorDTO {
+            kind: PUBLIC_KEY.to_string(),
+            id,
+        })
+        .collect()
+}
+
+impl From<RegistrationOptions> for RegistrationOptionsDTO {
+    fn from(options: RegistrationOptions) -> Self {
+        RegistrationOptionsDTO {
+            challenge: options.challenge,
+            rp: RelyingPartyDTO {
+                id: options.rp_id,
+                name: options.rp_name,
+            },
+            user: UserEntityDTO {
+                id: options.user_id,
+                name: options.user_name,
+                display_name: options.user_display_name,
+            },
+            pub_key_cred_params: vec![CredentialParameterDTO {
+                kind: PUBLIC_KEY.to_string(),
+                alg: ES256,
+            }],
+            timeout: options.timeout,
+            attestation: "none".to_string(),
+            exclude_credentials: descriptors(options.exclude_credentials),
+            authenticator_selection: AuthenticatorSelectionDTO {
+                resident_key: "preferred".to_string(),
+                user_verification: "required".to_string(),
+            },
+        }
+    }
+}
+
+impl From<AuthenticationOptions> for AuthenticationOptionsDTO {
+    fn from(options: AuthenticationOptions) -> Self {
+        AuthenticationOptionsDTO {
+            challenge: options.challenge,
+            rp_id: options.rp_id,
+            timeout: options.timeout,
+            allow_credentials: descriptors(options.allow_credentials),
+            user_verification: "required".to_string(),
+        }
+    }
+}
+
+impl From<RegisterCredentialDTO> for RegisterCredential {
+    fn from(credential: RegisterCredentialDTO) -> Self {
+        RegisterCredential {
+            credential_id: credential.id,
+            client_data_json: credential.response.client_data_json,
+            attestation_object: credential.response.attestation_object,
+        }
+    }
+}
+
+impl From<AssertCredentialDTO> for AssertCredential {
+    fn from(credential: AssertCredentialDTO) -> Self {
+        AssertCredential {
+            credential_id: credential.id,
+            client_data_json: credential.response.client_data_json,
+            authenticator_data: credential.response.authenticator_data,
+            signature: credential.response.signature,
+            user_handle: credential.response.user_handle,
+        }
+    }
+}
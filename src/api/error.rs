@@ -1,5 +1,147 @@
-use actix_web::HttpResponse;
+use actix_web::{
+    HttpResponse, ResponseError,
+    http::{StatusCode, header::WWW_AUTHENTICATE},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
 
-use crate::domain::error::AppResult;
+use crate::domain::error::{AppError, AppResult};
 
 pub type ApiResult = AppResult<HttpResponse>;
+
+pub type OAuthResult = Result<HttpResponse, OAuthError>;
+
+// Error codes from RFC 6749, section 5.2, the services pass them as the message of a bad request
+const OAUTH_ERRORS: [(&str, &str); 5] = [
+    (
+        "invalid_request",
+        "The request is missing a parameter or is otherwise malformed",
+    ),
+    (
+        "invalid_grant",
+        "The grant is invalid, expired, revoked or was issued to another client",
+    ),
+    (
+        "invalid_scope",
+        "The requested scope is invalid or exceeds the granted scope",
+    ),
+    (
+        "unauthorized_client",
+        "The client is not authorized to use this grant type",
+    ),
+    ("unsupported_grant_type", "The grant type is not supported"),
+];
+
+// Endpoints called by OAuth clients answer in the RFC 6749 format instead of the AppError one
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct OAuthError {
+    pub error: String,
+    pub error_description: String,
+    #[serde(skip)]
+    pub code: u16,
+}
+
+impl OAuthError {
+    fn new(error: &str, error_description: &str, status: StatusCode) -> OAuthError {
+        OAuthError {
+            error: error.to_owned(),
+            error_description: error_description.to_owned(),
+            code: status.as_u16(),
+        }
+    }
+
+    pub fn example_400() -> OAuthError {
+        OAuthError::from(AppError::BadRequest("invalid_grant"))
+    }
+
+    pub fn example_401() -> OAuthError {
+        OAuthError::from(AppError::Unauthorized())
+    }
+
+    pub fn example_500() -> OAuthError {
+        OAuthError::from(AppError::InternalError())
+    }
+
+    pub fn example_503() -> OAuthError {
+        OAuthError::from(AppError::ServiceUnavailable())
+    }
+}
+
+impl From<AppError> for OAuthError {
+    fn from(error: AppError) -> Self {
+        match StatusCode::from_u16(error.code).unwrap() {
+            StatusCode::BAD_REQUEST => match OAUTH_ERRORS
+                .iter()
+                .find(|(code, _)| *code == error.message)
+            {
+                Some((code, description)) => {
+                    OAuthError::new(code, description, StatusCode::BAD_REQUEST)
+                }
+                None => OAuthError::new("invalid_request", &error.message, StatusCode::BAD_REQUEST),
+            },
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                OAuthError::new("invalid_request", &error.message, StatusCode::BAD_REQUEST)
+            }
+            StatusCode::UNAUTHORIZED => OAuthError::new(
+                "invalid_client",
+                "Client authentication failed",
+                StatusCode::UNAUTHORIZED,
+            ),
+            // The account behind the grant can no longer be used, e.g. suspended or deactivated
+            StatusCode::FORBIDDEN | StatusCode::LOCKED | StatusCode::GONE => {
+                OAuthError::new("invalid_grant", &error.message, StatusCode::BAD_REQUEST)
+            }
+            StatusCode::SERVICE_UNAVAILABLE => OAuthError::new(
+                "temporarily_unavailable",
+                &error.message,
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            status => OAuthError::new("server_error", &error.message, status),
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error {}: {}", self.code, self.error)
+    }
+}
+
+impl ResponseError for OAuthError {
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+
+        // Clients failing to authenticate are told which scheme to use (RFC 6749, section 5.2)
+        if self.code == StatusCode::UNAUTHORIZED.as_u16() {
+            response.insert_header((WWW_AUTHENTICATE, "Basic"));
+        }
+
+        response.json(self)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.code).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::grant(AppError::BadRequest("invalid_grant"), "invalid_grant", 400)]
+    #[case::malformed(AppError::BadRequest("Parse error"), "invalid_request", 400)]
+    #[case::validation(AppError::UnprocessableEntity("{}"), "invalid_request", 400)]
+    #[case::client(AppError::Unauthorized(), "invalid_client", 401)]
+    #[case::suspended(AppError::Suspended(), "invalid_grant", 400)]
+    #[case::internal(AppError::InternalError(), "server_error", 500)]
+    fn test_from_app_error(#[case] error: AppError, #[case] expected: &str, #[case] code: u16) {
+        let error = OAuthError::from(error);
+
+        assert_eq!(error.error, expected);
+        assert_eq!(error.code, code);
+    }
+}
@@ -1,28 +1,86 @@
 use crate::domain::error::AppError;
-use crate::domain::models::jsonwebtoken::Claims;
+use crate::domain::models::api_key::API_KEY_PREFIX;
+use crate::domain::models::jsonwebtoken::{AccessToken, Claims};
+use crate::domain::services::api_key::ApiKeyService;
 use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use actix_web::cookie::time::OffsetDateTime;
+use actix_web::cookie::{Cookie, SameSite};
 use actix_web::dev::Payload;
-use actix_web::{FromRequest, HttpRequest, web};
-use futures::future::{Ready, err, ok};
+use actix_web::{FromRequest, HttpMessage, HttpRequest, web};
+use futures::future::{FutureExt, LocalBoxFuture, ready};
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RequireJsonWebToken {
-    #[allow(dead_code)]
     pub claims: Claims,
+    // Set when the caller authenticated with an API key instead of a JSON Web Token
+    pub api_key: Option<String>,
 }
 
-fn get_token(req: &HttpRequest) -> Result<String, AppError> {
+// Delegated credentials cannot mint further credentials, so a leaked one cannot escalate
+pub fn require_session(auth: &RequireJsonWebToken) -> Result<(), AppError> {
+    match (&auth.api_key, &auth.claims.client_id) {
+        (None, None) => Ok(()),
+        _ => Err(AppError::Forbidden()),
+    }
+}
+
+enum Credential {
+    JsonWebToken(String),
+    ApiKey(String),
+}
+
+pub fn authorization_cookie(access_token: &AccessToken) -> Cookie<'static> {
+    Cookie::build("Authorization", access_token.token.clone())
+        .http_only(true)
+        .secure(true)
+        .path("/api")
+        .same_site(SameSite::Strict)
+        .expires(OffsetDateTime::from_unix_timestamp(access_token.expiration).unwrap())
+        .finish()
+}
+
+pub fn removal_authorization_cookie() -> Cookie<'static> {
+    let mut cookie = Cookie::build("Authorization", "")
+        .http_only(true)
+        .secure(true)
+        .path("/api")
+        .same_site(SameSite::Strict)
+        .finish();
+
+    cookie.make_removal();
+
+    cookie
+}
+
+fn get_token(req: &HttpRequest) -> Result<Credential, AppError> {
     if let Some(cookie) = req.cookie("Authorization") {
-        return Ok(cookie.value().to_string());
+        return Ok(Credential::JsonWebToken(cookie.value().to_string()));
+    }
+
+    if let Some(header) = req.headers().get("X-API-Key") {
+        return Ok(Credential::ApiKey(
+            header
+                .to_str()
+                .map_err(|_| AppError::Unauthorized())?
+                .trim()
+                .to_string(),
+        ));
     }
 
     if let Some(header) = req.headers().get("Authorization") {
-        return Ok(header
+        let token = header
             .to_str()
             .map_err(|_| AppError::Unauthorized())?
             .trim_start_matches("Bearer")
-            .to_string());
+            .trim()
+            .to_string();
+
+        if token.starts_with(API_KEY_PREFIX) {
+            return Ok(Credential::ApiKey(token));
+        }
+
+        return Ok(Credential::JsonWebToken(token));
     }
 
     Err(AppError::Unauthorized())
@@ -30,22 +88,58 @@ fn get_token(req: &HttpRequest) -> Result<String, AppError> {
 
 impl FromRequest for RequireJsonWebToken {
     type Error = AppError;
-    type Future = Ready<Result<RequireJsonWebToken, AppError>>;
+    type Future = LocalBoxFuture<'static, Result<RequireJsonWebToken, AppError>>;
 
+    // The outcome is kept in the request extensions, so a credential checked by a middleware is not
+    // validated, and its session touched, a second time by the handler
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        if let Some(jsonwebtoken_service) =
-            req.app_data::<web::Data<Arc<dyn JsonWebTokenService>>>()
-        {
-            return match get_token(req) {
-                Ok(token) => match jsonwebtoken_service.validate_token(token.trim()) {
-                    Ok(claims) => ok(RequireJsonWebToken { claims }),
-                    Err(error) => err(error),
-                },
-                Err(error) => err(error),
-            };
+        if let Some(auth) = req.extensions().get::<RequireJsonWebToken>() {
+            return ready(Ok(auth.clone())).boxed_local();
         }
 
-        err(AppError::InternalError().trace("JsonWebTokenService is not defined"))
+        let req = req.clone();
+
+        let jsonwebtoken_service = req
+            .app_data::<web::Data<Arc<dyn JsonWebTokenService>>>()
+            .cloned();
+
+        let api_key_service = req.app_data::<web::Data<Arc<dyn ApiKeyService>>>().cloned();
+
+        let token = get_token(&req);
+
+        async move {
+            let auth = match token? {
+                Credential::JsonWebToken(token) => {
+                    let jsonwebtoken_service = jsonwebtoken_service.ok_or_else(|| {
+                        AppError::InternalError().trace("JsonWebTokenService is not defined")
+                    })?;
+
+                    let claims = jsonwebtoken_service.validate_token(&token).await?;
+
+                    RequireJsonWebToken {
+                        claims,
+                        api_key: None,
+                    }
+                }
+                Credential::ApiKey(key) => {
+                    let api_key_service = api_key_service.ok_or_else(|| {
+                        AppError::InternalError().trace("ApiKeyService is not defined")
+                    })?;
+
+                    let claims = api_key_service.authenticate(&key).await?;
+
+                    RequireJsonWebToken {
+                        api_key: Some(claims.jti.clone()),
+                        claims,
+                    }
+                }
+            };
+
+            req.extensions_mut().insert(auth.clone());
+
+            Ok(auth)
+        }
+        .boxed_local()
     }
 }
 
@@ -60,11 +154,37 @@ mod tests {
         web,
     };
 
+    use tokio::sync::Mutex;
+
+    use crate::domain::models::account::{Account, AccountStatus, Role};
+    use crate::domain::models::api_key::NewApiKey;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::api_key::mock::ApiKeyRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::api_key::ApiKeyServiceImpl;
     use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
-    use crate::tests::utils::crypto::generate_keypair;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
 
     use super::*;
 
+    fn account() -> Account {
+        Account {
+            id: "ajk".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
     async fn index(_: RequireJsonWebToken) -> impl Responder {
         HttpResponse::new(StatusCode::OK)
     }
@@ -73,7 +193,20 @@ mod tests {
 
     #[fixture]
     fn jwt_service() -> Arc<dyn JsonWebTokenService> {
-        Arc::new(JsonWebTokenServiceImpl::new(generate_keypair()))
+        let repo = Arc::new(RevokedTokenRepositoryImpl {
+            tokens: Mutex::new(vec![]),
+        });
+        Arc::new(JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            repo,
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![]),
+            }),
+            Arc::new(SessionRepositoryImpl {
+                sessions: Mutex::new(vec![]),
+            }),
+        ))
     }
 
     enum Auth {
@@ -156,11 +289,97 @@ mod tests {
     #[case::header(Auth::Header)]
     #[actix_web::test]
     async fn test_authorized_access(jwt_service: Arc<dyn JsonWebTokenService>, #[case] auth: Auth) {
-        let access_token = jwt_service.generate_token("ajk".into()).unwrap();
+        let access_token = jwt_service.generate_token(&account(), "session").unwrap();
 
         assert_eq!(
             send_req("Authorization", &access_token.token, auth, jwt_service).await,
             StatusCode::OK
         );
     }
+
+    #[rstest]
+    #[case::cookie(Auth::Cookie)]
+    #[case::header(Auth::Header)]
+    #[actix_web::test]
+    async fn test_revoked_access(jwt_service: Arc<dyn JsonWebTokenService>, #[case] auth: Auth) {
+        let access_token = jwt_service.generate_token(&account(), "session").unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        jwt_service.revoke_token(&claims).await.unwrap();
+
+        assert_eq!(
+            send_req("Authorization", &access_token.token, auth, jwt_service).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[rstest]
+    #[case::cookie(Auth::Cookie)]
+    #[case::header(Auth::Header)]
+    #[actix_web::test]
+    async fn test_revoked_session(jwt_service: Arc<dyn JsonWebTokenService>, #[case] auth: Auth) {
+        let access_token = jwt_service.generate_token(&account(), "session").unwrap();
+
+        jwt_service.revoke_session("session");
+
+        assert_eq!(
+            send_req("Authorization", &access_token.token, auth, jwt_service).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[rstest]
+    #[case::header("X-API-Key", "")]
+    #[case::bearer("Authorization", "Bearer ")]
+    #[actix_web::test]
+    async fn test_api_key_access(#[case] name: &str, #[case] scheme: &str) {
+        let api_key_service: Arc<dyn ApiKeyService> = Arc::new(ApiKeyServiceImpl::new(
+            &jsonwebtoken_config(),
+            Arc::new(ApiKeyRepositoryImpl {
+                api_keys: Mutex::new(vec![]),
+            }),
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![account()]),
+            }),
+        ));
+
+        let issued = api_key_service
+            .create(
+                &account(),
+                NewApiKey {
+                    name: "CI".to_string(),
+                    scopes: None,
+                    expiration: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .route("/index", web::get().to(index))
+                .app_data(web::Data::new(api_key_service)),
+        )
+        .await;
+
+        let res = TestRequest::get()
+            .uri("/index")
+            .insert_header((name, format!("{scheme}{}", issued.key)))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = TestRequest::get()
+            .uri("/index")
+            .insert_header((name, format!("{scheme}{API_KEY_PREFIX}invalid")))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
 }
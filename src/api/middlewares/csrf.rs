@@ -0,0 +1,156 @@
+use crate::config::CsrfConfig;
+use crate::domain::error::AppError;
+use crate::domain::models::jsonwebtoken::AccessToken;
+use crate::services::crypto::hash_token;
+use crate::services::rate_limit::in_scope;
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::cookie::time::OffsetDateTime;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{HttpMessage, ResponseError, web};
+
+pub const CSRF_COOKIE: &str = "csrf_token";
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+// Derived from the session itself, so a cookie planted from a sibling subdomain cannot match it
+pub fn csrf_token(access_token: &str) -> String {
+    hash_token(access_token)
+}
+
+// Readable by scripts, which echo it back in the CSRF header of state-changing requests
+pub fn csrf_cookie(access_token: &AccessToken) -> Cookie<'static> {
+    Cookie::build(CSRF_COOKIE, csrf_token(&access_token.token))
+        .secure(true)
+        .path("/")
+        .same_site(SameSite::Strict)
+        .expires(OffsetDateTime::from_unix_timestamp(access_token.expiration).unwrap())
+        .finish()
+}
+
+pub fn removal_csrf_cookie() -> Cookie<'static> {
+    let mut cookie = Cookie::build(CSRF_COOKIE, "")
+        .secure(true)
+        .path("/")
+        .same_site(SameSite::Strict)
+        .finish();
+
+    cookie.make_removal();
+
+    cookie
+}
+
+pub async fn csrf(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, actix_web::Error> {
+    let config = match req.app_data::<web::Data<CsrfConfig>>() {
+        Some(config) => config.clone(),
+        None => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+
+    let exempt = req.method().is_safe()
+        || config
+            .exempt
+            .iter()
+            .any(|scope| in_scope(scope, req.path()));
+
+    // The Authorization cookie takes precedence over headers, so its presence means the browser
+    // authenticates the request on its own and the caller has to prove it can read the CSRF cookie
+    let session = match req.cookie("Authorization") {
+        Some(session) if !exempt => session,
+        _ => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+
+    let valid = req
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .is_some_and(|token| token == csrf_token(session.value()));
+
+    if !valid {
+        let res = AppError::Forbidden().error_response();
+
+        return Ok(req.into_response(res).map_into_right_body());
+    }
+
+    Ok(next.call(req).await?.map_into_left_body())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App, HttpResponse, Responder,
+        http::StatusCode,
+        middleware::from_fn,
+        test::{self, TestRequest},
+    };
+
+    use rstest::*;
+
+    use super::*;
+
+    async fn index() -> impl Responder {
+        HttpResponse::new(StatusCode::OK)
+    }
+
+    async fn send_req(method: &str, uri: &str, cookie: bool, token: Option<&str>) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .route("/index", web::to(index))
+                .route("/exempt/index", web::to(index))
+                .wrap(from_fn(csrf))
+                .app_data(web::Data::new(CsrfConfig {
+                    exempt: vec!["/exempt".to_string()],
+                })),
+        )
+        .await;
+
+        let mut req = TestRequest::default()
+            .method(method.parse().unwrap())
+            .uri(uri);
+
+        if cookie {
+            req = req.cookie(Cookie::new("Authorization", "session"));
+        }
+
+        if let Some(token) = token {
+            req = req.insert_header((CSRF_HEADER, token.to_owned()));
+        }
+
+        req.send_request(&app).await.status()
+    }
+
+    #[actix_web::test]
+    async fn test_valid_token() {
+        let token = csrf_token("session");
+
+        assert_eq!(
+            send_req("POST", "/index", true, Some(&token)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[rstest]
+    #[case::missing(None)]
+    #[case::mismatch(Some("forged"))]
+    #[actix_web::test]
+    async fn test_invalid_token(#[case] token: Option<&str>) {
+        for method in ["POST", "PUT", "PATCH", "DELETE"] {
+            assert_eq!(
+                send_req(method, "/index", true, token).await,
+                StatusCode::FORBIDDEN
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::safe_method("GET", "/index", true)]
+    #[case::exempt_path("POST", "/exempt/index", true)]
+    #[case::header_auth("POST", "/index", false)]
+    #[actix_web::test]
+    async fn test_not_checked(#[case] method: &str, #[case] uri: &str, #[case] cookie: bool) {
+        assert_eq!(send_req(method, uri, cookie, None).await, StatusCode::OK);
+    }
+}
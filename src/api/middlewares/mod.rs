@@ -1,2 +1,6 @@
 pub mod auth;
+pub mod csrf;
+pub mod rate_limit;
+#[allow(dead_code)]
+pub mod rbac;
 pub mod validate;
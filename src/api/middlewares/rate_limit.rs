@@ -0,0 +1,150 @@
+use crate::api::middlewares::auth::RequireJsonWebToken;
+use crate::domain::error::AppError;
+use crate::domain::models::rate_limit::{RateLimit, RateLimitRequest};
+use crate::domain::services::rate_limit::RateLimitService;
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{FromRequest, ResponseError, web};
+use std::sync::Arc;
+
+const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, actix_web::Error> {
+    let rate_limit_service = match req.app_data::<web::Data<Arc<dyn RateLimitService>>>() {
+        Some(rate_limit_service) => rate_limit_service.clone(),
+        None => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+
+    let path = req.path().to_owned();
+
+    // Tokens are only validated when a matching rule is keyed by subject, the handler reuses the result
+    let sub = if rate_limit_service.keys_by_subject(&path) {
+        RequireJsonWebToken::extract(req.request())
+            .await
+            .ok()
+            .map(|auth| auth.claims.sub)
+    } else {
+        None
+    };
+
+    let request = RateLimitRequest {
+        route: req.match_pattern().unwrap_or_else(|| path.clone()),
+        path,
+        ip: req.peer_addr().map(|addr| addr.ip().to_string()),
+        sub,
+    };
+
+    // An unavailable backend must not take the API down with it
+    let rate_limit = match rate_limit_service.acquire(&request).await {
+        Ok(rate_limit) => rate_limit,
+        Err(err) => {
+            tracing::warn!(error = ?err, "rate limit backend unavailable");
+            None
+        }
+    };
+
+    let rate_limit = match rate_limit {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+
+    if let Some(retry_after) = rate_limit.retry_after {
+        let mut res = AppError::TooManyRequests()
+            .retry_after(retry_after)
+            .error_response();
+
+        insert_headers(res.headers_mut(), &rate_limit);
+
+        return Ok(req.into_response(res).map_into_right_body());
+    }
+
+    let mut res = next.call(req).await?;
+
+    insert_headers(res.headers_mut(), &rate_limit);
+
+    Ok(res.map_into_left_body())
+}
+
+fn insert_headers(headers: &mut HeaderMap, rate_limit: &RateLimit) {
+    headers.insert(RATELIMIT_LIMIT, HeaderValue::from(rate_limit.limit));
+    headers.insert(RATELIMIT_REMAINING, HeaderValue::from(rate_limit.remaining));
+    headers.insert(RATELIMIT_RESET, HeaderValue::from(rate_limit.reset));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App, HttpResponse, Responder,
+        http::StatusCode,
+        middleware::from_fn,
+        test::{self, TestRequest},
+    };
+
+    use crate::config::{RateLimitConfig, RateLimitKey, RateLimitRuleConfig};
+    use crate::infrastructure::repositories::rate_limit::MemoryRateLimitRepositoryImpl;
+    use crate::services::rate_limit::RateLimitServiceImpl;
+
+    use super::*;
+
+    async fn index() -> impl Responder {
+        HttpResponse::new(StatusCode::OK)
+    }
+
+    #[actix_web::test]
+    async fn test_rate_limit_headers() {
+        let rate_limit_service: Arc<dyn RateLimitService> = Arc::new(RateLimitServiceImpl::new(
+            &RateLimitConfig {
+                rules: vec![RateLimitRuleConfig {
+                    scope: "/limited".to_string(),
+                    key: RateLimitKey::Ip,
+                    capacity: 1,
+                    period: 60,
+                }],
+                ..Default::default()
+            },
+            Arc::new(MemoryRateLimitRepositoryImpl::default()),
+        ));
+
+        let app = test::init_service(
+            App::new()
+                .route("/limited", web::get().to(index))
+                .route("/open", web::get().to(index))
+                .wrap(from_fn(rate_limit))
+                .app_data(web::Data::new(rate_limit_service)),
+        )
+        .await;
+
+        let send = |uri: &'static str| {
+            TestRequest::get()
+                .uri(uri)
+                .peer_addr("10.0.0.1:4000".parse().unwrap())
+                .to_request()
+        };
+
+        let res = test::call_service(&app, send("/limited")).await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "1");
+        assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+        assert_eq!(res.headers().get("ratelimit-reset").unwrap(), "60");
+
+        let res = test::call_service(&app, send("/limited")).await;
+
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(res.headers().contains_key("retry-after"));
+        assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+
+        let res = test::call_service(&app, send("/open")).await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.headers().contains_key("ratelimit-limit"));
+    }
+}
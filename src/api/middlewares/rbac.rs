@@ -0,0 +1,222 @@
+use crate::api::middlewares::auth::RequireJsonWebToken;
+use crate::domain::error::AppError;
+use crate::domain::models::account::{Permission, Role};
+use crate::domain::models::jsonwebtoken::Claims;
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{FutureExt, LocalBoxFuture};
+use std::marker::PhantomData;
+
+pub trait RoleGuard: 'static {
+    const ROLE: Role;
+}
+
+pub trait PermissionGuard: 'static {
+    const PERMISSION: Permission;
+}
+
+pub struct Admin;
+
+impl RoleGuard for Admin {
+    const ROLE: Role = Role::Admin;
+}
+
+pub struct AccountsRead;
+
+impl PermissionGuard for AccountsRead {
+    const PERMISSION: Permission = Permission::AccountsRead;
+}
+
+pub struct AccountsWrite;
+
+impl PermissionGuard for AccountsWrite {
+    const PERMISSION: Permission = Permission::AccountsWrite;
+}
+
+#[derive(Debug)]
+pub struct RequireRole<R: RoleGuard> {
+    pub claims: Claims,
+    role: PhantomData<R>,
+}
+
+#[derive(Debug)]
+pub struct RequirePermission<P: PermissionGuard> {
+    pub claims: Claims,
+    permission: PhantomData<P>,
+}
+
+impl<R: RoleGuard> FromRequest for RequireRole<R> {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<RequireRole<R>, AppError>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let auth = RequireJsonWebToken::from_request(req, payload);
+
+        async move {
+            let claims = auth.await?.claims;
+
+            if !claims.roles.contains(&R::ROLE) {
+                return Err(AppError::Forbidden());
+            }
+
+            Ok(RequireRole {
+                claims,
+                role: PhantomData,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+impl<P: PermissionGuard> FromRequest for RequirePermission<P> {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<RequirePermission<P>, AppError>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let auth = RequireJsonWebToken::from_request(req, payload);
+
+        async move {
+            let claims = auth.await?.claims;
+
+            if !claims.permissions.contains(&P::PERMISSION) {
+                return Err(AppError::Forbidden());
+            }
+
+            Ok(RequirePermission {
+                claims,
+                permission: PhantomData,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use actix_web::{
+        App, HttpResponse, Responder,
+        http::StatusCode,
+        test::{self, TestRequest},
+        web,
+    };
+
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    use crate::domain::models::account::{Account, AccountStatus};
+    use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
+
+    use super::*;
+
+    use rstest::*;
+
+    async fn admin(_: RequireRole<Admin>) -> impl Responder {
+        HttpResponse::new(StatusCode::OK)
+    }
+
+    async fn accounts(_: RequirePermission<AccountsRead>) -> impl Responder {
+        HttpResponse::new(StatusCode::OK)
+    }
+
+    #[fixture]
+    fn jwt_service() -> Arc<dyn JsonWebTokenService> {
+        let repo = Arc::new(RevokedTokenRepositoryImpl {
+            tokens: Mutex::new(vec![]),
+        });
+        Arc::new(JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            repo,
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![]),
+            }),
+            Arc::new(SessionRepositoryImpl {
+                sessions: Mutex::new(vec![]),
+            }),
+        ))
+    }
+
+    async fn send_req(
+        uri: &str,
+        token: Option<String>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    ) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .route("/admin", web::get().to(admin))
+                .route("/accounts", web::get().to(accounts))
+                .app_data(web::Data::new(jsonwebtoken_service)),
+        )
+        .await;
+
+        let mut req = TestRequest::get().uri(uri);
+
+        if let Some(token) = token {
+            req = req.insert_header(("Authorization", format!("Bearer {token}")));
+        }
+
+        req.send_request(&app).await.status()
+    }
+
+    fn token(
+        jwt_service: &Arc<dyn JsonWebTokenService>,
+        roles: Vec<Role>,
+        permissions: Vec<Permission>,
+    ) -> String {
+        let account = Account {
+            id: "ajk".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles,
+            permissions,
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        };
+
+        jwt_service
+            .generate_token(&account, "session")
+            .unwrap()
+            .token
+    }
+
+    #[rstest]
+    #[case::admin("/admin")]
+    #[case::permission("/accounts")]
+    #[actix_web::test]
+    async fn test_missing_token(jwt_service: Arc<dyn JsonWebTokenService>, #[case] uri: &str) {
+        assert_eq!(
+            send_req(uri, None, jwt_service).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[rstest]
+    #[case::user_as_admin("/admin", vec![Role::User], vec![], StatusCode::FORBIDDEN)]
+    #[case::admin_as_admin("/admin", vec![Role::Admin], vec![], StatusCode::OK)]
+    #[case::user_without_permission("/accounts", vec![Role::User], vec![], StatusCode::FORBIDDEN)]
+    #[case::user_with_permission("/accounts", vec![Role::User], vec![Permission::AccountsRead], StatusCode::OK)]
+    #[case::admin_permissions("/accounts", vec![Role::Admin], vec![], StatusCode::OK)]
+    #[actix_web::test]
+    async fn test_access(
+        jwt_service: Arc<dyn JsonWebTokenService>,
+        #[case] uri: &str,
+        #[case] roles: Vec<Role>,
+        #[case] permissions: Vec<Permission>,
+        #[case] status: StatusCode,
+    ) {
+        let token = token(&jwt_service, roles, permissions);
+
+        assert_eq!(send_req(uri, Some(token), jwt_service).await, status);
+    }
+}
@@ -4,8 +4,9 @@ use std::ops::Deref;
 use crate::domain::error::AppError;
 use actix_web::FromRequest;
 use actix_web::HttpRequest;
-use actix_web::dev::{JsonBody, Payload};
-use futures::future::{FutureExt, LocalBoxFuture};
+use actix_web::dev::{JsonBody, Payload, UrlEncoded};
+use actix_web::web::Query as QueryString;
+use futures::future::{FutureExt, LocalBoxFuture, Ready, ready};
 use serde::de::DeserializeOwned;
 use validator::Validate;
 
@@ -59,6 +60,83 @@ where
     }
 }
 
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+impl<T> Query<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for Query<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            QueryString::<T>::from_query(req.query_string())
+                .map_err(AppError::from)
+                .and_then(|query| {
+                    let query = query.into_inner();
+
+                    query
+                        .validate()
+                        .map(|_| Query(query))
+                        .map_err(AppError::from)
+                }),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Form<T>(pub T);
+
+impl<T> Form<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Form<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for Form<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        UrlEncoded::new(req, payload)
+            .limit(16384)
+            .map(|res: Result<T, _>| match res {
+                Ok(form) => form.validate().map(|_| Form(form)).map_err(AppError::from),
+                Err(err) => Err(AppError::from(err)),
+            })
+            .boxed_local()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -95,6 +173,44 @@ mod tests {
         HttpResponse::Ok().json(data.0)
     }
 
+    async fn search(query: Query<UserDTO>) -> impl Responder {
+        HttpResponse::Ok().json(query.0)
+    }
+
+    async fn submit(form: Form<UserDTO>) -> impl Responder {
+        HttpResponse::Ok().json(form.0)
+    }
+
+    async fn send_form<T: DeserializeOwned>(form: &str) -> (StatusCode, T) {
+        let app = test::init_service(App::new().route("/submit", web::post().to(submit))).await;
+
+        let res = TestRequest::post()
+            .uri("/submit")
+            .set_payload(form.to_string())
+            .insert_header(ContentType::form_url_encoded())
+            .send_request(&app)
+            .await;
+
+        let status = res.status();
+        let body: T = test::read_body_json(res).await;
+
+        (status, body)
+    }
+
+    async fn send_query<T: DeserializeOwned>(query: &str) -> (StatusCode, T) {
+        let app = test::init_service(App::new().route("/search", web::get().to(search))).await;
+
+        let res = TestRequest::get()
+            .uri(&format!("/search?{query}"))
+            .send_request(&app)
+            .await;
+
+        let status = res.status();
+        let body: T = test::read_body_json(res).await;
+
+        (status, body)
+    }
+
     async fn send_req<T: DeserializeOwned>(data: &str) -> (StatusCode, T) {
         let app = test::init_service(App::new().route("/index", web::post().to(index))).await;
 
@@ -193,4 +309,54 @@ mod tests {
             "Json deserialize error: EOF while parsing a value at line 1 column 0"
         );
     }
+
+    #[actix_web::test]
+    async fn test_valid_query() {
+        let (status, body) =
+            send_query::<UserDTO>("name=new_user&email=new_user%40spacecraft.com").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.email, "new_user@spacecraft.com");
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_query_value() {
+        let (status, err) = send_query::<Error>("name=new_user&email=spacecraft.com").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.message, "{\"email\":\"Invalid email format\"}");
+    }
+
+    #[actix_web::test]
+    async fn test_missing_query_parameter() {
+        let (status, err) = send_query::<Error>("name=new_user").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.code, 400);
+    }
+
+    #[actix_web::test]
+    async fn test_valid_form() {
+        let (status, body) =
+            send_form::<UserDTO>("name=new_user&email=new_user%40spacecraft.com").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.email, "new_user@spacecraft.com");
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_form_value() {
+        let (status, err) = send_form::<Error>("name=new_user&email=spacecraft.com").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.message, "{\"email\":\"Invalid email format\"}");
+    }
+
+    #[actix_web::test]
+    async fn test_missing_form_field() {
+        let (status, err) = send_form::<Error>("name=new_user").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.code, 400);
+    }
 }
@@ -5,6 +5,21 @@ mod dto;
 mod error;
 mod middlewares;
 
+pub use middlewares::csrf::csrf;
+pub use middlewares::rate_limit::rate_limit;
+
 pub fn routes(cfg: &mut ServiceConfig) {
-    cfg.service(scope("/api/v1").configure(controllers::account::routes));
+    cfg.service(
+        scope("/api/v1")
+            .configure(controllers::account::routes)
+            .configure(controllers::password::routes)
+            .configure(controllers::mfa::routes)
+            .configure(controllers::webauthn::routes)
+            .configure(controllers::oidc::routes)
+            .configure(controllers::api_key::routes)
+            .configure(controllers::session::routes)
+            .configure(controllers::admin::routes)
+            .configure(controllers::oauth::routes),
+    )
+    .configure(controllers::jsonwebtoken::routes);
 }
@@ -40,20 +40,42 @@ pub fn create(
             SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", api)
         })
         .into_app()
+        .wrap(from_fn(api::csrf))
+        .wrap(from_fn(api::rate_limit))
         .wrap(TracingLogger::default())
         .wrap(cors())
         .wrap(from_fn(request_headers))
         .app_data(web::Data::new(container.account_service.clone()))
+        .app_data(web::Data::new(container.account_status_service.clone()))
+        .app_data(web::Data::new(container.account_deletion_service.clone()))
         .app_data(web::Data::new(container.jsonwebtoken_service.clone()))
+        .app_data(web::Data::new(container.refresh_token_service.clone()))
+        .app_data(web::Data::new(container.session_service.clone()))
+        .app_data(web::Data::new(container.password_reset_service.clone()))
+        .app_data(web::Data::new(container.email_verification_service.clone()))
+        .app_data(web::Data::new(container.totp_service.clone()))
+        .app_data(web::Data::new(container.webauthn_service.clone()))
+        .app_data(web::Data::new(container.rate_limit_service.clone()))
+        .app_data(web::Data::new(container.api_key_service.clone()))
+        .app_data(web::Data::new(container.oidc_service.clone()))
+        .app_data(web::Data::new(container.oauth_service.clone()))
+        .app_data(web::Data::new(container.csrf_config.clone()))
 }
 
 fn cors() -> Cors {
     Cors::default()
         .allowed_origin("http://localhost:8080")
-        .allowed_origin("http://localhost:8080")
-        .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+        .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE"])
         .allowed_headers(&[header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allowed_header("x-api-key")
+        .allowed_header("x-csrf-token")
         .allowed_header(header::CONTENT_TYPE)
+        .expose_headers([
+            "ratelimit-limit",
+            "ratelimit-remaining",
+            "ratelimit-reset",
+            "retry-after",
+        ])
         .block_on_origin_mismatch(false)
         .max_age(3600)
 }
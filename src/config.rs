@@ -2,6 +2,7 @@ use figment::{
     Figment,
     providers::{Env, Format, Serialized, Toml},
 };
+use jsonwebtoken::Algorithm;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -10,12 +11,196 @@ pub struct AppConfig {
     pub logging: LoggingConfig,
     pub surrealdb: SurrealDbConfig,
     pub jsonwebtoken: JsonWebTokenConfig,
+    pub password_reset: PasswordResetConfig,
+    pub email_verification: EmailVerificationConfig,
+    pub totp: TotpConfig,
+    pub webauthn: WebAuthnConfig,
+    pub signin_lockout: SigninLockoutConfig,
+    pub rate_limit: RateLimitConfig,
+    pub oidc: OidcConfig,
+    pub oauth: OAuthConfig,
+    pub password_hash: PasswordHashConfig,
+    pub password_policy: PasswordPolicyConfig,
+    pub password_history: PasswordHistoryConfig,
+    pub csrf: CsrfConfig,
+    pub account_deletion: AccountDeletionConfig,
+    pub mailer: MailerConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct JsonWebTokenConfig {
+    pub algorithm: Algorithm,
+    pub signing_kid: String,
+    pub keys: Vec<JsonWebKeyConfig>,
+    pub issuer: String,
+    pub audience: String,
+    pub ttl: i64,
+    pub refresh_ttl: i64,
+    pub mfa_ttl: i64,
+    pub not_before: i64,
+    pub leeway: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonWebKeyConfig {
+    pub kid: String,
     pub public_keyfile: String,
-    pub private_keyfile: String,
+    pub private_keyfile: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PasswordResetConfig {
+    pub ttl: i64,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct EmailVerificationConfig {
+    pub required: bool,
+    pub ttl: i64,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TotpConfig {
+    pub issuer: String,
+    pub recovery_codes: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct WebAuthnConfig {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origin: String,
+    pub ttl: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SigninLockoutConfig {
+    pub account_threshold: u32,
+    pub ip_threshold: u32,
+    pub window: i64,
+    pub lockout: i64,
+    pub max_lockout: i64,
+}
+
+// Path prefixes whose state-changing requests are accepted without a CSRF token
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CsrfConfig {
+    pub exempt: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RateLimitConfig {
+    pub backend: RateLimitBackend,
+    pub rules: Vec<RateLimitRuleConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitBackend {
+    #[default]
+    Memory,
+    SurrealDb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitKey {
+    #[default]
+    Ip,
+    Sub,
+    Route,
+}
+
+// Token bucket of `capacity` requests, refilled at `capacity` tokens every `period` seconds
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RateLimitRuleConfig {
+    pub scope: String,
+    pub key: RateLimitKey,
+    pub capacity: u32,
+    pub period: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct OidcConfig {
+    pub ttl: i64,
+    pub providers: Vec<OidcProviderConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OidcProviderConfig {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct OAuthConfig {
+    pub code_ttl: i64,
+    pub refresh_ttl: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordHashAlgorithm {
+    Argon2d,
+    Argon2i,
+    #[default]
+    Argon2id,
+}
+
+// Raising any cost upgrades existing hashes on the next successful signin
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PasswordHashConfig {
+    pub algorithm: PasswordHashAlgorithm,
+    pub version: u32,
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+    pub pepper: Option<String>,
+}
+
+// Applied on top of the request validation wherever a new password is set
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PasswordPolicyConfig {
+    pub min_entropy: f64,
+    pub reject_personal_info: bool,
+    pub breached_passwords: Option<String>,
+}
+
+// Counts the current password, so 1 only forbids setting the same password again and 0 disables it
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PasswordHistoryConfig {
+    pub size: usize,
+}
+
+// Deleted accounts can be restored for `retention` seconds, a purge every `purge_interval` seconds
+// then removes them for good
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct AccountDeletionConfig {
+    pub retention: i64,
+    pub purge_interval: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MailerBackend {
+    #[default]
+    Log,
+    Http,
+}
+
+// With the http backend every mail is POSTed as JSON to `endpoint`, `api_key` is sent as a bearer token
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct MailerConfig {
+    pub backend: MailerBackend,
+    pub from: String,
+    pub endpoint: String,
+    pub api_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -41,7 +226,7 @@ pub struct SurrealDbConfig {
 
 impl AppConfig {
     pub fn load() -> Result<Self, figment::Error> {
-        Figment::new()
+        let config: AppConfig = Figment::new()
             .merge(Serialized::defaults(AppConfig {
                 service: ServiceConfig {
                     name: "surreal-actix".to_string(),
@@ -50,8 +235,83 @@ impl AppConfig {
                     level: "info".to_string(),
                 },
                 jsonwebtoken: JsonWebTokenConfig {
-                    public_keyfile: "config/public_key.pem".to_string(),
-                    private_keyfile: "config/private_key.pem".to_string(),
+                    algorithm: Algorithm::RS256,
+                    signing_kid: "default".to_string(),
+                    keys: vec![JsonWebKeyConfig {
+                        kid: "default".to_string(),
+                        public_keyfile: "config/public_key.pem".to_string(),
+                        private_keyfile: Some("config/private_key.pem".to_string()),
+                    }],
+                    issuer: "surreal-actix".to_string(),
+                    audience: "surreal-actix".to_string(),
+                    ttl: 3600,
+                    refresh_ttl: 2592000,
+                    mfa_ttl: 300,
+                    not_before: 0,
+                    leeway: 60,
+                },
+                password_reset: PasswordResetConfig {
+                    ttl: 900,
+                    url: "http://localhost:8080/password/reset?token=".to_string(),
+                },
+                email_verification: EmailVerificationConfig {
+                    required: false,
+                    ttl: 86400,
+                    url: "http://localhost:8080/api/v1/verify-email?token=".to_string(),
+                },
+                totp: TotpConfig {
+                    issuer: "surreal-actix".to_string(),
+                    recovery_codes: 10,
+                },
+                webauthn: WebAuthnConfig {
+                    rp_id: "localhost".to_string(),
+                    rp_name: "surreal-actix".to_string(),
+                    origin: "http://localhost:8080".to_string(),
+                    ttl: 300,
+                },
+                signin_lockout: SigninLockoutConfig {
+                    account_threshold: 5,
+                    ip_threshold: 20,
+                    window: 900,
+                    lockout: 60,
+                    max_lockout: 3600,
+                },
+                rate_limit: RateLimitConfig {
+                    backend: RateLimitBackend::Memory,
+                    rules: vec![],
+                },
+                oidc: OidcConfig {
+                    ttl: 600,
+                    providers: vec![],
+                },
+                oauth: OAuthConfig {
+                    code_ttl: 60,
+                    refresh_ttl: 2592000,
+                },
+                password_hash: PasswordHashConfig {
+                    algorithm: PasswordHashAlgorithm::Argon2id,
+                    version: 19,
+                    memory_cost: 19456,
+                    time_cost: 2,
+                    parallelism: 1,
+                    pepper: None,
+                },
+                password_policy: PasswordPolicyConfig {
+                    min_entropy: 50.0,
+                    reject_personal_info: true,
+                    breached_passwords: Some("config/breached_passwords.txt".to_string()),
+                },
+                password_history: PasswordHistoryConfig { size: 5 },
+                csrf: CsrfConfig { exempt: vec![] },
+                account_deletion: AccountDeletionConfig {
+                    retention: 2592000,
+                    purge_interval: 3600,
+                },
+                mailer: MailerConfig {
+                    backend: MailerBackend::Log,
+                    from: "no-reply@localhost".to_string(),
+                    endpoint: String::new(),
+                    api_key: None,
                 },
                 surrealdb: SurrealDbConfig {
                     host: "localhost".to_string(),
@@ -69,6 +329,15 @@ impl AppConfig {
                 std::env::var("RUST_ENV").unwrap_or("development".to_string())
             )))
             .merge(Env::prefixed("APP_").split("__"))
-            .extract()
+            .extract()?;
+
+        // The purge runs on a tokio interval, which panics on a zero period
+        if config.account_deletion.purge_interval == 0 {
+            return Err(figment::Error::from(
+                "account_deletion.purge_interval must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(config)
     }
 }
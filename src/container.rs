@@ -3,38 +3,455 @@ use std::sync::Arc;
 use surrealdb::Surreal;
 use surrealdb::engine::remote::ws::Client;
 
+use crate::config::{
+    AccountDeletionConfig, AppConfig, CsrfConfig, EmailVerificationConfig, JsonWebTokenConfig,
+    MailerBackend, MailerConfig, OAuthConfig, OidcConfig, PasswordHistoryConfig,
+    PasswordResetConfig, RateLimitBackend, RateLimitConfig, SigninLockoutConfig, TotpConfig,
+    WebAuthnConfig,
+};
 use crate::domain::repositories::account::AccountRepository;
+use crate::domain::repositories::account_audit::AccountAuditRepository;
+use crate::domain::repositories::api_key::ApiKeyRepository;
+use crate::domain::repositories::email_verification::EmailVerificationRepository;
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::repositories::oidc::OidcRepository;
+use crate::domain::repositories::password_history::PasswordHistoryRepository;
+use crate::domain::repositories::password_reset::PasswordResetRepository;
+use crate::domain::repositories::rate_limit::RateLimitRepository;
+use crate::domain::repositories::refresh_token::RefreshTokenRepository;
+use crate::domain::repositories::revoked_token::RevokedTokenRepository;
+use crate::domain::repositories::session::SessionRepository;
+use crate::domain::repositories::signin_attempt::SigninAttemptRepository;
+use crate::domain::repositories::totp::TotpRepository;
+use crate::domain::repositories::webauthn::WebAuthnRepository;
 use crate::domain::services::account::AccountService;
+use crate::domain::services::account_deletion::AccountDeletionService;
+use crate::domain::services::account_status::AccountStatusService;
+use crate::domain::services::api_key::ApiKeyService;
+use crate::domain::services::email_verification::EmailVerificationService;
 use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::domain::services::mailer::MailerService;
+use crate::domain::services::oauth::OAuthService;
+use crate::domain::services::oidc::OidcService;
+use crate::domain::services::password_reset::PasswordResetService;
+use crate::domain::services::rate_limit::RateLimitService;
+use crate::domain::services::refresh_token::RefreshTokenService;
+use crate::domain::services::session::SessionService;
+use crate::domain::services::totp::TotpService;
+use crate::domain::services::webauthn::WebAuthnService;
 
 use crate::services::account::AccountServiceImpl;
-use crate::services::jsonwebtoken::{JsonWebTokenServiceImpl, KeyPair};
+use crate::services::account_deletion::AccountDeletionServiceImpl;
+use crate::services::account_status::AccountStatusServiceImpl;
+use crate::services::api_key::ApiKeyServiceImpl;
+use crate::services::email_verification::EmailVerificationServiceImpl;
+use crate::services::hasher::Argon2Hasher;
+use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+use crate::services::keyring::Keyring;
+use crate::services::mailer::{HttpMailerServiceImpl, LogMailerServiceImpl};
+use crate::services::oauth::OAuthServiceImpl;
+use crate::services::oidc::OidcServiceImpl;
+use crate::services::password_history::PasswordHistory;
+use crate::services::password_policy::PasswordPolicy;
+use crate::services::password_reset::PasswordResetServiceImpl;
+use crate::services::rate_limit::RateLimitServiceImpl;
+use crate::services::refresh_token::RefreshTokenServiceImpl;
+use crate::services::session::SessionServiceImpl;
+use crate::services::signin_lockout::SigninLockout;
+use crate::services::totp::TotpServiceImpl;
+use crate::services::webauthn::WebAuthnServiceImpl;
 
 use crate::infrastructure::repositories::account::AccountRepositoryImpl;
+use crate::infrastructure::repositories::account_audit::AccountAuditRepositoryImpl;
+use crate::infrastructure::repositories::api_key::ApiKeyRepositoryImpl;
+use crate::infrastructure::repositories::email_verification::EmailVerificationRepositoryImpl;
+use crate::infrastructure::repositories::oauth::OAuthRepositoryImpl;
+use crate::infrastructure::repositories::oidc::OidcRepositoryImpl;
+use crate::infrastructure::repositories::password_history::PasswordHistoryRepositoryImpl;
+use crate::infrastructure::repositories::password_reset::PasswordResetRepositoryImpl;
+use crate::infrastructure::repositories::rate_limit::{
+    MemoryRateLimitRepositoryImpl, RateLimitRepositoryImpl,
+};
+use crate::infrastructure::repositories::refresh_token::RefreshTokenRepositoryImpl;
+use crate::infrastructure::repositories::revoked_token::RevokedTokenRepositoryImpl;
+use crate::infrastructure::repositories::session::SessionRepositoryImpl;
+use crate::infrastructure::repositories::signin_attempt::SigninAttemptRepositoryImpl;
+use crate::infrastructure::repositories::totp::TotpRepositoryImpl;
+use crate::infrastructure::repositories::webauthn::WebAuthnRepositoryImpl;
 
 pub struct Container {
     pub account_service: Arc<dyn AccountService>,
+    pub account_status_service: Arc<dyn AccountStatusService>,
+    pub account_deletion_service: Arc<dyn AccountDeletionService>,
     pub jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    pub refresh_token_service: Arc<dyn RefreshTokenService>,
+    pub session_service: Arc<dyn SessionService>,
+    pub password_reset_service: Arc<dyn PasswordResetService>,
+    pub email_verification_service: Arc<dyn EmailVerificationService>,
+    pub totp_service: Arc<dyn TotpService>,
+    pub webauthn_service: Arc<dyn WebAuthnService>,
+    pub rate_limit_service: Arc<dyn RateLimitService>,
+    pub api_key_service: Arc<dyn ApiKeyService>,
+    pub oidc_service: Arc<dyn OidcService>,
+    pub oauth_service: Arc<dyn OAuthService>,
+    pub csrf_config: CsrfConfig,
 }
 
 impl Container {
-    pub fn new(conn: Surreal<Client>, keys: KeyPair) -> Self {
+    pub fn new(
+        conn: Surreal<Client>,
+        keys: Keyring,
+        hasher: Argon2Hasher,
+        policy: PasswordPolicy,
+        config: &AppConfig,
+    ) -> Self {
         let db = Arc::new(conn);
 
+        let hasher = Arc::new(hasher);
+        let policy = Arc::new(policy);
+        let history = password_history(db.clone(), &config.password_history, hasher.clone());
+        let lockout = signin_lockout(db.clone(), &config.signin_lockout);
+
+        let mailer = mailer_service(&config.mailer);
+
+        let jsonwebtoken_service = jsonwebtoken_service(db.clone(), keys, &config.jsonwebtoken);
+        let email_verification_service =
+            email_verification_service(db.clone(), &config.email_verification, mailer.clone());
+
         Container {
-            account_service: account_service(db.clone()),
-            jsonwebtoken_service: jsonwebtoken_service(keys),
+            account_service: account_service(
+                db.clone(),
+                &config.email_verification,
+                hasher.clone(),
+                policy.clone(),
+                history.clone(),
+                lockout.clone(),
+            ),
+            account_status_service: account_status_service(
+                db.clone(),
+                jsonwebtoken_service.clone(),
+            ),
+            account_deletion_service: account_deletion_service(
+                db.clone(),
+                &config.account_deletion,
+            ),
+            jsonwebtoken_service: jsonwebtoken_service.clone(),
+            refresh_token_service: refresh_token_service(db.clone(), &config.jsonwebtoken),
+            session_service: session_service(db.clone(), jsonwebtoken_service.clone()),
+            password_reset_service: password_reset_service(
+                db.clone(),
+                &config.password_reset,
+                hasher.clone(),
+                policy.clone(),
+                history,
+                mailer.clone(),
+            ),
+            email_verification_service: email_verification_service.clone(),
+            totp_service: totp_service(db.clone(), &config.totp, lockout),
+            webauthn_service: webauthn_service(
+                db.clone(),
+                &config.webauthn,
+                &config.email_verification,
+            ),
+            rate_limit_service: rate_limit_service(db.clone(), &config.rate_limit),
+            api_key_service: api_key_service(db.clone(), &config.jsonwebtoken),
+            oidc_service: oidc_service(
+                db.clone(),
+                &config.oidc,
+                &config.email_verification,
+                hasher.clone(),
+                email_verification_service,
+            ),
+            oauth_service: oauth_service(db.clone(), &config.oauth, jsonwebtoken_service),
+            csrf_config: config.csrf.clone(),
         }
     }
 }
 
-fn account_service(db: Arc<Surreal<Client>>) -> Arc<dyn AccountService> {
+fn account_service(
+    db: Arc<Surreal<Client>>,
+    config: &EmailVerificationConfig,
+    hasher: Arc<Argon2Hasher>,
+    policy: Arc<PasswordPolicy>,
+    history: Arc<PasswordHistory>,
+    lockout: Arc<SigninLockout>,
+) -> Arc<dyn AccountService> {
     let account_repository: Arc<dyn AccountRepository> =
         Arc::new(AccountRepositoryImpl::new(db.clone()));
 
-    Arc::new(AccountServiceImpl::new(account_repository))
+    Arc::new(AccountServiceImpl::new(
+        config,
+        hasher,
+        policy,
+        history,
+        lockout,
+        account_repository,
+    ))
+}
+
+fn account_status_service(
+    db: Arc<Surreal<Client>>,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+) -> Arc<dyn AccountStatusService> {
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    let account_audit_repository: Arc<dyn AccountAuditRepository> =
+        Arc::new(AccountAuditRepositoryImpl::new(db.clone()));
+
+    Arc::new(AccountStatusServiceImpl::new(
+        account_repository,
+        account_audit_repository,
+        jsonwebtoken_service,
+    ))
+}
+
+fn account_deletion_service(
+    db: Arc<Surreal<Client>>,
+    config: &AccountDeletionConfig,
+) -> Arc<dyn AccountDeletionService> {
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    let webauthn_repository: Arc<dyn WebAuthnRepository> =
+        Arc::new(WebAuthnRepositoryImpl::new(db.clone()));
+
+    let oidc_repository: Arc<dyn OidcRepository> = Arc::new(OidcRepositoryImpl::new(db.clone()));
+
+    let oauth_repository: Arc<dyn OAuthRepository> = Arc::new(OAuthRepositoryImpl::new(db.clone()));
+
+    Arc::new(AccountDeletionServiceImpl::new(
+        config,
+        account_repository,
+        webauthn_repository,
+        oidc_repository,
+        oauth_repository,
+    ))
+}
+
+fn jsonwebtoken_service(
+    db: Arc<Surreal<Client>>,
+    keys: Keyring,
+    config: &JsonWebTokenConfig,
+) -> Arc<dyn JsonWebTokenService> {
+    let revoked_token_repository: Arc<dyn RevokedTokenRepository> =
+        Arc::new(RevokedTokenRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    let session_repository: Arc<dyn SessionRepository> =
+        Arc::new(SessionRepositoryImpl::new(db.clone()));
+
+    Arc::new(JsonWebTokenServiceImpl::new(
+        keys,
+        config,
+        revoked_token_repository,
+        account_repository,
+        session_repository,
+    ))
+}
+
+fn refresh_token_service(
+    db: Arc<Surreal<Client>>,
+    config: &JsonWebTokenConfig,
+) -> Arc<dyn RefreshTokenService> {
+    let refresh_token_repository: Arc<dyn RefreshTokenRepository> =
+        Arc::new(RefreshTokenRepositoryImpl::new(db.clone()));
+
+    Arc::new(RefreshTokenServiceImpl::new(
+        config,
+        refresh_token_repository,
+    ))
+}
+
+fn session_service(
+    db: Arc<Surreal<Client>>,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+) -> Arc<dyn SessionService> {
+    let session_repository: Arc<dyn SessionRepository> =
+        Arc::new(SessionRepositoryImpl::new(db.clone()));
+
+    let refresh_token_repository: Arc<dyn RefreshTokenRepository> =
+        Arc::new(RefreshTokenRepositoryImpl::new(db.clone()));
+
+    let oauth_repository: Arc<dyn OAuthRepository> = Arc::new(OAuthRepositoryImpl::new(db.clone()));
+
+    Arc::new(SessionServiceImpl::new(
+        session_repository,
+        refresh_token_repository,
+        oauth_repository,
+        jsonwebtoken_service,
+    ))
+}
+
+fn password_history(
+    db: Arc<Surreal<Client>>,
+    config: &PasswordHistoryConfig,
+    hasher: Arc<Argon2Hasher>,
+) -> Arc<PasswordHistory> {
+    let password_history_repository: Arc<dyn PasswordHistoryRepository> =
+        Arc::new(PasswordHistoryRepositoryImpl::new(db.clone()));
+
+    Arc::new(PasswordHistory::new(
+        config,
+        hasher,
+        password_history_repository,
+    ))
+}
+
+fn signin_lockout(db: Arc<Surreal<Client>>, config: &SigninLockoutConfig) -> Arc<SigninLockout> {
+    let signin_attempt_repository: Arc<dyn SigninAttemptRepository> =
+        Arc::new(SigninAttemptRepositoryImpl::new(db.clone()));
+
+    Arc::new(SigninLockout::new(config, signin_attempt_repository))
+}
+
+fn mailer_service(config: &MailerConfig) -> Arc<dyn MailerService> {
+    match config.backend {
+        MailerBackend::Log => Arc::new(LogMailerServiceImpl),
+        MailerBackend::Http => Arc::new(HttpMailerServiceImpl::new(config)),
+    }
 }
 
-fn jsonwebtoken_service(keys: KeyPair) -> Arc<dyn JsonWebTokenService> {
-    Arc::new(JsonWebTokenServiceImpl::new(keys))
+fn password_reset_service(
+    db: Arc<Surreal<Client>>,
+    config: &PasswordResetConfig,
+    hasher: Arc<Argon2Hasher>,
+    policy: Arc<PasswordPolicy>,
+    history: Arc<PasswordHistory>,
+    mailer: Arc<dyn MailerService>,
+) -> Arc<dyn PasswordResetService> {
+    let password_reset_repository: Arc<dyn PasswordResetRepository> =
+        Arc::new(PasswordResetRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(PasswordResetServiceImpl::new(
+        config,
+        hasher,
+        policy,
+        history,
+        password_reset_repository,
+        account_repository,
+        mailer,
+    ))
+}
+
+fn email_verification_service(
+    db: Arc<Surreal<Client>>,
+    config: &EmailVerificationConfig,
+    mailer: Arc<dyn MailerService>,
+) -> Arc<dyn EmailVerificationService> {
+    let email_verification_repository: Arc<dyn EmailVerificationRepository> =
+        Arc::new(EmailVerificationRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(EmailVerificationServiceImpl::new(
+        config,
+        email_verification_repository,
+        account_repository,
+        mailer,
+    ))
+}
+
+fn totp_service(
+    db: Arc<Surreal<Client>>,
+    config: &TotpConfig,
+    lockout: Arc<SigninLockout>,
+) -> Arc<dyn TotpService> {
+    let totp_repository: Arc<dyn TotpRepository> = Arc::new(TotpRepositoryImpl::new(db.clone()));
+
+    Arc::new(TotpServiceImpl::new(config, lockout, totp_repository))
+}
+
+fn webauthn_service(
+    db: Arc<Surreal<Client>>,
+    config: &WebAuthnConfig,
+    verification_config: &EmailVerificationConfig,
+) -> Arc<dyn WebAuthnService> {
+    let webauthn_repository: Arc<dyn WebAuthnRepository> =
+        Arc::new(WebAuthnRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(WebAuthnServiceImpl::new(
+        config,
+        verification_config,
+        webauthn_repository,
+        account_repository,
+    ))
+}
+
+fn rate_limit_service(
+    db: Arc<Surreal<Client>>,
+    config: &RateLimitConfig,
+) -> Arc<dyn RateLimitService> {
+    let rate_limit_repository: Arc<dyn RateLimitRepository> = match config.backend {
+        RateLimitBackend::Memory => Arc::new(MemoryRateLimitRepositoryImpl::default()),
+        RateLimitBackend::SurrealDb => Arc::new(RateLimitRepositoryImpl::new(db.clone())),
+    };
+
+    Arc::new(RateLimitServiceImpl::new(config, rate_limit_repository))
+}
+
+fn api_key_service(
+    db: Arc<Surreal<Client>>,
+    config: &JsonWebTokenConfig,
+) -> Arc<dyn ApiKeyService> {
+    let api_key_repository: Arc<dyn ApiKeyRepository> =
+        Arc::new(ApiKeyRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(ApiKeyServiceImpl::new(
+        config,
+        api_key_repository,
+        account_repository,
+    ))
+}
+
+fn oidc_service(
+    db: Arc<Surreal<Client>>,
+    config: &OidcConfig,
+    verification_config: &EmailVerificationConfig,
+    hasher: Arc<Argon2Hasher>,
+    email_verification_service: Arc<dyn EmailVerificationService>,
+) -> Arc<dyn OidcService> {
+    let oidc_repository: Arc<dyn OidcRepository> = Arc::new(OidcRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(OidcServiceImpl::new(
+        config,
+        verification_config,
+        hasher,
+        email_verification_service,
+        oidc_repository,
+        account_repository,
+    ))
+}
+
+fn oauth_service(
+    db: Arc<Surreal<Client>>,
+    config: &OAuthConfig,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+) -> Arc<dyn OAuthService> {
+    let oauth_repository: Arc<dyn OAuthRepository> = Arc::new(OAuthRepositoryImpl::new(db.clone()));
+
+    let account_repository: Arc<dyn AccountRepository> =
+        Arc::new(AccountRepositoryImpl::new(db.clone()));
+
+    Arc::new(OAuthServiceImpl::new(
+        config,
+        oauth_repository,
+        account_repository,
+        jsonwebtoken_service,
+    ))
 }
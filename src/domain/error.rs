@@ -1,10 +1,13 @@
 use actix_web::{
     HttpResponse, ResponseError,
-    http::{StatusCode, header::ContentType},
+    http::{
+        StatusCode,
+        header::{ContentType, RETRY_AFTER},
+    },
     web::Json,
 };
 
-use actix_web::error::JsonPayloadError;
+use actix_web::error::{JsonPayloadError, QueryPayloadError, UrlencodedError};
 
 use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
@@ -25,6 +28,7 @@ macro_rules! static_error {
                 message: message.to_string(),
                 code: $status.as_u16(),
                 trace: None,
+                retry_after: None,
             }
         }
     };
@@ -36,6 +40,7 @@ macro_rules! static_error {
                 message: $default.to_string(),
                 code: $status.as_u16(),
                 trace: None,
+                retry_after: None,
             }
         }
     };
@@ -47,17 +52,23 @@ pub struct AppError {
     pub code: u16,
     #[serde(skip)]
     pub trace: Option<String>,
+    #[serde(skip)]
+    pub retry_after: Option<u64>,
 }
 
 #[rustfmt::skip]
 pub mod message {
     pub static CONFLICT: &str = "Conflict with the current state of the resource";
-    // pub static NOT_FOUND: &str = "The server cannot find the requested resource";
+    pub static NOT_FOUND: &str = "The server cannot find the requested resource";
     pub static UNAUTHORIZED: &str = "The request was not successful because it lacks valid authentication credentials";
+    pub static FORBIDDEN: &str = "The server understood the request but the credentials do not grant access to the resource";
     pub static UNPROCESSABLE_ENTITY: &str = "The server was unable to process the request because it contains invalid data";
     pub static BAD_REQUEST: &str = "The server would not process the request due to something the server considered to be a client error";
     pub static INTERNAL_ERROR: &str = "The server encountered an unexpected condition that prevented it from fulfilling the request";
     pub static SERVICE_UNAVAILABLE: &str = "The server is not ready to handle the request";
+    pub static TOO_MANY_REQUESTS: &str = "The client has sent too many requests in a given amount of time";
+    pub static SUSPENDED: &str = "The account has been suspended";
+    pub static DEACTIVATED: &str = "The account has been deactivated";
 }
 
 #[rustfmt::skip]
@@ -66,18 +77,28 @@ impl AppError {
     static_error!(Conflict, StatusCode::CONFLICT);
     static_error!(BadRequest, StatusCode::BAD_REQUEST);
     static_error!(UnprocessableEntity, StatusCode::UNPROCESSABLE_ENTITY);
-    // static_error!(NotFound, StatusCode::NOT_FOUND);
+    static_error!(NotFound, StatusCode::NOT_FOUND);
 
     // 2. Errors with Default Message
     static_error!(Unauthorized, StatusCode::UNAUTHORIZED, message::UNAUTHORIZED);
+    static_error!(Forbidden, StatusCode::FORBIDDEN, message::FORBIDDEN);
     static_error!(InternalError, StatusCode::INTERNAL_SERVER_ERROR, message::INTERNAL_ERROR);
     static_error!(ServiceUnavailable, StatusCode::SERVICE_UNAVAILABLE, message::SERVICE_UNAVAILABLE);
+    static_error!(TooManyRequests, StatusCode::TOO_MANY_REQUESTS, message::TOO_MANY_REQUESTS);
+    static_error!(Suspended, StatusCode::LOCKED, message::SUSPENDED);
+    static_error!(Deactivated, StatusCode::GONE, message::DEACTIVATED);
 
     pub fn trace(self, message: &str) -> AppError {
         AppError {
-            code: self.code,
-            message: self.message,
             trace: Some(message.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn retry_after(self, seconds: u64) -> AppError {
+        AppError {
+            retry_after: Some(seconds),
+            ..self
         }
     }
 
@@ -93,6 +114,10 @@ impl AppError {
         AppError::Unauthorized()
     }
 
+    pub fn example_403() -> AppError {
+        AppError::Forbidden()
+    }
+
     pub fn example_422() -> AppError {
         AppError::UnprocessableEntity(message::UNPROCESSABLE_ENTITY)
     }
@@ -105,9 +130,21 @@ impl AppError {
         AppError::Conflict(message::CONFLICT)
     }
 
-    // pub fn example_404() -> AppError {
-    //     AppError::NotFound(message::NOT_FOUND)
-    // }
+    pub fn example_429() -> AppError {
+        AppError::TooManyRequests()
+    }
+
+    pub fn example_404() -> AppError {
+        AppError::NotFound(message::NOT_FOUND)
+    }
+
+    pub fn example_423() -> AppError {
+        AppError::Suspended()
+    }
+
+    pub fn example_410() -> AppError {
+        AppError::Deactivated()
+    }
 }
 
 impl std::error::Error for AppError {}
@@ -120,9 +157,13 @@ impl std::fmt::Display for AppError {
 
 impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
-        HttpResponse::build(self.status_code())
-            .content_type(ContentType::json())
-            .json(Json(self))
+        let mut response = HttpResponse::build(self.status_code());
+
+        if let Some(seconds) = self.retry_after {
+            response.insert_header((RETRY_AFTER, seconds));
+        }
+
+        response.content_type(ContentType::json()).json(Json(self))
     }
 
     fn status_code(&self) -> StatusCode {
@@ -197,6 +238,18 @@ impl From<JsonPayloadError> for AppError {
     }
 }
 
+impl From<QueryPayloadError> for AppError {
+    fn from(error: QueryPayloadError) -> Self {
+        AppError::BadRequest(error.to_string())
+    }
+}
+
+impl From<UrlencodedError> for AppError {
+    fn from(error: UrlencodedError) -> Self {
+        AppError::BadRequest(error.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
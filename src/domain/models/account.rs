@@ -1,9 +1,114 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, AppResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Admin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Permission {
+    #[serde(rename = "accounts:read")]
+    AccountsRead,
+    #[serde(rename = "accounts:write")]
+    AccountsWrite,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::AccountsRead => "accounts:read",
+            Permission::AccountsWrite => "accounts:write",
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = ();
+
+    fn from_str(permission: &str) -> Result<Self, Self::Err> {
+        match permission {
+            "accounts:read" => Ok(Permission::AccountsRead),
+            "accounts:write" => Ok(Permission::AccountsWrite),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountStatus {
+    #[default]
+    Active,
+    // Set by an administrator, the account keeps its sessions but cannot use them
+    Suspended,
+    // Set by the owner, the account is signed out everywhere until an administrator reactivates it
+    Deactivated,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Suspended => "suspended",
+            AccountStatus::Deactivated => "deactivated",
+        }
+    }
+
+    // Gate shared by every way of signing in or acting on behalf of the account
+    pub fn ensure_active(&self) -> AppResult<()> {
+        match self {
+            AccountStatus::Active => Ok(()),
+            AccountStatus::Suspended => Err(AppError::Suspended()),
+            AccountStatus::Deactivated => Err(AppError::Deactivated()),
+        }
+    }
+}
+
+impl Role {
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::User => &[],
+            Role::Admin => &[Permission::AccountsRead, Permission::AccountsWrite],
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Account {
     pub id: String,
     pub name: String,
     pub email: String,
     pub password: String,
+    pub roles: Vec<Role>,
+    pub permissions: Vec<Permission>,
+    pub password_changed_at: Option<i64>,
+    pub email_verified_at: Option<i64>,
+    pub status: AccountStatus,
+    pub deleted_at: Option<i64>,
+    pub created_at: i64,
+}
+
+impl Account {
+    // Permissions granted by the account roles plus the ones granted to the account directly
+    pub fn effective_permissions(&self) -> Vec<Permission> {
+        let mut permissions: Vec<Permission> = self
+            .roles
+            .iter()
+            .flat_map(|role| role.permissions().iter().copied())
+            .chain(self.permissions.iter().copied())
+            .collect();
+
+        permissions.sort();
+        permissions.dedup();
+
+        permissions
+    }
 }
 
 #[derive(Clone)]
@@ -13,6 +118,47 @@ pub struct CreateAccount {
     pub password: String,
 }
 
+#[derive(Clone, Default)]
+pub struct UpdateAccount {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ChangePassword {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Clone, Default)]
+pub struct AccountFilter {
+    // Case-insensitive substrings
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub created_after: Option<i64>,
+    pub created_before: Option<i64>,
+    // Accounts awaiting their purge are left out unless asked for
+    pub include_deleted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccountSortField {
+    #[default]
+    CreatedAt,
+    Email,
+    Name,
+}
+
+impl AccountSortField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountSortField::CreatedAt => "created_at",
+            AccountSortField::Email => "email",
+            AccountSortField::Name => "name",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Credentials {
     pub email: String,
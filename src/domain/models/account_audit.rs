@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountAuditAction {
+    Suspend,
+    Reactivate,
+    Deactivate,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAccountAudit {
+    pub account: String,
+    // Administrator who changed the account, or the account itself when it deactivates
+    pub actor: String,
+    pub action: AccountAuditAction,
+    pub reason: Option<String>,
+}
@@ -0,0 +1,40 @@
+use crate::domain::models::account::Permission;
+
+// Lets `get_token` tell API keys apart from JSON Web Tokens in the Authorization header
+pub const API_KEY_PREFIX: &str = "sak_";
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: String,
+    pub account: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Option<Vec<Permission>>,
+    pub expiration: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Clone)]
+pub struct CreateApiKey {
+    pub account: String,
+    pub name: String,
+    pub key_hash: String,
+    pub prefix: String,
+    pub scopes: Option<Vec<Permission>>,
+    pub expiration: Option<i64>,
+}
+
+#[derive(Clone)]
+pub struct NewApiKey {
+    pub name: String,
+    pub scopes: Option<Vec<Permission>>,
+    pub expiration: Option<i64>,
+}
+
+// The plain key is only available right after creation
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub key: String,
+    pub api_key: ApiKey,
+}
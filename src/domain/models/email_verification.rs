@@ -0,0 +1,14 @@
+#[derive(Debug, Clone)]
+pub struct EmailVerificationToken {
+    pub id: String,
+    pub account: String,
+    pub expiration: i64,
+    pub used: bool,
+}
+
+#[derive(Clone)]
+pub struct CreateEmailVerificationToken {
+    pub account: String,
+    pub token_hash: String,
+    pub expiration: i64,
+}
@@ -1,13 +1,34 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::models::account::{Permission, Role};
+
 pub struct AccessToken {
     pub token: String,
     pub expiration: i64,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Claims {
     pub sub: String,
-    pub exp: usize,
+    pub iss: String,
+    pub aud: String,
+    // Unset on API key claims, keys without an expiration never expire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<usize>,
+    pub nbf: usize,
     pub iat: usize,
+    pub jti: String,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+    // Set on tokens issued to OAuth clients through the authorization server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    // Set on client credentials tokens, whose subject is the client, to the account owning it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    // Set on tokens issued at signin, refers to the session they were issued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
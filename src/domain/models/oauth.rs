@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::account::Permission;
+use crate::domain::models::jsonwebtoken::AccessToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    AuthorizationCode,
+    ClientCredentials,
+    RefreshToken,
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuthClient {
+    pub id: String,
+    pub owner: String,
+    pub name: String,
+    // Public clients have no secret and must rely on PKCE alone
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+    pub scopes: Vec<Permission>,
+    pub created_at: i64,
+}
+
+#[derive(Clone)]
+pub struct CreateOAuthClient {
+    pub owner: String,
+    pub name: String,
+    pub secret_hash: Option<String>,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+    pub scopes: Vec<Permission>,
+}
+
+#[derive(Clone)]
+pub struct NewOAuthClient {
+    pub name: String,
+    pub confidential: bool,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<GrantType>,
+    pub scopes: Vec<Permission>,
+}
+
+// The plain secret is only available right after registration
+#[derive(Debug, Clone)]
+pub struct IssuedOAuthClient {
+    pub client_secret: Option<String>,
+    pub client: OAuthClient,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<Permission>,
+    pub state: Option<String>,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthorizationCode {
+    pub client: String,
+    pub account: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<Permission>,
+    pub code_challenge: String,
+}
+
+#[derive(Clone)]
+pub struct CreateAuthorizationCode {
+    pub client: String,
+    pub account: String,
+    pub code_hash: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<Permission>,
+    pub code_challenge: String,
+    pub expiration: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredOAuthRefreshToken {
+    pub id: String,
+    pub client: String,
+    pub account: String,
+    pub family: String,
+    pub scopes: Vec<Permission>,
+    pub expiration: i64,
+    pub rotated: bool,
+    pub revoked: bool,
+}
+
+#[derive(Clone)]
+pub struct CreateOAuthRefreshToken {
+    pub client: String,
+    pub account: String,
+    pub token_hash: String,
+    pub family: String,
+    pub scopes: Vec<Permission>,
+    pub expiration: i64,
+}
+
+#[derive(Clone)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Clone)]
+pub enum TokenGrant {
+    AuthorizationCode {
+        code: String,
+        redirect_uri: String,
+        code_verifier: String,
+    },
+    ClientCredentials {
+        scopes: Option<Vec<Permission>>,
+    },
+    RefreshToken {
+        refresh_token: String,
+        scopes: Option<Vec<Permission>>,
+    },
+}
+
+pub struct OAuthToken {
+    pub access_token: AccessToken,
+    pub refresh_token: Option<String>,
+    pub scopes: Vec<Permission>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    AccessToken,
+    RefreshToken,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenIntrospection {
+    pub token_type: TokenType,
+    pub client_id: Option<String>,
+    pub sub: String,
+    pub scopes: Vec<Permission>,
+    pub expiration: Option<i64>,
+    pub issued_at: Option<i64>,
+}
+
+// Scopes travel as a space-delimited list of permission names
+pub fn parse_scope(scope: &str) -> Option<Vec<Permission>> {
+    scope
+        .split_whitespace()
+        .map(|scope| scope.parse().ok())
+        .collect()
+}
+
+pub fn format_scope(scopes: &[Permission]) -> String {
+    scopes
+        .iter()
+        .map(Permission::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
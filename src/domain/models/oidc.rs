@@ -0,0 +1,37 @@
+#[derive(Debug, Clone)]
+pub struct OidcState {
+    pub account: Option<String>,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+// The state is sent to the provider and also kept in a cookie the callback must present
+#[derive(Debug, Clone)]
+pub struct OidcAuthorization {
+    pub url: String,
+    pub state: String,
+    pub expiration: i64,
+}
+
+#[derive(Clone)]
+pub struct CreateOidcState {
+    pub account: Option<String>,
+    pub provider: String,
+    pub state_hash: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub expiration: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountIdentity {
+    pub account: String,
+}
+
+#[derive(Clone)]
+pub struct CreateAccountIdentity {
+    pub account: String,
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+}
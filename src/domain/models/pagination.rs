@@ -0,0 +1,44 @@
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sort<F> {
+    pub field: F,
+    pub order: SortOrder,
+}
+
+// Keyset pagination: the cursor is the id of the last item of the previous page, so pages stay
+// stable while rows are inserted in front of it
+#[derive(Debug, Clone)]
+pub struct PageRequest<F> {
+    pub cursor: Option<String>,
+    pub limit: usize,
+    pub sort: Sort<F>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    // Expects one item past the limit to have been fetched, telling whether another page follows
+    pub fn from_overfetched(mut items: Vec<T>, limit: usize, id: impl Fn(&T) -> String) -> Self {
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(id)
+        } else {
+            None
+        };
+
+        Page { items, next_cursor }
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(Debug, Clone)]
+pub struct PasswordResetToken {
+    pub id: String,
+    pub account: String,
+    pub expiration: i64,
+    pub used: bool,
+}
+
+#[derive(Clone)]
+pub struct CreatePasswordResetToken {
+    pub account: String,
+    pub token_hash: String,
+    pub expiration: i64,
+}
+
+#[derive(Clone)]
+pub struct ResetPassword {
+    pub token: String,
+    pub password: String,
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, Clone)]
+pub struct RateLimitBucket {
+    pub allowed: bool,
+    pub tokens: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitRequest {
+    pub path: String,
+    pub route: String,
+    pub ip: Option<String>,
+    pub sub: Option<String>,
+}
+
+// Most restrictive bucket among the rules matching a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: u64,
+    pub retry_after: Option<u64>,
+}
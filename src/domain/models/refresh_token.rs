@@ -0,0 +1,26 @@
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub account: String,
+    // Tokens rotated from one another form a family, which is the session they were issued for
+    pub session: String,
+    pub token: String,
+    pub expiration: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredRefreshToken {
+    pub id: String,
+    pub account: String,
+    pub family: String,
+    pub expiration: i64,
+    pub rotated: bool,
+    pub revoked: bool,
+}
+
+#[derive(Clone)]
+pub struct CreateRefreshToken {
+    pub account: String,
+    pub token_hash: String,
+    pub family: String,
+    pub expiration: i64,
+}
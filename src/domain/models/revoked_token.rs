@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct RevokedToken {
+    pub jti: String,
+    pub expiration: i64,
+}
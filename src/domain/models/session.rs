@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub account: String,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: i64,
+    pub last_seen_at: i64,
+}
+
+#[derive(Clone)]
+pub struct CreateSession {
+    pub account: String,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Default)]
+pub struct SigninAttempt {
+    pub failures: u32,
+    pub locked_until: Option<i64>,
+}
@@ -0,0 +1,21 @@
+#[derive(Debug, Clone)]
+pub struct TotpCredential {
+    pub account: String,
+    pub secret: String,
+    pub confirmed: bool,
+    pub last_step: Option<u64>,
+}
+
+#[derive(Clone)]
+pub struct CreateTotpCredential {
+    pub account: String,
+    pub secret: String,
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    pub uri: String,
+    pub recovery_codes: Vec<String>,
+}
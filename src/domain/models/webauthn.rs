@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ceremony {
+    Registration,
+    Authentication,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebAuthnCredential {
+    pub id: String,
+    pub account: String,
+    pub credential_id: String,
+    pub public_key: String,
+    pub sign_count: u32,
+}
+
+#[derive(Clone)]
+pub struct CreateWebAuthnCredential {
+    pub account: String,
+    pub credential_id: String,
+    pub public_key: String,
+    pub sign_count: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebAuthnChallenge {
+    pub account: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct CreateWebAuthnChallenge {
+    pub account: Option<String>,
+    pub ceremony: Ceremony,
+    pub challenge_hash: String,
+    pub expiration: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistrationOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub rp_name: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub user_display_name: String,
+    pub timeout: i64,
+    pub exclude_credentials: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthenticationOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: i64,
+    pub allow_credentials: Vec<String>,
+}
+
+// Binary fields are base64url encoded, as sent by the browser
+#[derive(Clone)]
+pub struct RegisterCredential {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+#[derive(Clone)]
+pub struct AssertCredential {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
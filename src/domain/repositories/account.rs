@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 
-use crate::domain::models::account::{Account, CreateAccount};
+use crate::domain::models::account::{
+    Account, AccountFilter, AccountSortField, AccountStatus, CreateAccount, UpdateAccount,
+};
+use crate::domain::models::pagination::{Page, PageRequest};
 
 use super::repository::RepositoryResult;
 
@@ -30,4 +33,34 @@ pub trait AccountRepository: Send + Sync {
     async fn is_account(&self, email: &str) -> RepositoryResult<bool>;
     async fn signup(&self, new_account: CreateAccount) -> RepositoryResult<Account>;
     async fn find_one(&self, column: FindByCol) -> RepositoryResult<Option<Account>>;
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<Account>>;
+    async fn exists(&self, id: &str) -> RepositoryResult<bool>;
+    async fn find_page(
+        &self,
+        filter: AccountFilter,
+        page: PageRequest<AccountSortField>,
+    ) -> RepositoryResult<Page<Account>>;
+    async fn update(&self, id: &str, account: UpdateAccount) -> RepositoryResult<Option<Account>>;
+    async fn delete(&self, id: &str) -> RepositoryResult<bool>;
+    async fn update_password(
+        &self,
+        id: &str,
+        password: String,
+    ) -> RepositoryResult<Option<Account>>;
+    async fn rehash_password(
+        &self,
+        id: &str,
+        current: &str,
+        password: String,
+    ) -> RepositoryResult<()>;
+    async fn find_password_changed_since(&self, since: i64) -> RepositoryResult<Vec<Account>>;
+    async fn verify_email(&self, id: &str) -> RepositoryResult<Option<Account>>;
+    async fn update_status(
+        &self,
+        id: &str,
+        status: AccountStatus,
+    ) -> RepositoryResult<Option<Account>>;
+    async fn find_suspended(&self) -> RepositoryResult<Vec<String>>;
+    async fn restore(&self, id: &str, deleted_since: i64) -> RepositoryResult<Option<Account>>;
+    async fn purge(&self, deleted_before: i64) -> RepositoryResult<usize>;
 }
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::domain::models::account::{Account, AccountStatus};
+use crate::domain::models::account_audit::CreateAccountAudit;
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait AccountAuditRepository: Send + Sync {
+    // Sets the status and records the audit entry in a single transaction, an account that is
+    // missing or deleted is left untouched and yields None
+    async fn change_status(
+        &self,
+        status: AccountStatus,
+        audit: CreateAccountAudit,
+    ) -> RepositoryResult<Option<Account>>;
+}
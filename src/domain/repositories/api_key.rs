@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::models::api_key::{ApiKey, CreateApiKey};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn create(&self, api_key: CreateApiKey) -> RepositoryResult<ApiKey>;
+    async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKey>>;
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<ApiKey>>;
+    async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool>;
+    async fn touch(&self, id: &str) -> RepositoryResult<()>;
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::domain::models::email_verification::{
+    CreateEmailVerificationToken, EmailVerificationToken,
+};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait EmailVerificationRepository: Send + Sync {
+    async fn create(
+        &self,
+        new_token: CreateEmailVerificationToken,
+    ) -> RepositoryResult<EmailVerificationToken>;
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<EmailVerificationToken>>;
+    async fn consume(&self, id: &str) -> RepositoryResult<bool>;
+    async fn consume_account(&self, account: &str) -> RepositoryResult<()>;
+}
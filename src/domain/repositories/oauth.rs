@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::domain::models::oauth::{
+    AuthorizationCode, CreateAuthorizationCode, CreateOAuthClient, CreateOAuthRefreshToken,
+    OAuthClient, StoredOAuthRefreshToken,
+};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait OAuthRepository: Send + Sync {
+    async fn create_client(&self, client: CreateOAuthClient) -> RepositoryResult<OAuthClient>;
+    async fn find_client(&self, id: &str) -> RepositoryResult<Option<OAuthClient>>;
+    async fn find_clients_by_owner(&self, owner: &str) -> RepositoryResult<Vec<OAuthClient>>;
+    async fn delete_client(&self, id: &str, owner: &str) -> RepositoryResult<bool>;
+    async fn create_code(&self, code: CreateAuthorizationCode) -> RepositoryResult<()>;
+    async fn consume_code(&self, code_hash: &str) -> RepositoryResult<Option<AuthorizationCode>>;
+    async fn create_refresh_token(
+        &self,
+        token: CreateOAuthRefreshToken,
+    ) -> RepositoryResult<StoredOAuthRefreshToken>;
+    async fn find_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<StoredOAuthRefreshToken>>;
+    async fn rotate_refresh_token(&self, id: &str) -> RepositoryResult<bool>;
+    async fn revoke_refresh_family(&self, family: &str) -> RepositoryResult<()>;
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<()>;
+    async fn prune(&self) -> RepositoryResult<()>;
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::domain::models::oidc::{
+    AccountIdentity, CreateAccountIdentity, CreateOidcState, OidcState,
+};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait OidcRepository: Send + Sync {
+    async fn create_state(&self, state: CreateOidcState) -> RepositoryResult<()>;
+    async fn consume_state(
+        &self,
+        state_hash: &str,
+        provider: &str,
+    ) -> RepositoryResult<Option<OidcState>>;
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> RepositoryResult<Option<AccountIdentity>>;
+    async fn create_identity(&self, identity: CreateAccountIdentity) -> RepositoryResult<()>;
+    async fn prune(&self) -> RepositoryResult<()>;
+}
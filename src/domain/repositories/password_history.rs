@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait PasswordHistoryRepository: Send + Sync {
+    async fn find_recent(&self, account: &str, limit: usize) -> RepositoryResult<Vec<String>>;
+    async fn add(&self, account: &str, password_hash: String, keep: usize) -> RepositoryResult<()>;
+}
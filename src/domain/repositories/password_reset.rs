@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::models::password_reset::{CreatePasswordResetToken, PasswordResetToken};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait PasswordResetRepository: Send + Sync {
+    async fn create(
+        &self,
+        new_token: CreatePasswordResetToken,
+    ) -> RepositoryResult<PasswordResetToken>;
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<PasswordResetToken>>;
+    async fn consume(&self, id: &str) -> RepositoryResult<bool>;
+    async fn consume_account(&self, account: &str) -> RepositoryResult<()>;
+}
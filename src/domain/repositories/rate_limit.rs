@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::models::rate_limit::RateLimitBucket;
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait RateLimitRepository: Send + Sync {
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        refill_rate: f64,
+    ) -> RepositoryResult<RateLimitBucket>;
+    async fn prune(&self, idle: i64) -> RepositoryResult<()>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::models::refresh_token::{CreateRefreshToken, StoredRefreshToken};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create(&self, new_token: CreateRefreshToken) -> RepositoryResult<StoredRefreshToken>;
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<StoredRefreshToken>>;
+    async fn rotate(&self, id: &str) -> RepositoryResult<bool>;
+    async fn find_live_families(&self, account: &str) -> RepositoryResult<Vec<String>>;
+    async fn revoke_family(&self, family: &str) -> RepositoryResult<()>;
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<()>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::models::revoked_token::RevokedToken;
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait RevokedTokenRepository: Send + Sync {
+    async fn revoke(&self, token: RevokedToken) -> RepositoryResult<()>;
+    async fn find_active(&self) -> RepositoryResult<Vec<RevokedToken>>;
+    async fn prune(&self) -> RepositoryResult<()>;
+}
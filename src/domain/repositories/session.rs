@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::models::session::{CreateSession, Session};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, session: CreateSession) -> RepositoryResult<Session>;
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<Session>>;
+    async fn touch(&self, id: &str) -> RepositoryResult<()>;
+    async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool>;
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<Vec<String>>;
+    async fn find_revoked_since(&self, since: i64) -> RepositoryResult<Vec<String>>;
+}
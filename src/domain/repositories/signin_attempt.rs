@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::models::signin_attempt::SigninAttempt;
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait SigninAttemptRepository: Send + Sync {
+    async fn find(&self, key: &str) -> RepositoryResult<Option<SigninAttempt>>;
+    async fn record_failure(&self, key: &str, window_start: i64)
+    -> RepositoryResult<SigninAttempt>;
+    async fn lock(&self, key: &str, until: i64) -> RepositoryResult<()>;
+    async fn reset(&self, key: &str) -> RepositoryResult<()>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::models::totp::{CreateTotpCredential, TotpCredential};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait TotpRepository: Send + Sync {
+    async fn save(&self, credential: CreateTotpCredential) -> RepositoryResult<TotpCredential>;
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Option<TotpCredential>>;
+    async fn confirm(&self, account: &str) -> RepositoryResult<bool>;
+    async fn accept_step(&self, account: &str, step: u64) -> RepositoryResult<bool>;
+    async fn consume_recovery_code(&self, account: &str, code_hash: &str)
+    -> RepositoryResult<bool>;
+}
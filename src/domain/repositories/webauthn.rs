@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::domain::models::webauthn::{
+    Ceremony, CreateWebAuthnChallenge, CreateWebAuthnCredential, WebAuthnChallenge,
+    WebAuthnCredential,
+};
+
+use super::repository::RepositoryResult;
+
+#[async_trait]
+pub trait WebAuthnRepository: Send + Sync {
+    async fn create_challenge(&self, challenge: CreateWebAuthnChallenge) -> RepositoryResult<()>;
+    async fn consume_challenge(
+        &self,
+        challenge_hash: &str,
+        ceremony: Ceremony,
+    ) -> RepositoryResult<Option<WebAuthnChallenge>>;
+    async fn create_credential(
+        &self,
+        credential: CreateWebAuthnCredential,
+    ) -> RepositoryResult<WebAuthnCredential>;
+    async fn find_credential(
+        &self,
+        credential_id: &str,
+    ) -> RepositoryResult<Option<WebAuthnCredential>>;
+    async fn find_credentials_by_account(
+        &self,
+        account: &str,
+    ) -> RepositoryResult<Vec<WebAuthnCredential>>;
+    async fn update_sign_count(&self, id: &str, sign_count: u32) -> RepositoryResult<bool>;
+    async fn prune(&self) -> RepositoryResult<()>;
+}
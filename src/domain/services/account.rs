@@ -1,10 +1,25 @@
 use async_trait::async_trait;
 
 use crate::domain::error::AppResult;
-use crate::domain::models::account::{Account, CreateAccount, Credentials};
+use crate::domain::models::account::{
+    Account, AccountFilter, AccountSortField, ChangePassword, CreateAccount, Credentials,
+    UpdateAccount,
+};
+use crate::domain::models::pagination::{Page, PageRequest};
 
 #[async_trait]
 pub trait AccountService: 'static + Sync + Send {
-    async fn signin(&self, credentials: Credentials) -> AppResult<Account>;
+    async fn signin(&self, credentials: Credentials, client_ip: Option<&str>)
+    -> AppResult<Account>;
     async fn signup(&self, mut new_account: CreateAccount) -> AppResult<Account>;
+    async fn find_by_id(&self, id: &str) -> AppResult<Account>;
+    async fn find(&self, id: &str) -> AppResult<Account>;
+    async fn list(
+        &self,
+        filter: AccountFilter,
+        page: PageRequest<AccountSortField>,
+    ) -> AppResult<Page<Account>>;
+    async fn update(&self, id: &str, account: UpdateAccount) -> AppResult<Account>;
+    async fn delete(&self, id: &str) -> AppResult<()>;
+    async fn change_password(&self, id: &str, passwords: ChangePassword) -> AppResult<Account>;
 }
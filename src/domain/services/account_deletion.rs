@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+
+#[async_trait]
+pub trait AccountDeletionService: 'static + Sync + Send {
+    async fn restore(&self, id: &str) -> AppResult<Account>;
+    async fn purge(&self) -> AppResult<usize>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+
+#[async_trait]
+pub trait AccountStatusService: 'static + Sync + Send {
+    async fn suspend(&self, id: &str, actor: &str, reason: String) -> AppResult<Account>;
+    async fn reactivate(&self, id: &str, actor: &str) -> AppResult<Account>;
+    async fn deactivate(&self, id: &str) -> AppResult<Account>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::api_key::{ApiKey, IssuedApiKey, NewApiKey};
+use crate::domain::models::jsonwebtoken::Claims;
+
+#[async_trait]
+pub trait ApiKeyService: 'static + Sync + Send {
+    async fn create(&self, account: &Account, api_key: NewApiKey) -> AppResult<IssuedApiKey>;
+    async fn list(&self, account: &str) -> AppResult<Vec<ApiKey>>;
+    async fn revoke(&self, account: &str, id: &str) -> AppResult<()>;
+    async fn authenticate(&self, key: &str) -> AppResult<Claims>;
+}
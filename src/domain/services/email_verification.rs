@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+
+#[async_trait]
+pub trait EmailVerificationService: 'static + Sync + Send {
+    async fn send(&self, account: &Account) -> AppResult<()>;
+    async fn resend(&self, email: &str) -> AppResult<()>;
+    async fn verify(&self, token: &str) -> AppResult<Account>;
+}
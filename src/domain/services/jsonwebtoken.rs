@@ -1,9 +1,30 @@
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+
 use crate::domain::{
     error::AppResult,
+    models::account::{Account, Permission},
     models::jsonwebtoken::{AccessToken, Claims},
 };
 
+#[async_trait]
 pub trait JsonWebTokenService: 'static + Sync + Send {
-    fn generate_token(&self, id: String) -> AppResult<AccessToken>;
-    fn validate_token(&self, token: &str) -> AppResult<Claims>;
+    fn generate_token(&self, account: &Account, session: &str) -> AppResult<AccessToken>;
+    async fn validate_token(&self, token: &str) -> AppResult<Claims>;
+    fn generate_mfa_token(&self, account: &Account) -> AppResult<AccessToken>;
+    async fn validate_mfa_token(&self, token: &str) -> AppResult<Claims>;
+    fn generate_client_token(
+        &self,
+        sub: &str,
+        client_id: &str,
+        owner: Option<&str>,
+        permissions: Vec<Permission>,
+    ) -> AppResult<AccessToken>;
+    fn jwks(&self) -> JwkSet;
+    async fn revoke_token(&self, claims: &Claims) -> AppResult<()>;
+    fn revoke_issued_before(&self, sub: &str, issued_before: i64);
+    fn revoke_session(&self, session: &str);
+    fn suspend(&self, sub: &str);
+    fn reactivate(&self, sub: &str);
+    async fn reload(&self) -> AppResult<()>;
 }
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::mail::Mail;
+
+#[async_trait]
+pub trait MailerService: 'static + Sync + Send {
+    async fn send(&self, mail: Mail) -> AppResult<()>;
+}
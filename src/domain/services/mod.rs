@@ -1,2 +1,15 @@
 pub mod account;
+pub mod account_deletion;
+pub mod account_status;
+pub mod api_key;
+pub mod email_verification;
 pub mod jsonwebtoken;
+pub mod mailer;
+pub mod oauth;
+pub mod oidc;
+pub mod password_reset;
+pub mod rate_limit;
+pub mod refresh_token;
+pub mod session;
+pub mod totp;
+pub mod webauthn;
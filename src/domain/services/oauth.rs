@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::oauth::{
+    AuthorizationRequest, ClientCredentials, IssuedOAuthClient, NewOAuthClient, OAuthClient,
+    OAuthToken, TokenGrant, TokenIntrospection,
+};
+
+#[async_trait]
+pub trait OAuthService: 'static + Sync + Send {
+    async fn register_client(
+        &self,
+        owner: &Account,
+        client: NewOAuthClient,
+    ) -> AppResult<IssuedOAuthClient>;
+    async fn list_clients(&self, owner: &str) -> AppResult<Vec<OAuthClient>>;
+    async fn delete_client(&self, owner: &str, id: &str) -> AppResult<()>;
+    async fn validate_authorization(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> AppResult<OAuthClient>;
+    async fn authorize(
+        &self,
+        account: &str,
+        request: AuthorizationRequest,
+        approved: bool,
+    ) -> AppResult<String>;
+    async fn token(
+        &self,
+        credentials: ClientCredentials,
+        grant: TokenGrant,
+    ) -> AppResult<OAuthToken>;
+    async fn introspect(
+        &self,
+        credentials: ClientCredentials,
+        token: &str,
+    ) -> AppResult<Option<TokenIntrospection>>;
+    async fn revoke(&self, credentials: ClientCredentials, token: &str) -> AppResult<()>;
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::oidc::OidcAuthorization;
+
+#[async_trait]
+pub trait OidcService: 'static + Sync + Send {
+    // Binding the request to an account links the returned identity to it instead of signing in
+    async fn authorize(
+        &self,
+        provider: &str,
+        account: Option<&str>,
+    ) -> AppResult<OidcAuthorization>;
+    async fn callback(&self, provider: &str, code: &str, state: &str) -> AppResult<Account>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::password_reset::ResetPassword;
+
+#[async_trait]
+pub trait PasswordResetService: 'static + Sync + Send {
+    async fn forgot(&self, email: &str) -> AppResult<()>;
+    async fn reset(&self, reset: ResetPassword) -> AppResult<Account>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::rate_limit::{RateLimit, RateLimitRequest};
+
+#[async_trait]
+pub trait RateLimitService: 'static + Sync + Send {
+    fn keys_by_subject(&self, path: &str) -> bool;
+    async fn acquire(&self, request: &RateLimitRequest) -> AppResult<Option<RateLimit>>;
+    async fn prune(&self) -> AppResult<()>;
+}
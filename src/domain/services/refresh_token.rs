@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::refresh_token::RefreshToken;
+
+#[async_trait]
+pub trait RefreshTokenService: 'static + Sync + Send {
+    async fn issue(&self, account: String, session: String) -> AppResult<RefreshToken>;
+    async fn rotate(&self, token: &str) -> AppResult<RefreshToken>;
+    async fn revoke_account(&self, account: &str) -> AppResult<()>;
+}
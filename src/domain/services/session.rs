@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::session::{CreateSession, Session};
+
+#[async_trait]
+pub trait SessionService: 'static + Sync + Send {
+    async fn create(&self, session: CreateSession) -> AppResult<Session>;
+    async fn list(&self, account: &str) -> AppResult<Vec<Session>>;
+    async fn revoke(&self, account: &str, id: &str) -> AppResult<()>;
+    async fn revoke_account(&self, account: &str) -> AppResult<()>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::totp::TotpEnrollment;
+
+#[async_trait]
+pub trait TotpService: 'static + Sync + Send {
+    async fn enroll(&self, account: &Account) -> AppResult<TotpEnrollment>;
+    async fn confirm(&self, account: &str, code: &str) -> AppResult<()>;
+    async fn is_enabled(&self, account: &str) -> AppResult<bool>;
+    async fn verify(&self, account: &str, code: &str, client_ip: Option<&str>) -> AppResult<()>;
+}
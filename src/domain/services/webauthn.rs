@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::models::webauthn::{
+    AssertCredential, AuthenticationOptions, RegisterCredential, RegistrationOptions,
+};
+
+#[async_trait]
+pub trait WebAuthnService: 'static + Sync + Send {
+    async fn start_registration(&self, account: &Account) -> AppResult<RegistrationOptions>;
+    async fn finish_registration(
+        &self,
+        account: &str,
+        credential: RegisterCredential,
+    ) -> AppResult<()>;
+    async fn start_authentication(&self, email: Option<&str>) -> AppResult<AuthenticationOptions>;
+    async fn finish_authentication(&self, credential: AssertCredential) -> AppResult<Account>;
+}
@@ -0,0 +1,219 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::pagination::SortOrder;
+
+// Field names are fixed by the caller, every value is bound as a parameter so nothing supplied by
+// a client ever ends up in the query text
+pub struct SelectQuery {
+    table: &'static str,
+    conditions: Vec<String>,
+    bindings: Vec<(String, Value)>,
+    sort: Option<(&'static str, SortOrder)>,
+    after: Option<String>,
+    limit: Option<usize>,
+}
+
+impl SelectQuery {
+    pub fn from(table: &'static str) -> Self {
+        Self {
+            table,
+            conditions: vec![],
+            bindings: vec![],
+            sort: None,
+            after: None,
+            limit: None,
+        }
+    }
+
+    fn bind(&mut self, value: impl Serialize) -> String {
+        let name = format!("p{}", self.bindings.len());
+
+        self.bindings
+            .push((name.clone(), serde_json::to_value(value).unwrap()));
+
+        format!("${name}")
+    }
+
+    pub fn missing(mut self, field: &'static str) -> Self {
+        self.conditions.push(format!("{field} IS NONE"));
+        self
+    }
+
+    // Case-insensitive substring match
+    pub fn contains(mut self, field: &'static str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            let param = self.bind(value.to_lowercase());
+
+            self.conditions.push(format!(
+                "string::contains(string::lowercase({field}), {param})"
+            ));
+        }
+
+        self
+    }
+
+    pub fn since(mut self, field: &'static str, timestamp: Option<i64>) -> Self {
+        if let Some(timestamp) = timestamp {
+            let param = self.bind(timestamp);
+
+            self.conditions
+                .push(format!("{field} >= time::from::unix({param})"));
+        }
+
+        self
+    }
+
+    pub fn until(mut self, field: &'static str, timestamp: Option<i64>) -> Self {
+        if let Some(timestamp) = timestamp {
+            let param = self.bind(timestamp);
+
+            self.conditions
+                .push(format!("{field} < time::from::unix({param})"));
+        }
+
+        self
+    }
+
+    // Ties are broken by id, so every row has a stable position to resume from
+    pub fn order_by(mut self, field: &'static str, order: SortOrder) -> Self {
+        self.sort = Some((field, order));
+        self
+    }
+
+    // Resumes right after the row with this id, in the current sort order
+    pub fn after(mut self, id: Option<String>) -> Self {
+        self.after = id;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn build(&self) -> String {
+        let (field, order) = self.sort.unwrap_or(("id", SortOrder::Asc));
+
+        let (direction, comparison) = match order {
+            SortOrder::Asc => ("ASC", ">"),
+            SortOrder::Desc => ("DESC", "<"),
+        };
+
+        let mut conditions = self.conditions.clone();
+        let mut query = String::new();
+
+        if self.after.is_some() {
+            query.push_str("LET $anchor = (SELECT * FROM type::thing($table, $after))[0];\n");
+
+            conditions.push(if field == "id" {
+                format!("id {comparison} $anchor.id")
+            } else {
+                format!(
+                    "({field} {comparison} $anchor.{field} OR ({field} = $anchor.{field} AND id {comparison} $anchor.id))"
+                )
+            });
+        }
+
+        query.push_str("SELECT * FROM type::table($table)");
+
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&conditions.join(" AND "));
+        }
+
+        if field == "id" {
+            query.push_str(&format!(" ORDER BY id {direction}"));
+        } else {
+            query.push_str(&format!(" ORDER BY {field} {direction}, id {direction}"));
+        }
+
+        if self.limit.is_some() {
+            query.push_str(" LIMIT $limit");
+        }
+
+        query
+    }
+
+    pub async fn fetch<T: DeserializeOwned>(
+        self,
+        db: &Surreal<Client>,
+    ) -> surrealdb::Result<Vec<T>> {
+        let mut query = db.query(self.build()).bind(("table", self.table));
+
+        for binding in self.bindings {
+            query = query.bind(binding);
+        }
+
+        if let Some(limit) = self.limit {
+            query = query.bind(("limit", limit));
+        }
+
+        // The anchor is looked up by a statement of its own, ahead of the select
+        let index = match self.after {
+            Some(after) => {
+                query = query.bind(("after", after));
+                1
+            }
+            None => 0,
+        };
+
+        query.await?.take(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_all() {
+        assert_eq!(
+            SelectQuery::from("account").build(),
+            "SELECT * FROM type::table($table) ORDER BY id ASC"
+        );
+    }
+
+    #[test]
+    fn test_filters_are_bound() {
+        let query = SelectQuery::from("account")
+            .missing("deleted_at")
+            .contains("email", Some("Robert'); DELETE account; --"))
+            .contains("name", None)
+            .since("created_at", Some(1700000000))
+            .until("created_at", None)
+            .limit(21);
+
+        assert_eq!(
+            query.build(),
+            "SELECT * FROM type::table($table) WHERE deleted_at IS NONE AND string::contains(string::lowercase(email), $p0) AND created_at >= time::from::unix($p1) ORDER BY id ASC LIMIT $limit"
+        );
+        assert_eq!(
+            query.bindings,
+            vec![
+                (
+                    "p0".to_string(),
+                    Value::from("robert'); delete account; --")
+                ),
+                ("p1".to_string(), Value::from(1700000000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keyset() {
+        let query = SelectQuery::from("account")
+            .order_by("email", SortOrder::Desc)
+            .after(Some("last".to_string()))
+            .limit(21);
+
+        assert_eq!(
+            query.build(),
+            "LET $anchor = (SELECT * FROM type::thing($table, $after))[0];\n\
+             SELECT * FROM type::table($table) WHERE (email < $anchor.email OR (email = $anchor.email AND id < $anchor.id)) ORDER BY email DESC, id DESC LIMIT $limit"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::Thing;
+use surrealdb::sql::{Datetime, Thing};
 
-use crate::domain::models::account::{Account, CreateAccount};
+use crate::domain::models::account::{
+    Account, AccountStatus, CreateAccount, Permission, Role, UpdateAccount,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct SurrealAccount {
@@ -9,6 +11,16 @@ pub struct SurrealAccount {
     name: String,
     email: String,
     password: String,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    permissions: Vec<Permission>,
+    password_changed_at: Option<Datetime>,
+    email_verified_at: Option<Datetime>,
+    #[serde(default)]
+    status: AccountStatus,
+    deleted_at: Option<Datetime>,
+    created_at: Datetime,
 }
 
 #[derive(Serialize)]
@@ -18,6 +30,23 @@ pub struct SurrealAccountCreate {
     password: String,
 }
 
+#[derive(Serialize)]
+pub struct SurrealAccountUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+impl From<UpdateAccount> for SurrealAccountUpdate {
+    fn from(acc: UpdateAccount) -> Self {
+        SurrealAccountUpdate {
+            name: acc.name,
+            email: acc.email,
+        }
+    }
+}
+
 impl From<CreateAccount> for SurrealAccountCreate {
     fn from(acc: CreateAccount) -> Self {
         SurrealAccountCreate {
@@ -35,6 +64,13 @@ impl From<SurrealAccount> for Account {
             name: acc.name,
             email: acc.email,
             password: acc.password,
+            roles: acc.roles,
+            permissions: acc.permissions,
+            password_changed_at: acc.password_changed_at.map(|at| at.timestamp()),
+            email_verified_at: acc.email_verified_at.map(|at| at.timestamp()),
+            status: acc.status,
+            deleted_at: acc.deleted_at.map(|at| at.timestamp()),
+            created_at: acc.created_at.timestamp(),
         }
     }
 }
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::account::Permission;
+use crate::domain::models::api_key::ApiKey;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealApiKey {
+    id: Thing,
+    account: Thing,
+    name: String,
+    prefix: String,
+    scopes: Option<Vec<Permission>>,
+    expires_at: Option<Datetime>,
+    last_used_at: Option<Datetime>,
+    created_at: Datetime,
+}
+
+impl From<SurrealApiKey> for ApiKey {
+    fn from(api_key: SurrealApiKey) -> Self {
+        ApiKey {
+            id: api_key.id.id.to_string(),
+            account: api_key.account.id.to_string(),
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes,
+            expiration: api_key.expires_at.map(|at| at.timestamp()),
+            last_used_at: api_key.last_used_at.map(|at| at.timestamp()),
+            created_at: api_key.created_at.timestamp(),
+        }
+    }
+}
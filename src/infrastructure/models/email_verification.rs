@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::email_verification::EmailVerificationToken;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealEmailVerificationToken {
+    id: Thing,
+    account: Thing,
+    expires_at: Datetime,
+    used_at: Option<Datetime>,
+}
+
+impl From<SurrealEmailVerificationToken> for EmailVerificationToken {
+    fn from(token: SurrealEmailVerificationToken) -> Self {
+        EmailVerificationToken {
+            id: token.id.id.to_string(),
+            account: token.account.id.to_string(),
+            expiration: token.expires_at.timestamp(),
+            used: token.used_at.is_some(),
+        }
+    }
+}
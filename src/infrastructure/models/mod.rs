@@ -1 +1,13 @@
 pub mod account;
+pub mod api_key;
+pub mod email_verification;
+pub mod oauth;
+pub mod oidc;
+pub mod password_reset;
+pub mod rate_limit;
+pub mod refresh_token;
+pub mod revoked_token;
+pub mod session;
+pub mod signin_attempt;
+pub mod totp;
+pub mod webauthn;
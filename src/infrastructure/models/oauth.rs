@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::account::Permission;
+use crate::domain::models::oauth::{
+    AuthorizationCode, GrantType, OAuthClient, StoredOAuthRefreshToken,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealOAuthClient {
+    id: Thing,
+    owner: Thing,
+    name: String,
+    secret_hash: Option<String>,
+    redirect_uris: Vec<String>,
+    grant_types: Vec<GrantType>,
+    scopes: Vec<Permission>,
+    created_at: Datetime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealAuthorizationCode {
+    client: Thing,
+    account: Thing,
+    redirect_uri: String,
+    scopes: Vec<Permission>,
+    code_challenge: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealOAuthRefreshToken {
+    id: Thing,
+    client: Thing,
+    account: Thing,
+    family: String,
+    scopes: Vec<Permission>,
+    expires_at: Datetime,
+    rotated_at: Option<Datetime>,
+    revoked_at: Option<Datetime>,
+}
+
+impl From<SurrealOAuthClient> for OAuthClient {
+    fn from(client: SurrealOAuthClient) -> Self {
+        OAuthClient {
+            id: client.id.id.to_string(),
+            owner: client.owner.id.to_string(),
+            name: client.name,
+            secret_hash: client.secret_hash,
+            redirect_uris: client.redirect_uris,
+            grant_types: client.grant_types,
+            scopes: client.scopes,
+            created_at: client.created_at.timestamp(),
+        }
+    }
+}
+
+impl From<SurrealAuthorizationCode> for AuthorizationCode {
+    fn from(code: SurrealAuthorizationCode) -> Self {
+        AuthorizationCode {
+            client: code.client.id.to_string(),
+            account: code.account.id.to_string(),
+            redirect_uri: code.redirect_uri,
+            scopes: code.scopes,
+            code_challenge: code.code_challenge,
+        }
+    }
+}
+
+impl From<SurrealOAuthRefreshToken> for StoredOAuthRefreshToken {
+    fn from(token: SurrealOAuthRefreshToken) -> Self {
+        StoredOAuthRefreshToken {
+            id: token.id.id.to_string(),
+            client: token.client.id.to_string(),
+            account: token.account.id.to_string(),
+            family: token.family,
+            scopes: token.scopes,
+            expiration: token.expires_at.timestamp(),
+            rotated: token.rotated_at.is_some(),
+            revoked: token.revoked_at.is_some(),
+        }
+    }
+}
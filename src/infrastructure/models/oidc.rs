@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use surrealdb::sql::Thing;
+
+use crate::domain::models::oidc::{AccountIdentity, OidcState};
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealOidcState {
+    account: Option<Thing>,
+    nonce: String,
+    code_verifier: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealAccountIdentity {
+    account: Thing,
+}
+
+impl From<SurrealOidcState> for OidcState {
+    fn from(state: SurrealOidcState) -> Self {
+        OidcState {
+            account: state.account.map(|account| account.id.to_string()),
+            nonce: state.nonce,
+            code_verifier: state.code_verifier,
+        }
+    }
+}
+
+impl From<SurrealAccountIdentity> for AccountIdentity {
+    fn from(identity: SurrealAccountIdentity) -> Self {
+        AccountIdentity {
+            account: identity.account.id.to_string(),
+        }
+    }
+}
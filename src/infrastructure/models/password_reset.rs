@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::password_reset::PasswordResetToken;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealPasswordResetToken {
+    id: Thing,
+    account: Thing,
+    expires_at: Datetime,
+    used_at: Option<Datetime>,
+}
+
+impl From<SurrealPasswordResetToken> for PasswordResetToken {
+    fn from(token: SurrealPasswordResetToken) -> Self {
+        PasswordResetToken {
+            id: token.id.id.to_string(),
+            account: token.account.id.to_string(),
+            expiration: token.expires_at.timestamp(),
+            used: token.used_at.is_some(),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+use crate::domain::models::rate_limit::RateLimitBucket;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealRateLimitBucket {
+    allowed: bool,
+    tokens: f64,
+}
+
+impl From<SurrealRateLimitBucket> for RateLimitBucket {
+    fn from(bucket: SurrealRateLimitBucket) -> Self {
+        RateLimitBucket {
+            allowed: bucket.allowed,
+            tokens: bucket.tokens,
+        }
+    }
+}
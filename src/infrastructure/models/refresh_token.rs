@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::refresh_token::StoredRefreshToken;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealRefreshToken {
+    id: Thing,
+    account: Thing,
+    family: String,
+    expires_at: Datetime,
+    rotated_at: Option<Datetime>,
+    revoked_at: Option<Datetime>,
+}
+
+impl From<SurrealRefreshToken> for StoredRefreshToken {
+    fn from(token: SurrealRefreshToken) -> Self {
+        StoredRefreshToken {
+            id: token.id.id.to_string(),
+            account: token.account.id.to_string(),
+            family: token.family,
+            expiration: token.expires_at.timestamp(),
+            rotated: token.rotated_at.is_some(),
+            revoked: token.revoked_at.is_some(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use surrealdb::sql::Datetime;
+
+use crate::domain::models::revoked_token::RevokedToken;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealRevokedToken {
+    jti: String,
+    expires_at: Datetime,
+}
+
+impl From<SurrealRevokedToken> for RevokedToken {
+    fn from(token: SurrealRevokedToken) -> Self {
+        RevokedToken {
+            jti: token.jti,
+            expiration: token.expires_at.timestamp(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::session::Session;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealSession {
+    id: Thing,
+    account: Thing,
+    user_agent: Option<String>,
+    ip: Option<String>,
+    last_seen_at: Datetime,
+    created_at: Datetime,
+}
+
+impl From<SurrealSession> for Session {
+    fn from(session: SurrealSession) -> Self {
+        Session {
+            id: session.id.id.to_string(),
+            account: session.account.id.to_string(),
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at.timestamp(),
+            last_seen_at: session.last_seen_at.timestamp(),
+        }
+    }
+}
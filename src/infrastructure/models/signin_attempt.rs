@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use surrealdb::sql::Datetime;
+
+use crate::domain::models::signin_attempt::SigninAttempt;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealSigninAttempt {
+    failures: u32,
+    locked_until: Option<Datetime>,
+}
+
+impl From<SurrealSigninAttempt> for SigninAttempt {
+    fn from(attempt: SurrealSigninAttempt) -> Self {
+        SigninAttempt {
+            failures: attempt.failures,
+            locked_until: attempt.locked_until.map(|until| until.timestamp()),
+        }
+    }
+}
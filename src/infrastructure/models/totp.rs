@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use surrealdb::sql::{Datetime, Thing};
+
+use crate::domain::models::totp::TotpCredential;
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealTotpCredential {
+    account: Thing,
+    secret: String,
+    confirmed_at: Option<Datetime>,
+    last_step: Option<u64>,
+}
+
+impl From<SurrealTotpCredential> for TotpCredential {
+    fn from(credential: SurrealTotpCredential) -> Self {
+        TotpCredential {
+            account: credential.account.id.to_string(),
+            secret: credential.secret,
+            confirmed: credential.confirmed_at.is_some(),
+            last_step: credential.last_step,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use surrealdb::sql::Thing;
+
+use crate::domain::models::webauthn::{WebAuthnChallenge, WebAuthnCredential};
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealWebAuthnCredential {
+    id: Thing,
+    account: Thing,
+    credential_id: String,
+    public_key: String,
+    sign_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SurrealWebAuthnChallenge {
+    account: Option<Thing>,
+}
+
+impl From<SurrealWebAuthnCredential> for WebAuthnCredential {
+    fn from(credential: SurrealWebAuthnCredential) -> Self {
+        WebAuthnCredential {
+            id: credential.id.id.to_string(),
+            account: credential.account.id.to_string(),
+            credential_id: credential.credential_id,
+            public_key: credential.public_key,
+            sign_count: credential.sign_count,
+        }
+    }
+}
+
+impl From<SurrealWebAuthnChallenge> for WebAuthnChallenge {
+    fn from(challenge: SurrealWebAuthnChallenge) -> Self {
+        WebAuthnChallenge {
+            account: challenge.account.map(|account| account.id.to_string()),
+        }
+    }
+}
@@ -4,10 +4,16 @@ use async_trait::async_trait;
 use surrealdb::Surreal;
 use surrealdb::engine::remote::ws::Client;
 
-use crate::domain::models::account::{Account, CreateAccount};
+use crate::domain::models::account::{
+    Account, AccountFilter, AccountSortField, AccountStatus, CreateAccount, UpdateAccount,
+};
+use crate::domain::models::pagination::{Page, PageRequest};
 use crate::domain::repositories::account::{AccountRepository, FindByCol};
 use crate::domain::repositories::repository::RepositoryResult;
-use crate::infrastructure::models::account::{SurrealAccount, SurrealAccountCreate, SurrealCount};
+use crate::infrastructure::databases::query::SelectQuery;
+use crate::infrastructure::models::account::{
+    SurrealAccount, SurrealAccountCreate, SurrealAccountUpdate, SurrealCount,
+};
 
 pub struct AccountRepositoryImpl {
     db: Arc<Surreal<Client>>,
@@ -34,6 +40,8 @@ impl AccountRepository for AccountRepositoryImpl {
         Ok(account.into())
     }
 
+    // Deleted accounts still hold their email until purged, so a restore never collides with a
+    // new signup
     async fn is_account(&self, email: &str) -> RepositoryResult<bool> {
         let mut res = self
             .db
@@ -51,7 +59,7 @@ impl AccountRepository for AccountRepositoryImpl {
         let account: Option<SurrealAccount> = self
             .db
             .query(format!(
-                "SELECT * FROM type::table($table) WHERE {column} = type::string($value)"
+                "SELECT * FROM type::table($table) WHERE {column} = type::string($value) AND deleted_at IS NONE"
             ))
             .bind(("table", ACCOUNT))
             .bind(("value", column.value()))
@@ -60,13 +68,253 @@ impl AccountRepository for AccountRepositoryImpl {
 
         Ok(account.map(Into::into))
     }
+
+    async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("SELECT * FROM type::thing($table, $id) WHERE deleted_at IS NONE")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    // Deleted accounts included, a page may resume from one listed with `include_deleted`
+    async fn exists(&self, id: &str) -> RepositoryResult<bool> {
+        let mut res = self
+            .db
+            .query("(SELECT count() FROM type::thing($table, $id))[0] or { count: 0 }")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .await?;
+
+        let counter = res.take::<Option<SurrealCount>>(0)?.unwrap();
+
+        Ok(counter.count > 0)
+    }
+
+    async fn find_page(
+        &self,
+        filter: AccountFilter,
+        page: PageRequest<AccountSortField>,
+    ) -> RepositoryResult<Page<Account>> {
+        let query = match filter.include_deleted {
+            true => SelectQuery::from(ACCOUNT),
+            false => SelectQuery::from(ACCOUNT).missing("deleted_at"),
+        };
+
+        let accounts: Vec<SurrealAccount> = query
+            .contains("email", filter.email.as_deref())
+            .contains("name", filter.name.as_deref())
+            .since("created_at", filter.created_after)
+            .until("created_at", filter.created_before)
+            .order_by(page.sort.field.as_str(), page.sort.order)
+            .after(page.cursor)
+            .limit(page.limit + 1)
+            .fetch(&self.db)
+            .await?;
+
+        Ok(Page::from_overfetched(
+            accounts.into_iter().map(Into::into).collect(),
+            page.limit,
+            |account: &Account| account.id.clone(),
+        ))
+    }
+
+    // A new email is unverified until its owner follows the link sent to it
+    async fn update(&self, id: &str, account: UpdateAccount) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $id) SET
+                    email_verified_at = IF ($account.email ?? email) != email THEN NONE ELSE email_verified_at END,
+                    name = $account.name ?? name,
+                    email = $account.email ?? email
+                WHERE deleted_at IS NONE RETURN AFTER",
+            )
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .bind(("account", SurrealAccountUpdate::from(account)))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET deleted_at = time::now() WHERE deleted_at IS NONE RETURN BEFORE")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(account.is_some())
+    }
+
+    async fn update_password(
+        &self,
+        id: &str,
+        password: String,
+    ) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET password = $password, password_changed_at = time::now() WHERE deleted_at IS NONE RETURN AFTER")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .bind(("password", password))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    // Same password under new parameters, so sessions survive and a concurrent change wins
+    async fn rehash_password(
+        &self,
+        id: &str,
+        current: &str,
+        password: String,
+    ) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::thing($table, $id) SET password = $password WHERE password = $current AND deleted_at IS NONE")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .bind(("current", current.to_owned()))
+            .bind(("password", password))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn find_password_changed_since(&self, since: i64) -> RepositoryResult<Vec<Account>> {
+        let accounts: Vec<SurrealAccount> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE password_changed_at > time::from::unix($since) AND deleted_at IS NONE")
+            .bind(("table", ACCOUNT))
+            .bind(("since", since))
+            .await?
+            .take(0)?;
+
+        Ok(accounts.into_iter().map(Into::into).collect())
+    }
+
+    async fn verify_email(&self, id: &str) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET email_verified_at = time::now() WHERE deleted_at IS NONE RETURN AFTER")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    async fn update_status(
+        &self,
+        id: &str,
+        status: AccountStatus,
+    ) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET status = $status WHERE deleted_at IS NONE RETURN AFTER")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .bind(("status", status))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    async fn find_suspended(&self) -> RepositoryResult<Vec<String>> {
+        let accounts: Vec<SurrealAccount> = self
+            .db
+            .query(
+                "SELECT * FROM type::table($table) WHERE status = $status AND deleted_at IS NONE",
+            )
+            .bind(("table", ACCOUNT))
+            .bind(("status", AccountStatus::Suspended))
+            .await?
+            .take(0)?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| Account::from(account).id)
+            .collect())
+    }
+
+    async fn restore(&self, id: &str, deleted_since: i64) -> RepositoryResult<Option<Account>> {
+        let account: Option<SurrealAccount> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET deleted_at = NONE WHERE deleted_at >= time::from::unix($since) RETURN AFTER")
+            .bind(("table", ACCOUNT))
+            .bind(("id", id.to_owned()))
+            .bind(("since", deleted_since))
+            .await?
+            .take(0)?;
+
+        Ok(account.map(Into::into))
+    }
+
+    // Everything that points at a purged account goes with it, clients it owns included along
+    // with the grants other accounts made to them, and so do the lockout counters keyed by its
+    // email or id. Audit entries outlive the account, the id they keep no longer resolves to any
+    // personal data once the row is gone. Nothing here can be restored any more, so a run that
+    // fails halfway is simply picked up again by the next one
+    async fn purge(&self, deleted_before: i64) -> RepositoryResult<usize> {
+        let mut res = self
+            .db
+            .query(
+                r#"
+                LET $accounts = (SELECT VALUE id FROM type::table($table) WHERE deleted_at IS NOT NONE AND deleted_at < time::from::unix($before));
+                LET $attempts = array::concat(
+                    (SELECT VALUE type::thing('signin_attempt', string::concat('account:', string::lowercase(email))) FROM $accounts),
+                    (SELECT VALUE type::thing('signin_attempt', string::concat('mfa:', record::id(id))) FROM $accounts)
+                );
+                LET $clients = (SELECT VALUE id FROM oauth_client WHERE owner INSIDE $accounts);
+                DELETE oauth_authorization_code WHERE account INSIDE $accounts OR client INSIDE $clients;
+                DELETE oauth_refresh_token WHERE account INSIDE $accounts OR client INSIDE $clients;
+                DELETE oauth_client WHERE owner INSIDE $accounts;
+                DELETE account_identity WHERE account INSIDE $accounts;
+                DELETE account_session WHERE account INSIDE $accounts;
+                DELETE api_key WHERE account INSIDE $accounts;
+                DELETE email_verification_token WHERE account INSIDE $accounts;
+                DELETE password_history WHERE account INSIDE $accounts;
+                DELETE password_reset_token WHERE account INSIDE $accounts;
+                DELETE refresh_token WHERE account INSIDE $accounts;
+                DELETE totp_credential WHERE account INSIDE $accounts;
+                DELETE webauthn_challenge WHERE account INSIDE $accounts;
+                DELETE webauthn_credential WHERE account INSIDE $accounts;
+                DELETE $attempts;
+                DELETE $accounts RETURN BEFORE;
+                "#,
+            )
+            .bind(("table", ACCOUNT))
+            .bind(("before", deleted_before))
+            .await?;
+
+        let last = res.num_statements() - 1;
+        let purged: Vec<SurrealAccount> = res.take(last)?;
+
+        Ok(purged.len())
+    }
 }
 
 #[cfg(test)]
 pub mod mock {
     use tokio::sync::Mutex;
 
+    use chrono::Utc;
+
     use super::*;
+    use crate::domain::models::account::Role;
+    use crate::domain::models::pagination::SortOrder;
 
     pub struct AccountRepositoryImpl {
         pub accounts: Mutex<Vec<Account>>,
@@ -87,6 +335,13 @@ pub mod mock {
                 name: account.name.to_owned(),
                 email: account.email.to_owned(),
                 password: account.password.to_owned(),
+                roles: vec![Role::User],
+                permissions: vec![],
+                password_changed_at: None,
+                email_verified_at: None,
+                status: AccountStatus::Active,
+                deleted_at: None,
+                created_at: Utc::now().timestamp(),
             };
 
             accounts.push(acc.clone());
@@ -99,10 +354,213 @@ pub mod mock {
 
             match column {
                 FindByCol::Email(email) => {
-                    let account = accounts.iter().find(|a| a.email == email).cloned();
+                    let account = accounts
+                        .iter()
+                        .find(|a| a.email == email && a.deleted_at.is_none())
+                        .cloned();
                     Ok(account)
                 }
             }
         }
+
+        async fn find_by_id(&self, id: &str) -> RepositoryResult<Option<Account>> {
+            let accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .cloned())
+        }
+
+        async fn exists(&self, id: &str) -> RepositoryResult<bool> {
+            let accounts = self.accounts.lock().await;
+
+            Ok(accounts.iter().any(|a| a.id == id))
+        }
+
+        async fn find_page(
+            &self,
+            filter: AccountFilter,
+            page: PageRequest<AccountSortField>,
+        ) -> RepositoryResult<Page<Account>> {
+            let accounts = self.accounts.lock().await;
+
+            let contains = |value: &str, needle: &Option<String>| {
+                needle
+                    .as_ref()
+                    .is_none_or(|needle| value.to_lowercase().contains(&needle.to_lowercase()))
+            };
+
+            let key = |a: &Account| match page.sort.field {
+                AccountSortField::CreatedAt => format!("{:020}", a.created_at),
+                AccountSortField::Email => a.email.clone(),
+                AccountSortField::Name => a.name.clone(),
+            };
+
+            let mut matched: Vec<Account> = accounts
+                .iter()
+                .filter(|a| filter.include_deleted || a.deleted_at.is_none())
+                .filter(|a| contains(&a.email, &filter.email) && contains(&a.name, &filter.name))
+                .filter(|a| filter.created_after.is_none_or(|at| a.created_at >= at))
+                .filter(|a| filter.created_before.is_none_or(|at| a.created_at < at))
+                .cloned()
+                .collect();
+
+            matched.sort_by_key(|a| (key(a), a.id.clone()));
+
+            if page.sort.order == SortOrder::Desc {
+                matched.reverse();
+            }
+
+            if let Some(cursor) = page.cursor {
+                let position = matched.iter().position(|a| a.id == cursor);
+                matched = matched.split_off(position.map_or(matched.len(), |p| p + 1));
+            }
+
+            matched.truncate(page.limit + 1);
+
+            Ok(Page::from_overfetched(
+                matched,
+                page.limit,
+                |a: &Account| a.id.clone(),
+            ))
+        }
+
+        async fn update(
+            &self,
+            id: &str,
+            account: UpdateAccount,
+        ) -> RepositoryResult<Option<Account>> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .map(|acc| {
+                    if let Some(name) = account.name {
+                        acc.name = name;
+                    }
+                    if let Some(email) = account.email.filter(|email| *email != acc.email) {
+                        acc.email = email;
+                        acc.email_verified_at = None;
+                    }
+                    acc.clone()
+                }))
+        }
+
+        async fn delete(&self, id: &str) -> RepositoryResult<bool> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .map(|acc| acc.deleted_at = Some(Utc::now().timestamp()))
+                .is_some())
+        }
+
+        async fn update_password(
+            &self,
+            id: &str,
+            password: String,
+        ) -> RepositoryResult<Option<Account>> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .map(|acc| {
+                    acc.password = password;
+                    acc.password_changed_at = Some(Utc::now().timestamp());
+                    acc.clone()
+                }))
+        }
+
+        async fn rehash_password(
+            &self,
+            id: &str,
+            current: &str,
+            password: String,
+        ) -> RepositoryResult<()> {
+            let mut accounts = self.accounts.lock().await;
+
+            if let Some(acc) = accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.password == current && a.deleted_at.is_none())
+            {
+                acc.password = password;
+            }
+
+            Ok(())
+        }
+
+        async fn find_password_changed_since(&self, since: i64) -> RepositoryResult<Vec<Account>> {
+            let accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter()
+                .filter(|a| a.password_changed_at.is_some_and(|at| at > since))
+                .filter(|a| a.deleted_at.is_none())
+                .cloned()
+                .collect())
+        }
+
+        async fn verify_email(&self, id: &str) -> RepositoryResult<Option<Account>> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .map(|acc| {
+                    acc.email_verified_at = Some(Utc::now().timestamp());
+                    acc.clone()
+                }))
+        }
+
+        async fn update_status(
+            &self,
+            id: &str,
+            status: AccountStatus,
+        ) -> RepositoryResult<Option<Account>> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_none())
+                .map(|acc| {
+                    acc.status = status;
+                    acc.clone()
+                }))
+        }
+
+        async fn find_suspended(&self) -> RepositoryResult<Vec<String>> {
+            let accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter()
+                .filter(|a| a.status == AccountStatus::Suspended && a.deleted_at.is_none())
+                .map(|a| a.id.clone())
+                .collect())
+        }
+
+        async fn restore(&self, id: &str, deleted_since: i64) -> RepositoryResult<Option<Account>> {
+            let mut accounts = self.accounts.lock().await;
+
+            Ok(accounts
+                .iter_mut()
+                .find(|a| a.id == id && a.deleted_at.is_some_and(|at| at >= deleted_since))
+                .map(|acc| {
+                    acc.deleted_at = None;
+                    acc.clone()
+                }))
+        }
+
+        async fn purge(&self, deleted_before: i64) -> RepositoryResult<usize> {
+            let mut accounts = self.accounts.lock().await;
+
+            let count = accounts.len();
+            accounts.retain(|a| a.deleted_at.is_none_or(|at| at >= deleted_before));
+
+            Ok(count - accounts.len())
+        }
     }
 }
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::account::{Account, AccountStatus};
+use crate::domain::models::account_audit::CreateAccountAudit;
+use crate::domain::repositories::account_audit::AccountAuditRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::account::SurrealAccount;
+
+pub struct AccountAuditRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl AccountAuditRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const ACCOUNT_AUDIT: &str = "account_audit";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl AccountAuditRepository for AccountAuditRepositoryImpl {
+    async fn change_status(
+        &self,
+        status: AccountStatus,
+        audit: CreateAccountAudit,
+    ) -> RepositoryResult<Option<Account>> {
+        let mut res = self
+            .db
+            .query(
+                "BEGIN TRANSACTION;
+                LET $updated = (UPDATE type::thing($account_table, $account) SET status = $status WHERE deleted_at IS NONE RETURN AFTER);
+                IF $updated {
+                    CREATE type::table($table) CONTENT {
+                        account: type::thing($account_table, $account),
+                        actor: type::thing($account_table, $actor),
+                        action: $action,
+                        reason: $reason
+                    };
+                };
+                RETURN $updated;
+                COMMIT TRANSACTION;",
+            )
+            .bind(("table", ACCOUNT_AUDIT))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", audit.account))
+            .bind(("actor", audit.actor))
+            .bind(("action", audit.action))
+            .bind(("reason", audit.reason))
+            .bind(("status", status))
+            .await?;
+
+        let last = res.num_statements() - 1;
+        let account: Option<SurrealAccount> = res.take(last)?;
+
+        Ok(account.map(Into::into))
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::repositories::account::AccountRepository;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+
+    pub struct AccountAuditRepositoryImpl {
+        pub accounts: Arc<AccountRepositoryImpl>,
+        pub audits: Mutex<Vec<CreateAccountAudit>>,
+    }
+
+    #[async_trait]
+    impl AccountAuditRepository for AccountAuditRepositoryImpl {
+        async fn change_status(
+            &self,
+            status: AccountStatus,
+            audit: CreateAccountAudit,
+        ) -> RepositoryResult<Option<Account>> {
+            let account = self.accounts.update_status(&audit.account, status).await?;
+
+            if account.is_some() {
+                self.audits.lock().await.push(audit);
+            }
+
+            Ok(account)
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::api_key::{ApiKey, CreateApiKey};
+use crate::domain::repositories::api_key::ApiKeyRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::api_key::SurrealApiKey;
+
+pub struct ApiKeyRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl ApiKeyRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const API_KEY: &str = "api_key";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {
+    async fn create(&self, api_key: CreateApiKey) -> RepositoryResult<ApiKey> {
+        let api_key: Option<SurrealApiKey> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    name: $name,
+                    key_hash: $key_hash,
+                    prefix: $prefix,
+                    scopes: $scopes,
+                    expires_at: IF $expires_at THEN time::from::unix($expires_at) END
+                }",
+            )
+            .bind(("table", API_KEY))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", api_key.account))
+            .bind(("name", api_key.name))
+            .bind(("key_hash", api_key.key_hash))
+            .bind(("prefix", api_key.prefix))
+            .bind(("scopes", api_key.scopes))
+            .bind(("expires_at", api_key.expiration))
+            .await?
+            .take(0)?;
+
+        Ok(api_key.unwrap().into())
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKey>> {
+        let api_key: Option<SurrealApiKey> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE key_hash = type::string($key_hash)")
+            .bind(("table", API_KEY))
+            .bind(("key_hash", key_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(api_key.map(Into::into))
+    }
+
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<ApiKey>> {
+        let api_keys: Vec<SurrealApiKey> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE account = type::thing($account_table, $account) ORDER BY created_at DESC")
+            .bind(("table", API_KEY))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(api_keys.into_iter().map(Into::into).collect())
+    }
+
+    async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool> {
+        let api_key: Option<SurrealApiKey> = self
+            .db
+            .query("DELETE type::thing($table, $id) WHERE account = type::thing($account_table, $account) RETURN BEFORE")
+            .bind(("table", API_KEY))
+            .bind(("id", id.to_owned()))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(api_key.is_some())
+    }
+
+    async fn touch(&self, id: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::thing($table, $id) SET last_used_at = time::now()")
+            .bind(("table", API_KEY))
+            .bind(("id", id.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct ApiKeyRepositoryImpl {
+        pub api_keys: Mutex<Vec<(ApiKey, String)>>,
+    }
+
+    #[async_trait]
+    impl ApiKeyRepository for ApiKeyRepositoryImpl {
+        async fn create(&self, api_key: CreateApiKey) -> RepositoryResult<ApiKey> {
+            let mut api_keys = self.api_keys.lock().await;
+
+            let created = ApiKey {
+                id: api_keys.len().to_string(),
+                account: api_key.account,
+                name: api_key.name,
+                prefix: api_key.prefix,
+                scopes: api_key.scopes,
+                expiration: api_key.expiration,
+                last_used_at: None,
+                created_at: chrono::Utc::now().timestamp(),
+            };
+
+            api_keys.push((created.clone(), api_key.key_hash));
+
+            Ok(created)
+        }
+
+        async fn find_by_hash(&self, key_hash: &str) -> RepositoryResult<Option<ApiKey>> {
+            let api_keys = self.api_keys.lock().await;
+
+            Ok(api_keys
+                .iter()
+                .find(|(_, hash)| hash == key_hash)
+                .map(|(api_key, _)| api_key.clone()))
+        }
+
+        async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<ApiKey>> {
+            let api_keys = self.api_keys.lock().await;
+
+            Ok(api_keys
+                .iter()
+                .filter(|(api_key, _)| api_key.account == account)
+                .map(|(api_key, _)| api_key.clone())
+                .collect())
+        }
+
+        async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool> {
+            let mut api_keys = self.api_keys.lock().await;
+
+            let count = api_keys.len();
+            api_keys.retain(|(api_key, _)| api_key.id != id || api_key.account != account);
+
+            Ok(api_keys.len() < count)
+        }
+
+        async fn touch(&self, id: &str) -> RepositoryResult<()> {
+            let mut api_keys = self.api_keys.lock().await;
+
+            if let Some((api_key, _)) = api_keys.iter_mut().find(|(api_key, _)| api_key.id == id) {
+                api_key.last_used_at = Some(chrono::Utc::now().timestamp());
+            }
+
+            Ok(())
+        }
+    }
+}
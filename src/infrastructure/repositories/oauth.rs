@@ -0,0 +1,426 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::oauth::{
+    AuthorizationCode, CreateAuthorizationCode, CreateOAuthClient, CreateOAuthRefreshToken,
+    OAuthClient, StoredOAuthRefreshToken,
+};
+use crate::domain::repositories::oauth::OAuthRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::oauth::{
+    SurrealAuthorizationCode, SurrealOAuthClient, SurrealOAuthRefreshToken,
+};
+
+pub struct OAuthRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl OAuthRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const OAUTH_CLIENT: &str = "oauth_client";
+const OAUTH_AUTHORIZATION_CODE: &str = "oauth_authorization_code";
+const OAUTH_REFRESH_TOKEN: &str = "oauth_refresh_token";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl OAuthRepository for OAuthRepositoryImpl {
+    async fn create_client(&self, client: CreateOAuthClient) -> RepositoryResult<OAuthClient> {
+        let client: Option<SurrealOAuthClient> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    owner: type::thing($account_table, $owner),
+                    name: $name,
+                    secret_hash: $secret_hash,
+                    redirect_uris: $redirect_uris,
+                    grant_types: $grant_types,
+                    scopes: $scopes
+                }",
+            )
+            .bind(("table", OAUTH_CLIENT))
+            .bind(("account_table", ACCOUNT))
+            .bind(("owner", client.owner))
+            .bind(("name", client.name))
+            .bind(("secret_hash", client.secret_hash))
+            .bind(("redirect_uris", client.redirect_uris))
+            .bind(("grant_types", client.grant_types))
+            .bind(("scopes", client.scopes))
+            .await?
+            .take(0)?;
+
+        Ok(client.unwrap().into())
+    }
+
+    async fn find_client(&self, id: &str) -> RepositoryResult<Option<OAuthClient>> {
+        let client: Option<SurrealOAuthClient> = self
+            .db
+            .query("SELECT * FROM type::thing($table, $id)")
+            .bind(("table", OAUTH_CLIENT))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(client.map(Into::into))
+    }
+
+    async fn find_clients_by_owner(&self, owner: &str) -> RepositoryResult<Vec<OAuthClient>> {
+        let clients: Vec<SurrealOAuthClient> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE owner = type::thing($account_table, $owner) ORDER BY created_at DESC")
+            .bind(("table", OAUTH_CLIENT))
+            .bind(("account_table", ACCOUNT))
+            .bind(("owner", owner.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(clients.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete_client(&self, id: &str, owner: &str) -> RepositoryResult<bool> {
+        let client: Option<SurrealOAuthClient> = self
+            .db
+            .query("DELETE type::thing($table, $id) WHERE owner = type::thing($account_table, $owner) RETURN BEFORE")
+            .bind(("table", OAUTH_CLIENT))
+            .bind(("id", id.to_owned()))
+            .bind(("account_table", ACCOUNT))
+            .bind(("owner", owner.to_owned()))
+            .await?
+            .take(0)?;
+
+        if client.is_none() {
+            return Ok(false);
+        }
+
+        // Outstanding grants die with the client
+        self.db
+            .query(
+                "DELETE type::table($code_table) WHERE client = type::thing($table, $id);
+                DELETE type::table($token_table) WHERE client = type::thing($table, $id);",
+            )
+            .bind(("table", OAUTH_CLIENT))
+            .bind(("code_table", OAUTH_AUTHORIZATION_CODE))
+            .bind(("token_table", OAUTH_REFRESH_TOKEN))
+            .bind(("id", id.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(true)
+    }
+
+    async fn create_code(&self, code: CreateAuthorizationCode) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    client: type::thing($client_table, $client),
+                    account: type::thing($account_table, $account),
+                    code_hash: $code_hash,
+                    redirect_uri: $redirect_uri,
+                    scopes: $scopes,
+                    code_challenge: $code_challenge,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", OAUTH_AUTHORIZATION_CODE))
+            .bind(("client_table", OAUTH_CLIENT))
+            .bind(("client", code.client))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", code.account))
+            .bind(("code_hash", code.code_hash))
+            .bind(("redirect_uri", code.redirect_uri))
+            .bind(("scopes", code.scopes))
+            .bind(("code_challenge", code.code_challenge))
+            .bind(("expires_at", code.expiration))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn consume_code(&self, code_hash: &str) -> RepositoryResult<Option<AuthorizationCode>> {
+        let code: Option<SurrealAuthorizationCode> = self
+            .db
+            .query(
+                "UPDATE type::table($table) SET used_at = time::now() WHERE code_hash = type::string($code_hash) AND used_at IS NONE AND expires_at > time::now() RETURN AFTER",
+            )
+            .bind(("table", OAUTH_AUTHORIZATION_CODE))
+            .bind(("code_hash", code_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(code.map(Into::into))
+    }
+
+    async fn create_refresh_token(
+        &self,
+        token: CreateOAuthRefreshToken,
+    ) -> RepositoryResult<StoredOAuthRefreshToken> {
+        let token: Option<SurrealOAuthRefreshToken> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    client: type::thing($client_table, $client),
+                    account: type::thing($account_table, $account),
+                    token_hash: $token_hash,
+                    family: $family,
+                    scopes: $scopes,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", OAUTH_REFRESH_TOKEN))
+            .bind(("client_table", OAUTH_CLIENT))
+            .bind(("client", token.client))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", token.account))
+            .bind(("token_hash", token.token_hash))
+            .bind(("family", token.family))
+            .bind(("scopes", token.scopes))
+            .bind(("expires_at", token.expiration))
+            .await?
+            .take(0)?;
+
+        Ok(token.unwrap().into())
+    }
+
+    async fn find_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> RepositoryResult<Option<StoredOAuthRefreshToken>> {
+        let token: Option<SurrealOAuthRefreshToken> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE token_hash = type::string($token_hash)")
+            .bind(("table", OAUTH_REFRESH_TOKEN))
+            .bind(("token_hash", token_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.map(Into::into))
+    }
+
+    async fn rotate_refresh_token(&self, id: &str) -> RepositoryResult<bool> {
+        let token: Option<SurrealOAuthRefreshToken> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $id) SET rotated_at = time::now() WHERE rotated_at IS NONE RETURN AFTER",
+            )
+            .bind(("table", OAUTH_REFRESH_TOKEN))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.is_some())
+    }
+
+    async fn revoke_refresh_family(&self, family: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::table($table) SET revoked_at = time::now() WHERE family = type::string($family) AND revoked_at IS NONE")
+            .bind(("table", OAUTH_REFRESH_TOKEN))
+            .bind(("family", family.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::table($table) SET revoked_at = time::now() WHERE account = type::thing($account_table, $account) AND revoked_at IS NONE")
+            .bind(("table", OAUTH_REFRESH_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Codes of abandoned authorizations are never exchanged, used ones are kept until they expire too
+    async fn prune(&self) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::table($table) WHERE expires_at <= time::now()")
+            .bind(("table", OAUTH_AUTHORIZATION_CODE))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct OAuthRepositoryImpl {
+        pub clients: Mutex<Vec<OAuthClient>>,
+        pub codes: Mutex<Vec<(CreateAuthorizationCode, bool)>>,
+        pub refresh_tokens: Mutex<Vec<(StoredOAuthRefreshToken, String)>>,
+    }
+
+    #[async_trait]
+    impl OAuthRepository for OAuthRepositoryImpl {
+        async fn create_client(&self, client: CreateOAuthClient) -> RepositoryResult<OAuthClient> {
+            let mut clients = self.clients.lock().await;
+
+            let created = OAuthClient {
+                id: format!("client{}", clients.len()),
+                owner: client.owner,
+                name: client.name,
+                secret_hash: client.secret_hash,
+                redirect_uris: client.redirect_uris,
+                grant_types: client.grant_types,
+                scopes: client.scopes,
+                created_at: chrono::Utc::now().timestamp(),
+            };
+
+            clients.push(created.clone());
+
+            Ok(created)
+        }
+
+        async fn find_client(&self, id: &str) -> RepositoryResult<Option<OAuthClient>> {
+            let clients = self.clients.lock().await;
+
+            Ok(clients.iter().find(|c| c.id == id).cloned())
+        }
+
+        async fn find_clients_by_owner(&self, owner: &str) -> RepositoryResult<Vec<OAuthClient>> {
+            let clients = self.clients.lock().await;
+
+            Ok(clients
+                .iter()
+                .filter(|c| c.owner == owner)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_client(&self, id: &str, owner: &str) -> RepositoryResult<bool> {
+            let mut clients = self.clients.lock().await;
+
+            let count = clients.len();
+            clients.retain(|c| c.id != id || c.owner != owner);
+
+            if clients.len() == count {
+                return Ok(false);
+            }
+
+            self.codes.lock().await.retain(|(c, _)| c.client != id);
+            self.refresh_tokens
+                .lock()
+                .await
+                .retain(|(t, _)| t.client != id);
+
+            Ok(true)
+        }
+
+        async fn create_code(&self, code: CreateAuthorizationCode) -> RepositoryResult<()> {
+            self.codes.lock().await.push((code, false));
+
+            Ok(())
+        }
+
+        async fn consume_code(
+            &self,
+            code_hash: &str,
+        ) -> RepositoryResult<Option<AuthorizationCode>> {
+            let mut codes = self.codes.lock().await;
+
+            let now = chrono::Utc::now().timestamp();
+
+            Ok(codes
+                .iter_mut()
+                .find(|(c, used)| c.code_hash == code_hash && c.expiration > now && !used)
+                .map(|(c, used)| {
+                    *used = true;
+                    AuthorizationCode {
+                        client: c.client.clone(),
+                        account: c.account.clone(),
+                        redirect_uri: c.redirect_uri.clone(),
+                        scopes: c.scopes.clone(),
+                        code_challenge: c.code_challenge.clone(),
+                    }
+                }))
+        }
+
+        async fn create_refresh_token(
+            &self,
+            token: CreateOAuthRefreshToken,
+        ) -> RepositoryResult<StoredOAuthRefreshToken> {
+            let mut tokens = self.refresh_tokens.lock().await;
+
+            let stored = StoredOAuthRefreshToken {
+                id: tokens.len().to_string(),
+                client: token.client,
+                account: token.account,
+                family: token.family,
+                scopes: token.scopes,
+                expiration: token.expiration,
+                rotated: false,
+                revoked: false,
+            };
+
+            tokens.push((stored.clone(), token.token_hash));
+
+            Ok(stored)
+        }
+
+        async fn find_refresh_token(
+            &self,
+            token_hash: &str,
+        ) -> RepositoryResult<Option<StoredOAuthRefreshToken>> {
+            let tokens = self.refresh_tokens.lock().await;
+
+            Ok(tokens
+                .iter()
+                .find(|(_, hash)| hash == token_hash)
+                .map(|(t, _)| t.clone()))
+        }
+
+        async fn rotate_refresh_token(&self, id: &str) -> RepositoryResult<bool> {
+            let mut tokens = self.refresh_tokens.lock().await;
+
+            Ok(tokens
+                .iter_mut()
+                .find(|(t, _)| t.id == id && !t.rotated)
+                .map(|(t, _)| t.rotated = true)
+                .is_some())
+        }
+
+        async fn revoke_refresh_family(&self, family: &str) -> RepositoryResult<()> {
+            let mut tokens = self.refresh_tokens.lock().await;
+
+            tokens
+                .iter_mut()
+                .filter(|(t, _)| t.family == family)
+                .for_each(|(t, _)| t.revoked = true);
+
+            Ok(())
+        }
+
+        async fn revoke_account(&self, account: &str) -> RepositoryResult<()> {
+            let mut tokens = self.refresh_tokens.lock().await;
+
+            tokens
+                .iter_mut()
+                .filter(|(t, _)| t.account == account)
+                .for_each(|(t, _)| t.revoked = true);
+
+            Ok(())
+        }
+
+        async fn prune(&self) -> RepositoryResult<()> {
+            let now = chrono::Utc::now().timestamp();
+
+            self.codes.lock().await.retain(|(c, _)| c.expiration > now);
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::oidc::{
+    AccountIdentity, CreateAccountIdentity, CreateOidcState, OidcState,
+};
+use crate::domain::repositories::oidc::OidcRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::oidc::{SurrealAccountIdentity, SurrealOidcState};
+
+pub struct OidcRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl OidcRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const OIDC_STATE: &str = "oidc_state";
+const ACCOUNT_IDENTITY: &str = "account_identity";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl OidcRepository for OidcRepositoryImpl {
+    async fn create_state(&self, state: CreateOidcState) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: IF $account THEN type::thing($account_table, $account) END,
+                    provider: $provider,
+                    state_hash: $state_hash,
+                    nonce: $nonce,
+                    code_verifier: $code_verifier,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", OIDC_STATE))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", state.account))
+            .bind(("provider", state.provider))
+            .bind(("state_hash", state.state_hash))
+            .bind(("nonce", state.nonce))
+            .bind(("code_verifier", state.code_verifier))
+            .bind(("expires_at", state.expiration))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn consume_state(
+        &self,
+        state_hash: &str,
+        provider: &str,
+    ) -> RepositoryResult<Option<OidcState>> {
+        let state: Option<SurrealOidcState> = self
+            .db
+            .query(
+                "UPDATE type::table($table) SET used_at = time::now() WHERE state_hash = type::string($state_hash) AND provider = type::string($provider) AND used_at IS NONE AND expires_at > time::now() RETURN AFTER",
+            )
+            .bind(("table", OIDC_STATE))
+            .bind(("state_hash", state_hash.to_owned()))
+            .bind(("provider", provider.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(state.map(Into::into))
+    }
+
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> RepositoryResult<Option<AccountIdentity>> {
+        let identity: Option<SurrealAccountIdentity> = self
+            .db
+            .query(
+                "SELECT * FROM type::table($table) WHERE provider = type::string($provider) AND subject = type::string($subject)",
+            )
+            .bind(("table", ACCOUNT_IDENTITY))
+            .bind(("provider", provider.to_owned()))
+            .bind(("subject", subject.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(identity.map(Into::into))
+    }
+
+    async fn create_identity(&self, identity: CreateAccountIdentity) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    provider: $provider,
+                    subject: $subject,
+                    email: $email
+                }",
+            )
+            .bind(("table", ACCOUNT_IDENTITY))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", identity.account))
+            .bind(("provider", identity.provider))
+            .bind(("subject", identity.subject))
+            .bind(("email", identity.email))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // States of abandoned sign-ins are never consumed, used ones are kept until they expire too
+    async fn prune(&self) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::table($table) WHERE expires_at <= time::now()")
+            .bind(("table", OIDC_STATE))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct OidcRepositoryImpl {
+        pub states: Mutex<Vec<(CreateOidcState, bool)>>,
+        pub identities: Mutex<Vec<CreateAccountIdentity>>,
+    }
+
+    #[async_trait]
+    impl OidcRepository for OidcRepositoryImpl {
+        async fn create_state(&self, state: CreateOidcState) -> RepositoryResult<()> {
+            self.states.lock().await.push((state, false));
+
+            Ok(())
+        }
+
+        async fn consume_state(
+            &self,
+            state_hash: &str,
+            provider: &str,
+        ) -> RepositoryResult<Option<OidcState>> {
+            let mut states = self.states.lock().await;
+
+            let now = chrono::Utc::now().timestamp();
+
+            Ok(states
+                .iter_mut()
+                .find(|(s, used)| {
+                    s.state_hash == state_hash
+                        && s.provider == provider
+                        && s.expiration > now
+                        && !used
+                })
+                .map(|(s, used)| {
+                    *used = true;
+                    OidcState {
+                        account: s.account.clone(),
+                        nonce: s.nonce.clone(),
+                        code_verifier: s.code_verifier.clone(),
+                    }
+                }))
+        }
+
+        async fn find_identity(
+            &self,
+            provider: &str,
+            subject: &str,
+        ) -> RepositoryResult<Option<AccountIdentity>> {
+            let identities = self.identities.lock().await;
+
+            Ok(identities
+                .iter()
+                .find(|i| i.provider == provider && i.subject == subject)
+                .map(|i| AccountIdentity {
+                    account: i.account.clone(),
+                }))
+        }
+
+        async fn create_identity(&self, identity: CreateAccountIdentity) -> RepositoryResult<()> {
+            self.identities.lock().await.push(identity);
+
+            Ok(())
+        }
+
+        async fn prune(&self) -> RepositoryResult<()> {
+            let now = chrono::Utc::now().timestamp();
+
+            self.states.lock().await.retain(|(s, _)| s.expiration > now);
+
+            Ok(())
+        }
+    }
+}
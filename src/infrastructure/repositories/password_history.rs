@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::repositories::password_history::PasswordHistoryRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+
+pub struct PasswordHistoryRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl PasswordHistoryRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const PASSWORD_HISTORY: &str = "password_history";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl PasswordHistoryRepository for PasswordHistoryRepositoryImpl {
+    async fn find_recent(&self, account: &str, limit: usize) -> RepositoryResult<Vec<String>> {
+        let hashes: Vec<String> = self
+            .db
+            .query(
+                "(SELECT password_hash, created_at FROM type::table($table)
+                    WHERE account = type::thing($account_table, $account)
+                    ORDER BY created_at DESC LIMIT $limit).password_hash",
+            )
+            .bind(("table", PASSWORD_HISTORY))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .bind(("limit", limit))
+            .await?
+            .take(0)?;
+
+        Ok(hashes)
+    }
+
+    // Entries past the most recent `keep` are pruned in the same round trip
+    async fn add(&self, account: &str, password_hash: String, keep: usize) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    password_hash: $password_hash
+                };
+                LET $kept = (SELECT id, created_at FROM type::table($table)
+                    WHERE account = type::thing($account_table, $account)
+                    ORDER BY created_at DESC LIMIT $keep).id;
+                DELETE type::table($table)
+                    WHERE account = type::thing($account_table, $account) AND id NOTINSIDE $kept;",
+            )
+            .bind(("table", PASSWORD_HISTORY))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .bind(("password_hash", password_hash))
+            .bind(("keep", keep))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct PasswordHistoryRepositoryImpl {
+        // Oldest first, as (account, password hash)
+        pub entries: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl PasswordHistoryRepository for PasswordHistoryRepositoryImpl {
+        async fn find_recent(&self, account: &str, limit: usize) -> RepositoryResult<Vec<String>> {
+            let entries = self.entries.lock().await;
+
+            Ok(entries
+                .iter()
+                .rev()
+                .filter(|(owner, _)| owner == account)
+                .take(limit)
+                .map(|(_, hash)| hash.clone())
+                .collect())
+        }
+
+        async fn add(
+            &self,
+            account: &str,
+            password_hash: String,
+            keep: usize,
+        ) -> RepositoryResult<()> {
+            let mut entries = self.entries.lock().await;
+
+            entries.push((account.to_string(), password_hash));
+
+            let count = entries.iter().filter(|(owner, _)| owner == account).count();
+            let mut excess = count.saturating_sub(keep);
+
+            entries.retain(|(owner, _)| {
+                if owner == account && excess > 0 {
+                    excess -= 1;
+                    return false;
+                }
+
+                true
+            });
+
+            Ok(())
+        }
+    }
+}
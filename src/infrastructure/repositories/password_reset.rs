@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::password_reset::{CreatePasswordResetToken, PasswordResetToken};
+use crate::domain::repositories::password_reset::PasswordResetRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::password_reset::SurrealPasswordResetToken;
+
+pub struct PasswordResetRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl PasswordResetRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const PASSWORD_RESET_TOKEN: &str = "password_reset_token";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl PasswordResetRepository for PasswordResetRepositoryImpl {
+    async fn create(
+        &self,
+        new_token: CreatePasswordResetToken,
+    ) -> RepositoryResult<PasswordResetToken> {
+        let token: Option<SurrealPasswordResetToken> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    token_hash: $token_hash,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", PASSWORD_RESET_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", new_token.account))
+            .bind(("token_hash", new_token.token_hash))
+            .bind(("expires_at", new_token.expiration))
+            .await?
+            .take(0)?;
+
+        Ok(token.unwrap().into())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<PasswordResetToken>> {
+        let token: Option<SurrealPasswordResetToken> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE token_hash = type::string($token_hash)")
+            .bind(("table", PASSWORD_RESET_TOKEN))
+            .bind(("token_hash", token_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.map(Into::into))
+    }
+
+    async fn consume(&self, id: &str) -> RepositoryResult<bool> {
+        let token: Option<SurrealPasswordResetToken> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $id) SET used_at = time::now() WHERE used_at IS NONE RETURN AFTER",
+            )
+            .bind(("table", PASSWORD_RESET_TOKEN))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.is_some())
+    }
+
+    async fn consume_account(&self, account: &str) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "UPDATE type::table($table) SET used_at = time::now() WHERE account = type::thing($account_table, $account) AND used_at IS NONE",
+            )
+            .bind(("table", PASSWORD_RESET_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct PasswordResetRepositoryImpl {
+        pub tokens: Mutex<HashMap<String, PasswordResetToken>>,
+    }
+
+    #[async_trait]
+    impl PasswordResetRepository for PasswordResetRepositoryImpl {
+        async fn create(
+            &self,
+            new_token: CreatePasswordResetToken,
+        ) -> RepositoryResult<PasswordResetToken> {
+            let mut tokens = self.tokens.lock().await;
+
+            let token = PasswordResetToken {
+                id: tokens.len().to_string(),
+                account: new_token.account,
+                expiration: new_token.expiration,
+                used: false,
+            };
+
+            tokens.insert(new_token.token_hash, token.clone());
+
+            Ok(token)
+        }
+
+        async fn find_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> RepositoryResult<Option<PasswordResetToken>> {
+            let tokens = self.tokens.lock().await;
+            Ok(tokens.get(token_hash).cloned())
+        }
+
+        async fn consume(&self, id: &str) -> RepositoryResult<bool> {
+            let mut tokens = self.tokens.lock().await;
+
+            match tokens.values_mut().find(|t| t.id == id && !t.used) {
+                Some(token) => {
+                    token.used = true;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn consume_account(&self, account: &str) -> RepositoryResult<()> {
+            let mut tokens = self.tokens.lock().await;
+
+            for token in tokens.values_mut().filter(|t| t.account == account) {
+                token.used = true;
+            }
+
+            Ok(())
+        }
+    }
+}
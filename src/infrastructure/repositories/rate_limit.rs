@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::rate_limit::RateLimitBucket;
+use crate::domain::repositories::rate_limit::RateLimitRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::rate_limit::SurrealRateLimitBucket;
+
+// Buckets shared by every replica connected to the same database
+pub struct RateLimitRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl RateLimitRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const RATE_LIMIT_BUCKET: &str = "rate_limit_bucket";
+
+#[async_trait]
+impl RateLimitRepository for RateLimitRepositoryImpl {
+    // Assignments are applied in order, so the bucket is refilled before a token is taken
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        refill_rate: f64,
+    ) -> RepositoryResult<RateLimitBucket> {
+        let bucket: Option<SurrealRateLimitBucket> = self
+            .db
+            .query(
+                "UPSERT type::thing($table, $key) SET
+                    tokens = math::min([$capacity, (tokens ?? $capacity) + duration::millis(time::now() - (updated_at ?? time::now())) * $refill_rate / 1000]),
+                    allowed = tokens >= 1,
+                    tokens = IF allowed THEN tokens - 1 ELSE tokens END,
+                    updated_at = time::now()
+                RETURN AFTER",
+            )
+            .bind(("table", RATE_LIMIT_BUCKET))
+            .bind(("key", key.to_owned()))
+            .bind(("capacity", capacity as f64))
+            .bind(("refill_rate", refill_rate))
+            .await?
+            .take(0)?;
+
+        Ok(bucket.unwrap().into())
+    }
+
+    async fn prune(&self, idle: i64) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::table($table) WHERE updated_at < time::now() - duration::from::secs($idle)")
+            .bind(("table", RATE_LIMIT_BUCKET))
+            .bind(("idle", idle))
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Full buckets carry no state, so they are dropped once the map grows past this size
+const MEMORY_BUCKETS: usize = 10_000;
+
+// Buckets local to a single process
+#[derive(Default)]
+pub struct MemoryRateLimitRepositoryImpl {
+    buckets: Mutex<HashMap<String, (f64, i64)>>,
+}
+
+#[async_trait]
+impl RateLimitRepository for MemoryRateLimitRepositoryImpl {
+    async fn acquire(
+        &self,
+        key: &str,
+        capacity: u32,
+        refill_rate: f64,
+    ) -> RepositoryResult<RateLimitBucket> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        let now = Utc::now().timestamp_millis();
+        let capacity = capacity as f64;
+
+        let refill = |(tokens, updated_at): (f64, i64)| {
+            (tokens + (now - updated_at) as f64 * refill_rate / 1000.0).min(capacity)
+        };
+
+        if buckets.len() >= MEMORY_BUCKETS {
+            buckets.retain(|_, bucket| refill(*bucket) < capacity);
+        }
+
+        let bucket = buckets.entry(key.to_owned()).or_insert((capacity, now));
+
+        let tokens = refill(*bucket);
+        let allowed = tokens >= 1.0;
+        let tokens = if allowed { tokens - 1.0 } else { tokens };
+
+        *bucket = (tokens, now);
+
+        Ok(RateLimitBucket { allowed, tokens })
+    }
+
+    async fn prune(&self, idle: i64) -> RepositoryResult<()> {
+        let since = Utc::now().timestamp_millis() - idle * 1000;
+
+        self.buckets
+            .lock()
+            .unwrap()
+            .retain(|_, (_, updated_at)| *updated_at >= since);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_bucket_exhausted() {
+        let repository = MemoryRateLimitRepositoryImpl::default();
+
+        for remaining in (0..3).rev() {
+            let bucket = repository.acquire("key", 3, 0.001).await.unwrap();
+
+            assert!(bucket.allowed);
+            assert_eq!(bucket.tokens.floor() as u32, remaining);
+        }
+
+        assert!(!repository.acquire("key", 3, 0.001).await.unwrap().allowed);
+        assert!(repository.acquire("other", 3, 0.001).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_memory_bucket_refill() {
+        let repository = MemoryRateLimitRepositoryImpl::default();
+
+        assert!(repository.acquire("key", 1, 1000.0).await.unwrap().allowed);
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        assert!(repository.acquire("key", 1, 1000.0).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    async fn test_memory_prune() {
+        let repository = MemoryRateLimitRepositoryImpl::default();
+
+        repository.acquire("key", 1, 0.001).await.unwrap();
+
+        repository.prune(60).await.unwrap();
+        assert!(!repository.acquire("key", 1, 0.001).await.unwrap().allowed);
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        repository.prune(0).await.unwrap();
+        assert!(repository.acquire("key", 1, 0.001).await.unwrap().allowed);
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::refresh_token::{CreateRefreshToken, StoredRefreshToken};
+use crate::domain::repositories::refresh_token::RefreshTokenRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::models::refresh_token::SurrealRefreshToken;
+
+pub struct RefreshTokenRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl RefreshTokenRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const REFRESH_TOKEN: &str = "refresh_token";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn create(&self, new_token: CreateRefreshToken) -> RepositoryResult<StoredRefreshToken> {
+        let token: Option<SurrealRefreshToken> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    token_hash: $token_hash,
+                    family: $family,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", new_token.account))
+            .bind(("token_hash", new_token.token_hash))
+            .bind(("family", new_token.family))
+            .bind(("expires_at", new_token.expiration))
+            .await?
+            .take(0)?;
+
+        Ok(token.unwrap().into())
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> RepositoryResult<Option<StoredRefreshToken>> {
+        let token: Option<SurrealRefreshToken> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE token_hash = type::string($token_hash)")
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("token_hash", token_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.map(Into::into))
+    }
+
+    async fn rotate(&self, id: &str) -> RepositoryResult<bool> {
+        let token: Option<SurrealRefreshToken> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $id) SET rotated_at = time::now() WHERE rotated_at IS NONE RETURN AFTER",
+            )
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("id", id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(token.is_some())
+    }
+
+    // Families that still hold a token which can be exchanged
+    async fn find_live_families(&self, account: &str) -> RepositoryResult<Vec<String>> {
+        let families: Vec<String> = self
+            .db
+            .query("SELECT VALUE family FROM type::table($table) WHERE account = type::thing($account_table, $account) AND rotated_at IS NONE AND revoked_at IS NONE AND expires_at > time::now()")
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(families)
+    }
+
+    async fn revoke_family(&self, family: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::table($table) SET revoked_at = time::now() WHERE family = type::string($family) AND revoked_at IS NONE")
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("family", family.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::table($table) SET revoked_at = time::now() WHERE account = type::thing($account_table, $account) AND revoked_at IS NONE")
+            .bind(("table", REFRESH_TOKEN))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct RefreshTokenRepositoryImpl {
+        pub tokens: Mutex<HashMap<String, StoredRefreshToken>>,
+    }
+
+    #[async_trait]
+    impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+        async fn create(
+            &self,
+            new_token: CreateRefreshToken,
+        ) -> RepositoryResult<StoredRefreshToken> {
+            let mut tokens = self.tokens.lock().await;
+
+            let token = StoredRefreshToken {
+                id: tokens.len().to_string(),
+                account: new_token.account,
+                family: new_token.family,
+                expiration: new_token.expiration,
+                rotated: false,
+                revoked: false,
+            };
+
+            tokens.insert(new_token.token_hash, token.clone());
+
+            Ok(token)
+        }
+
+        async fn find_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> RepositoryResult<Option<StoredRefreshToken>> {
+            let tokens = self.tokens.lock().await;
+            Ok(tokens.get(token_hash).cloned())
+        }
+
+        async fn rotate(&self, id: &str) -> RepositoryResult<bool> {
+            let mut tokens = self.tokens.lock().await;
+
+            match tokens.values_mut().find(|t| t.id == id && !t.rotated) {
+                Some(token) => {
+                    token.rotated = true;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn find_live_families(&self, account: &str) -> RepositoryResult<Vec<String>> {
+            let tokens = self.tokens.lock().await;
+
+            let now = Utc::now().timestamp();
+
+            Ok(tokens
+                .values()
+                .filter(|t| t.account == account && !t.rotated && !t.revoked && t.expiration > now)
+                .map(|t| t.family.clone())
+                .collect())
+        }
+
+        async fn revoke_family(&self, family: &str) -> RepositoryResult<()> {
+            let mut tokens = self.tokens.lock().await;
+
+            tokens
+                .values_mut()
+                .filter(|t| t.family == family)
+                .for_each(|t| t.revoked = true);
+
+            Ok(())
+        }
+
+        async fn revoke_account(&self, account: &str) -> RepositoryResult<()> {
+            let mut tokens = self.tokens.lock().await;
+
+            tokens
+                .values_mut()
+                .filter(|t| t.account == account)
+                .for_each(|t| t.revoked = true);
+
+            Ok(())
+        }
+    }
+}
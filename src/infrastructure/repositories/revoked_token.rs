@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::revoked_token::RevokedToken;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::revoked_token::RevokedTokenRepository;
+use crate::infrastructure::models::revoked_token::SurrealRevokedToken;
+
+pub struct RevokedTokenRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl RevokedTokenRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const REVOKED_TOKEN: &str = "revoked_token";
+
+#[async_trait]
+impl RevokedTokenRepository for RevokedTokenRepositoryImpl {
+    async fn revoke(&self, token: RevokedToken) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "UPSERT type::thing($table, $jti) SET jti = $jti, expires_at = time::from::unix($expires_at)",
+            )
+            .bind(("table", REVOKED_TOKEN))
+            .bind(("jti", token.jti))
+            .bind(("expires_at", token.expiration))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn find_active(&self) -> RepositoryResult<Vec<RevokedToken>> {
+        let tokens: Vec<SurrealRevokedToken> = self
+            .db
+            .query("SELECT jti, expires_at FROM type::table($table) WHERE expires_at > time::now()")
+            .bind(("table", REVOKED_TOKEN))
+            .await?
+            .take(0)?;
+
+        Ok(tokens.into_iter().map(Into::into).collect())
+    }
+
+    async fn prune(&self) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::table($table) WHERE expires_at <= time::now()")
+            .bind(("table", REVOKED_TOKEN))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct RevokedTokenRepositoryImpl {
+        pub tokens: Mutex<Vec<RevokedToken>>,
+    }
+
+    #[async_trait]
+    impl RevokedTokenRepository for RevokedTokenRepositoryImpl {
+        async fn revoke(&self, token: RevokedToken) -> RepositoryResult<()> {
+            let mut tokens = self.tokens.lock().await;
+            tokens.push(token);
+            Ok(())
+        }
+
+        async fn find_active(&self) -> RepositoryResult<Vec<RevokedToken>> {
+            let tokens = self.tokens.lock().await;
+            let now = Utc::now().timestamp();
+
+            Ok(tokens
+                .iter()
+                .filter(|t| t.expiration > now)
+                .cloned()
+                .collect())
+        }
+
+        async fn prune(&self) -> RepositoryResult<()> {
+            let mut tokens = self.tokens.lock().await;
+            let now = Utc::now().timestamp();
+
+            tokens.retain(|t| t.expiration > now);
+
+            Ok(())
+        }
+    }
+}
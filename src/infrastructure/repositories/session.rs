@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::session::{CreateSession, Session};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::session::SessionRepository;
+use crate::infrastructure::models::session::SurrealSession;
+
+pub struct SessionRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl SessionRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const SESSION: &str = "account_session";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn create(&self, session: CreateSession) -> RepositoryResult<Session> {
+        let session: Option<SurrealSession> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    user_agent: $user_agent,
+                    ip: $ip
+                }",
+            )
+            .bind(("table", SESSION))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", session.account))
+            .bind(("user_agent", session.user_agent))
+            .bind(("ip", session.ip))
+            .await?
+            .take(0)?;
+
+        Ok(session.unwrap().into())
+    }
+
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<Session>> {
+        let sessions: Vec<SurrealSession> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE account = type::thing($account_table, $account) AND revoked_at IS NONE ORDER BY last_seen_at DESC")
+            .bind(("table", SESSION))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(sessions.into_iter().map(Into::into).collect())
+    }
+
+    // Written at most once a minute, since it runs on every authenticated request
+    async fn touch(&self, id: &str) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::thing($table, $id) SET last_seen_at = time::now() WHERE revoked_at IS NONE AND last_seen_at < time::now() - 1m")
+            .bind(("table", SESSION))
+            .bind(("id", id.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool> {
+        let session: Option<SurrealSession> = self
+            .db
+            .query("UPDATE type::thing($table, $id) SET revoked_at = time::now() WHERE account = type::thing($account_table, $account) AND revoked_at IS NONE RETURN BEFORE")
+            .bind(("table", SESSION))
+            .bind(("id", id.to_owned()))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(session.is_some())
+    }
+
+    async fn revoke_account(&self, account: &str) -> RepositoryResult<Vec<String>> {
+        let sessions: Vec<SurrealSession> = self
+            .db
+            .query("UPDATE type::table($table) SET revoked_at = time::now() WHERE account = type::thing($account_table, $account) AND revoked_at IS NONE RETURN AFTER")
+            .bind(("table", SESSION))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| Session::from(session).id)
+            .collect())
+    }
+
+    async fn find_revoked_since(&self, since: i64) -> RepositoryResult<Vec<String>> {
+        let sessions: Vec<SurrealSession> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE revoked_at > time::from::unix($since)")
+            .bind(("table", SESSION))
+            .bind(("since", since))
+            .await?
+            .take(0)?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| Session::from(session).id)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct SessionRepositoryImpl {
+        // Paired with the time the session was revoked, if it was
+        pub sessions: Mutex<Vec<(Session, Option<i64>)>>,
+    }
+
+    #[async_trait]
+    impl SessionRepository for SessionRepositoryImpl {
+        async fn create(&self, session: CreateSession) -> RepositoryResult<Session> {
+            let mut sessions = self.sessions.lock().await;
+
+            let now = Utc::now().timestamp();
+
+            let created = Session {
+                id: sessions.len().to_string(),
+                account: session.account,
+                user_agent: session.user_agent,
+                ip: session.ip,
+                created_at: now,
+                last_seen_at: now,
+            };
+
+            sessions.push((created.clone(), None));
+
+            Ok(created)
+        }
+
+        async fn find_by_account(&self, account: &str) -> RepositoryResult<Vec<Session>> {
+            let sessions = self.sessions.lock().await;
+
+            Ok(sessions
+                .iter()
+                .rev()
+                .filter(|(session, revoked_at)| session.account == account && revoked_at.is_none())
+                .map(|(session, _)| session.clone())
+                .collect())
+        }
+
+        async fn touch(&self, id: &str) -> RepositoryResult<()> {
+            let mut sessions = self.sessions.lock().await;
+
+            if let Some((session, None)) = sessions.iter_mut().find(|(session, _)| session.id == id)
+            {
+                session.last_seen_at = Utc::now().timestamp();
+            }
+
+            Ok(())
+        }
+
+        async fn revoke(&self, id: &str, account: &str) -> RepositoryResult<bool> {
+            let mut sessions = self.sessions.lock().await;
+
+            match sessions.iter_mut().find(|(session, revoked_at)| {
+                session.id == id && session.account == account && revoked_at.is_none()
+            }) {
+                Some((_, revoked_at)) => {
+                    *revoked_at = Some(Utc::now().timestamp());
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn revoke_account(&self, account: &str) -> RepositoryResult<Vec<String>> {
+            let mut sessions = self.sessions.lock().await;
+
+            let now = Utc::now().timestamp();
+
+            Ok(sessions
+                .iter_mut()
+                .filter(|(session, revoked_at)| session.account == account && revoked_at.is_none())
+                .map(|(session, revoked_at)| {
+                    *revoked_at = Some(now);
+                    session.id.clone()
+                })
+                .collect())
+        }
+
+        async fn find_revoked_since(&self, since: i64) -> RepositoryResult<Vec<String>> {
+            let sessions = self.sessions.lock().await;
+
+            Ok(sessions
+                .iter()
+                .filter(|(_, revoked_at)| revoked_at.is_some_and(|at| at > since))
+                .map(|(session, _)| session.id.clone())
+                .collect())
+        }
+    }
+}
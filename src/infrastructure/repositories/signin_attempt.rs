@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::signin_attempt::SigninAttempt;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::signin_attempt::SigninAttemptRepository;
+use crate::infrastructure::models::signin_attempt::SurrealSigninAttempt;
+
+pub struct SigninAttemptRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl SigninAttemptRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const SIGNIN_ATTEMPT: &str = "signin_attempt";
+
+#[async_trait]
+impl SigninAttemptRepository for SigninAttemptRepositoryImpl {
+    async fn find(&self, key: &str) -> RepositoryResult<Option<SigninAttempt>> {
+        let attempt: Option<SurrealSigninAttempt> = self
+            .db
+            .query("SELECT * FROM type::thing($table, $key)")
+            .bind(("table", SIGNIN_ATTEMPT))
+            .bind(("key", key.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(attempt.map(Into::into))
+    }
+
+    // Failures older than the window are forgotten instead of adding up forever
+    async fn record_failure(
+        &self,
+        key: &str,
+        window_start: i64,
+    ) -> RepositoryResult<SigninAttempt> {
+        let attempt: Option<SurrealSigninAttempt> = self
+            .db
+            .query(
+                "UPSERT type::thing($table, $key) SET
+                    failures = IF last_failed_at > time::from::unix($window_start) THEN failures + 1 ELSE 1 END,
+                    last_failed_at = time::now()
+                RETURN AFTER",
+            )
+            .bind(("table", SIGNIN_ATTEMPT))
+            .bind(("key", key.to_owned()))
+            .bind(("window_start", window_start))
+            .await?
+            .take(0)?;
+
+        Ok(attempt.unwrap().into())
+    }
+
+    async fn lock(&self, key: &str, until: i64) -> RepositoryResult<()> {
+        self.db
+            .query("UPDATE type::thing($table, $key) SET locked_until = time::from::unix($until)")
+            .bind(("table", SIGNIN_ATTEMPT))
+            .bind(("key", key.to_owned()))
+            .bind(("until", until))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn reset(&self, key: &str) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::thing($table, $key)")
+            .bind(("table", SIGNIN_ATTEMPT))
+            .bind(("key", key.to_owned()))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct SigninAttemptRepositoryImpl {
+        pub attempts: Mutex<HashMap<String, (SigninAttempt, i64)>>,
+    }
+
+    #[async_trait]
+    impl SigninAttemptRepository for SigninAttemptRepositoryImpl {
+        async fn find(&self, key: &str) -> RepositoryResult<Option<SigninAttempt>> {
+            let attempts = self.attempts.lock().await;
+
+            Ok(attempts.get(key).map(|(attempt, _)| attempt.clone()))
+        }
+
+        async fn record_failure(
+            &self,
+            key: &str,
+            window_start: i64,
+        ) -> RepositoryResult<SigninAttempt> {
+            let mut attempts = self.attempts.lock().await;
+
+            let (attempt, last_failed_at) = attempts.entry(key.to_string()).or_default();
+
+            attempt.failures = if *last_failed_at > window_start {
+                attempt.failures + 1
+            } else {
+                1
+            };
+            *last_failed_at = chrono::Utc::now().timestamp();
+
+            Ok(attempt.clone())
+        }
+
+        async fn lock(&self, key: &str, until: i64) -> RepositoryResult<()> {
+            let mut attempts = self.attempts.lock().await;
+
+            if let Some((attempt, _)) = attempts.get_mut(key) {
+                attempt.locked_until = Some(until);
+            }
+
+            Ok(())
+        }
+
+        async fn reset(&self, key: &str) -> RepositoryResult<()> {
+            self.attempts.lock().await.remove(key);
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::totp::{CreateTotpCredential, TotpCredential};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::totp::TotpRepository;
+use crate::infrastructure::models::totp::SurrealTotpCredential;
+
+pub struct TotpRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl TotpRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const TOTP_CREDENTIAL: &str = "totp_credential";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl TotpRepository for TotpRepositoryImpl {
+    // Credentials are keyed by account id, so enrolling again replaces the pending secret
+    async fn save(&self, credential: CreateTotpCredential) -> RepositoryResult<TotpCredential> {
+        let credential: Option<SurrealTotpCredential> = self
+            .db
+            .query(
+                "UPSERT type::thing($table, $account) CONTENT {
+                    account: type::thing($account_table, $account),
+                    secret: $secret,
+                    recovery_codes: $recovery_codes
+                }",
+            )
+            .bind(("table", TOTP_CREDENTIAL))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", credential.account))
+            .bind(("secret", credential.secret))
+            .bind(("recovery_codes", credential.recovery_codes))
+            .await?
+            .take(0)?;
+
+        Ok(credential.unwrap().into())
+    }
+
+    async fn find_by_account(&self, account: &str) -> RepositoryResult<Option<TotpCredential>> {
+        let credential: Option<SurrealTotpCredential> = self
+            .db
+            .query("SELECT * FROM type::thing($table, $account)")
+            .bind(("table", TOTP_CREDENTIAL))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(credential.map(Into::into))
+    }
+
+    async fn confirm(&self, account: &str) -> RepositoryResult<bool> {
+        let credential: Option<SurrealTotpCredential> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $account) SET confirmed_at = time::now() WHERE confirmed_at IS NONE RETURN AFTER",
+            )
+            .bind(("table", TOTP_CREDENTIAL))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(credential.is_some())
+    }
+
+    // Conditional on the stored step, so two requests racing with the same code cannot both pass
+    async fn accept_step(&self, account: &str, step: u64) -> RepositoryResult<bool> {
+        let credential: Option<SurrealTotpCredential> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $account) SET last_step = $step WHERE last_step IS NONE OR last_step < $step RETURN AFTER",
+            )
+            .bind(("table", TOTP_CREDENTIAL))
+            .bind(("account", account.to_owned()))
+            .bind(("step", step))
+            .await?
+            .take(0)?;
+
+        Ok(credential.is_some())
+    }
+
+    async fn consume_recovery_code(
+        &self,
+        account: &str,
+        code_hash: &str,
+    ) -> RepositoryResult<bool> {
+        let credential: Option<SurrealTotpCredential> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $account) SET recovery_codes -= $code_hash WHERE confirmed_at IS NOT NONE AND recovery_codes CONTAINS $code_hash RETURN AFTER",
+            )
+            .bind(("table", TOTP_CREDENTIAL))
+            .bind(("account", account.to_owned()))
+            .bind(("code_hash", code_hash.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(credential.is_some())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct TotpRepositoryImpl {
+        pub credentials: Mutex<Vec<(TotpCredential, Vec<String>)>>,
+    }
+
+    #[async_trait]
+    impl TotpRepository for TotpRepositoryImpl {
+        async fn save(&self, credential: CreateTotpCredential) -> RepositoryResult<TotpCredential> {
+            let mut credentials = self.credentials.lock().await;
+
+            let saved = TotpCredential {
+                account: credential.account,
+                secret: credential.secret,
+                confirmed: false,
+                last_step: None,
+            };
+
+            credentials.retain(|(c, _)| c.account != saved.account);
+            credentials.push((saved.clone(), credential.recovery_codes));
+
+            Ok(saved)
+        }
+
+        async fn find_by_account(&self, account: &str) -> RepositoryResult<Option<TotpCredential>> {
+            let credentials = self.credentials.lock().await;
+
+            Ok(credentials
+                .iter()
+                .find(|(c, _)| c.account == account)
+                .map(|(c, _)| c.clone()))
+        }
+
+        async fn confirm(&self, account: &str) -> RepositoryResult<bool> {
+            let mut credentials = self.credentials.lock().await;
+
+            match credentials
+                .iter_mut()
+                .find(|(c, _)| c.account == account && !c.confirmed)
+            {
+                Some((credential, _)) => {
+                    credential.confirmed = true;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn accept_step(&self, account: &str, step: u64) -> RepositoryResult<bool> {
+            let mut credentials = self.credentials.lock().await;
+
+            match credentials.iter_mut().find(|(c, _)| {
+                c.account == account && c.last_step.is_none_or(|last_step| last_step < step)
+            }) {
+                Some((credential, _)) => {
+                    credential.last_step = Some(step);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn consume_recovery_code(
+            &self,
+            account: &str,
+            code_hash: &str,
+        ) -> RepositoryResult<bool> {
+            let mut credentials = self.credentials.lock().await;
+
+            match credentials
+                .iter_mut()
+                .find(|(c, _)| c.account == account && c.confirmed)
+            {
+                Some((_, codes)) => {
+                    let count = codes.len();
+                    codes.retain(|code| code != code_hash);
+                    Ok(codes.len() < count)
+                }
+                None => Ok(false),
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use surrealdb::Surreal;
+use surrealdb::engine::remote::ws::Client;
+
+use crate::domain::models::webauthn::{
+    Ceremony, CreateWebAuthnChallenge, CreateWebAuthnCredential, WebAuthnChallenge,
+    WebAuthnCredential,
+};
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::webauthn::WebAuthnRepository;
+use crate::infrastructure::models::webauthn::{
+    SurrealWebAuthnChallenge, SurrealWebAuthnCredential,
+};
+
+pub struct WebAuthnRepositoryImpl {
+    db: Arc<Surreal<Client>>,
+}
+
+impl WebAuthnRepositoryImpl {
+    pub fn new(db: Arc<Surreal<Client>>) -> Self {
+        Self { db }
+    }
+}
+
+const WEBAUTHN_CREDENTIAL: &str = "webauthn_credential";
+const WEBAUTHN_CHALLENGE: &str = "webauthn_challenge";
+const ACCOUNT: &str = "account";
+
+#[async_trait]
+impl WebAuthnRepository for WebAuthnRepositoryImpl {
+    async fn create_challenge(&self, challenge: CreateWebAuthnChallenge) -> RepositoryResult<()> {
+        self.db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: IF $account THEN type::thing($account_table, $account) END,
+                    ceremony: $ceremony,
+                    challenge_hash: $challenge_hash,
+                    expires_at: time::from::unix($expires_at)
+                }",
+            )
+            .bind(("table", WEBAUTHN_CHALLENGE))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", challenge.account))
+            .bind(("ceremony", challenge.ceremony))
+            .bind(("challenge_hash", challenge.challenge_hash))
+            .bind(("expires_at", challenge.expiration))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    async fn consume_challenge(
+        &self,
+        challenge_hash: &str,
+        ceremony: Ceremony,
+    ) -> RepositoryResult<Option<WebAuthnChallenge>> {
+        let challenge: Option<SurrealWebAuthnChallenge> = self
+            .db
+            .query(
+                "UPDATE type::table($table) SET used_at = time::now() WHERE challenge_hash = type::string($challenge_hash) AND ceremony = $ceremony AND used_at IS NONE AND expires_at > time::now() RETURN AFTER",
+            )
+            .bind(("table", WEBAUTHN_CHALLENGE))
+            .bind(("challenge_hash", challenge_hash.to_owned()))
+            .bind(("ceremony", ceremony))
+            .await?
+            .take(0)?;
+
+        Ok(challenge.map(Into::into))
+    }
+
+    async fn create_credential(
+        &self,
+        credential: CreateWebAuthnCredential,
+    ) -> RepositoryResult<WebAuthnCredential> {
+        let credential: Option<SurrealWebAuthnCredential> = self
+            .db
+            .query(
+                "CREATE type::table($table) CONTENT {
+                    account: type::thing($account_table, $account),
+                    credential_id: $credential_id,
+                    public_key: $public_key,
+                    sign_count: $sign_count
+                }",
+            )
+            .bind(("table", WEBAUTHN_CREDENTIAL))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", credential.account))
+            .bind(("credential_id", credential.credential_id))
+            .bind(("public_key", credential.public_key))
+            .bind(("sign_count", credential.sign_count))
+            .await?
+            .take(0)?;
+
+        Ok(credential.unwrap().into())
+    }
+
+    async fn find_credential(
+        &self,
+        credential_id: &str,
+    ) -> RepositoryResult<Option<WebAuthnCredential>> {
+        let credential: Option<SurrealWebAuthnCredential> = self
+            .db
+            .query(
+                "SELECT * FROM type::table($table) WHERE credential_id = type::string($credential_id)",
+            )
+            .bind(("table", WEBAUTHN_CREDENTIAL))
+            .bind(("credential_id", credential_id.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(credential.map(Into::into))
+    }
+
+    async fn find_credentials_by_account(
+        &self,
+        account: &str,
+    ) -> RepositoryResult<Vec<WebAuthnCredential>> {
+        let credentials: Vec<SurrealWebAuthnCredential> = self
+            .db
+            .query("SELECT * FROM type::table($table) WHERE account = type::thing($account_table, $account)")
+            .bind(("table", WEBAUTHN_CREDENTIAL))
+            .bind(("account_table", ACCOUNT))
+            .bind(("account", account.to_owned()))
+            .await?
+            .take(0)?;
+
+        Ok(credentials.into_iter().map(Into::into).collect())
+    }
+
+    // The counter must keep increasing unless the authenticator does not implement one
+    async fn update_sign_count(&self, id: &str, sign_count: u32) -> RepositoryResult<bool> {
+        let credential: Option<SurrealWebAuthnCredential> = self
+            .db
+            .query(
+                "UPDATE type::thing($table, $id) SET sign_count = $sign_count, last_used_at = time::now() WHERE sign_count < $sign_count OR (sign_count = 0 AND $sign_count = 0) RETURN AFTER",
+            )
+            .bind(("table", WEBAUTHN_CREDENTIAL))
+            .bind(("id", id.to_owned()))
+            .bind(("sign_count", sign_count))
+            .await?
+            .take(0)?;
+
+        Ok(credential.is_some())
+    }
+
+    // Challenges are created by anonymous requests, nothing else removes the ones never answered
+    async fn prune(&self) -> RepositoryResult<()> {
+        self.db
+            .query("DELETE type::table($table) WHERE expires_at <= time::now()")
+            .bind(("table", WEBAUTHN_CHALLENGE))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct WebAuthnRepositoryImpl {
+        pub challenges: Mutex<Vec<(CreateWebAuthnChallenge, bool)>>,
+        pub credentials: Mutex<Vec<WebAuthnCredential>>,
+    }
+
+    #[async_trait]
+    impl WebAuthnRepository for WebAuthnRepositoryImpl {
+        async fn create_challenge(
+            &self,
+            challenge: CreateWebAuthnChallenge,
+        ) -> RepositoryResult<()> {
+            self.challenges.lock().await.push((challenge, false));
+
+            Ok(())
+        }
+
+        async fn consume_challenge(
+            &self,
+            challenge_hash: &str,
+            ceremony: Ceremony,
+        ) -> RepositoryResult<Option<WebAuthnChallenge>> {
+            let mut challenges = self.challenges.lock().await;
+
+            let now = chrono::Utc::now().timestamp();
+
+            Ok(challenges
+                .iter_mut()
+                .find(|(c, used)| {
+                    c.challenge_hash == challenge_hash
+                        && c.ceremony == ceremony
+                        && c.expiration > now
+                        && !used
+                })
+                .map(|(c, used)| {
+                    *used = true;
+                    WebAuthnChallenge {
+                        account: c.account.clone(),
+                    }
+                }))
+        }
+
+        async fn create_credential(
+            &self,
+            credential: CreateWebAuthnCredential,
+        ) -> RepositoryResult<WebAuthnCredential> {
+            let mut credentials = self.credentials.lock().await;
+
+            let created = WebAuthnCredential {
+                id: credentials.len().to_string(),
+                account: credential.account,
+                credential_id: credential.credential_id,
+                public_key: credential.public_key,
+                sign_count: credential.sign_count,
+            };
+
+            credentials.push(created.clone());
+
+            Ok(created)
+        }
+
+        async fn find_credential(
+            &self,
+            credential_id: &str,
+        ) -> RepositoryResult<Option<WebAuthnCredential>> {
+            let credentials = self.credentials.lock().await;
+
+            Ok(credentials
+                .iter()
+                .find(|c| c.credential_id == credential_id)
+                .cloned())
+        }
+
+        async fn find_credentials_by_account(
+            &self,
+            account: &str,
+        ) -> RepositoryResult<Vec<WebAuthnCredential>> {
+            let credentials = self.credentials.lock().await;
+
+            Ok(credentials
+                .iter()
+                .filter(|c| c.account == account)
+                .cloned()
+                .collect())
+        }
+
+        async fn update_sign_count(&self, id: &str, sign_count: u32) -> RepositoryResult<bool> {
+            let mut credentials = self.credentials.lock().await;
+
+            match credentials.iter_mut().find(|c| {
+                c.id == id && (c.sign_count < sign_count || (c.sign_count == 0 && sign_count == 0))
+            }) {
+                Some(credential) => {
+                    credential.sign_count = sign_count;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn prune(&self) -> RepositoryResult<()> {
+            let now = chrono::Utc::now().timestamp();
+
+            self.challenges
+                .lock()
+                .await
+                .retain(|(c, _)| c.expiration > now);
+
+            Ok(())
+        }
+    }
+}
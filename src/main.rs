@@ -10,12 +10,18 @@ mod services;
 use config::AppConfig;
 use container::Container;
 use infrastructure::databases::surrealdb;
-use services::jsonwebtoken::KeyPair;
+use services::account_deletion::purge_periodically;
+use services::hasher::Argon2Hasher;
+use services::jsonwebtoken::{REVOCATION_REFRESH_SECONDS, reload_periodically};
+use services::keyring::{KeyPair, Keyring, KeyringError};
+use services::password_policy::PasswordPolicy;
+use services::rate_limit::{RATE_LIMIT_PRUNE_SECONDS, prune_periodically};
 
 use actix_web::HttpServer;
 use include_dir::{Dir, include_dir};
 use std::fs;
 use std::sync::Arc;
+use std::time::Duration;
 use surrealdb_migrations::MigrationRunner;
 use thiserror::Error;
 
@@ -37,7 +43,9 @@ pub enum AppError {
     #[error(transparent)]
     OTel(#[from] opentelemetry::OTelError),
     #[error(transparent)]
-    JsonWebToken(#[from] jsonwebtoken::errors::Error),
+    Keyring(#[from] KeyringError),
+    #[error("Invalid password hash configuration: {0}")]
+    PasswordHash(#[from] argon2::Error),
     #[error("{0}: {1}")]
     ReadKey(String, String),
 }
@@ -57,12 +65,45 @@ async fn run() -> Result<(), AppError> {
 
     let provider = opentelemetry::configure(&config.service, &config.logging)?;
 
-    let private_key = read_key(&config.jsonwebtoken.private_keyfile)?;
-    let public_key = read_key(&config.jsonwebtoken.public_keyfile)?;
+    let keys = config
+        .jsonwebtoken
+        .keys
+        .iter()
+        .map(|key| {
+            let private_key = key.private_keyfile.as_deref().map(read_key).transpose()?;
+            let public_key = read_key(&key.public_keyfile)?;
 
-    let keys = KeyPair::from_rsa_pem(private_key, public_key)?;
+            Ok(KeyPair::from_pem(
+                &key.kid,
+                config.jsonwebtoken.algorithm,
+                private_key,
+                public_key,
+            )?)
+        })
+        .collect::<Result<Vec<KeyPair>, AppError>>()?;
 
-    let container = Arc::new(Container::new(conn, keys));
+    let keys = Keyring::new(&config.jsonwebtoken.signing_kid, keys)?;
+
+    let hasher = Argon2Hasher::new(&config.password_hash)?;
+
+    let policy = PasswordPolicy::new(&config.password_policy)?;
+
+    let container = Arc::new(Container::new(conn, keys, hasher, policy, &config));
+
+    actix_web::rt::spawn(reload_periodically(
+        container.jsonwebtoken_service.clone(),
+        Duration::from_secs(REVOCATION_REFRESH_SECONDS),
+    ));
+
+    actix_web::rt::spawn(purge_periodically(
+        container.account_deletion_service.clone(),
+        Duration::from_secs(config.account_deletion.purge_interval),
+    ));
+
+    actix_web::rt::spawn(prune_periodically(
+        container.rate_limit_service.clone(),
+        Duration::from_secs(RATE_LIMIT_PRUNE_SECONDS),
+    ));
 
     HttpServer::new(move || app::create(Arc::clone(&container)))
         .bind(("127.0.0.1", 8080))?
@@ -82,5 +123,5 @@ async fn main() {
 }
 
 fn read_key(keyfile: &str) -> Result<Vec<u8>, AppError> {
-    fs::read(keyfile).map_err(|err| AppError::ReadKey(err.to_string(), keyfile.to_string()))
+    fs::read(keyfile).map_err(|err| AppError::ReadKey(keyfile.to_string(), err.to_string()))
 }
@@ -1,28 +1,51 @@
 use std::sync::Arc;
 
+use crate::config::EmailVerificationConfig;
 use crate::domain::{
     error::{AppError, AppResult},
-    models::account::{Account, CreateAccount, Credentials},
+    models::account::{
+        Account, AccountFilter, AccountSortField, ChangePassword, CreateAccount, Credentials,
+        UpdateAccount,
+    },
+    models::pagination::{Page, PageRequest},
     repositories::account::{AccountRepository, FindByCol},
     services::account::AccountService,
 };
+use crate::services::hasher::Argon2Hasher;
+use crate::services::password_history::PasswordHistory;
+use crate::services::password_policy::PasswordPolicy;
+use crate::services::signin_lockout::{SigninLockout, account_key};
 
-use argon2::{
-    Argon2,
-    password_hash::{
-        PasswordHash, PasswordHasher, PasswordVerifier, Result, SaltString, rand_core::OsRng,
-    },
-};
+use argon2::password_hash::Error::Password;
 
 use async_trait::async_trait;
 
 pub struct AccountServiceImpl {
+    require_verified_email: bool,
+    hasher: Arc<Argon2Hasher>,
+    policy: Arc<PasswordPolicy>,
+    history: Arc<PasswordHistory>,
+    lockout: Arc<SigninLockout>,
     repository: Arc<dyn AccountRepository>,
 }
 
 impl AccountServiceImpl {
-    pub fn new(repository: Arc<dyn AccountRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        config: &EmailVerificationConfig,
+        hasher: Arc<Argon2Hasher>,
+        policy: Arc<PasswordPolicy>,
+        history: Arc<PasswordHistory>,
+        lockout: Arc<SigninLockout>,
+        repository: Arc<dyn AccountRepository>,
+    ) -> Self {
+        Self {
+            require_verified_email: config.required,
+            hasher,
+            policy,
+            history,
+            lockout,
+            repository,
+        }
     }
 
     async fn is_account(&self, email: &str) -> AppResult<bool> {
@@ -44,61 +67,232 @@ impl AccountService for AccountServiceImpl {
             return Err(AppError::Conflict("Account already exists"));
         }
 
-        new_account.password = encrypt_password(&new_account.password)?;
+        self.policy.check(
+            "password",
+            &new_account.password,
+            &[&new_account.name, &new_account.email],
+        )?;
+
+        new_account.password = self.hasher.hash(&new_account.password)?;
 
         Ok(self.repository.signup(new_account).await?)
     }
 
-    async fn signin(&self, credentials: Credentials) -> AppResult<Account> {
+    async fn signin(
+        &self,
+        credentials: Credentials,
+        client_ip: Option<&str>,
+    ) -> AppResult<Account> {
+        let keys = self
+            .lockout
+            .keys(account_key(&credentials.email), client_ip);
+
+        self.lockout.check(&keys).await?;
+
         let account = match self.find_by_email(&credentials.email).await? {
+            Some(account) => match self.hasher.verify(&credentials.password, &account.password) {
+                Ok(()) => Some(account),
+                Err(Password) => None,
+                Err(err) => return Err(err.into()),
+            },
+            None => None,
+        };
+
+        let account = match account {
             Some(account) => account,
-            None => return Err(AppError::Unauthorized()),
+            None => {
+                self.lockout.record_failure(&keys).await?;
+                return Err(AppError::Unauthorized());
+            }
         };
 
-        verify_password(&credentials.password, &account.password)?;
+        // Rejected before any state changes, so a blocked account cannot clear its lockout
+        account.status.ensure_active()?;
+
+        if self.require_verified_email && account.email_verified_at.is_none() {
+            return Err(AppError::Forbidden());
+        }
+
+        self.lockout.reset(&account_key(&credentials.email)).await?;
+
+        // Hashes with outdated parameters are upgraded while the plain password is at hand
+        if self.hasher.needs_rehash(&account.password) {
+            let password = self.hasher.hash(&credentials.password)?;
+
+            self.repository
+                .rehash_password(&account.id, &account.password, password)
+                .await?;
+        }
 
         Ok(account)
     }
-}
 
-pub fn encrypt_password(password: &str) -> Result<String> {
-    let argon2 = Argon2::default();
-    let salt = SaltString::generate(&mut OsRng);
+    async fn find_by_id(&self, id: &str) -> AppResult<Account> {
+        match self.repository.find_by_id(id).await? {
+            Some(account) => Ok(account),
+            None => Err(AppError::Unauthorized()),
+        }
+    }
+
+    // Looks up someone else's account, so a missing one is not an authentication failure
+    async fn find(&self, id: &str) -> AppResult<Account> {
+        match self.repository.find_by_id(id).await? {
+            Some(account) => Ok(account),
+            None => Err(AppError::NotFound("Account not found")),
+        }
+    }
 
-    argon2
-        .hash_password(password.as_bytes(), &salt)
-        .map(|hash| hash.to_string())
-}
+    async fn list(
+        &self,
+        filter: AccountFilter,
+        page: PageRequest<AccountSortField>,
+    ) -> AppResult<Page<Account>> {
+        // Without its anchor the page would silently start over from the first row
+        if let Some(cursor) = &page.cursor {
+            let valid = !cursor.is_empty()
+                && cursor.chars().all(|c| c.is_ascii_alphanumeric())
+                && self.repository.exists(cursor).await?;
+
+            if !valid {
+                return Err(AppError::BadRequest("Invalid cursor"));
+            }
+        }
+
+        Ok(self.repository.find_page(filter, page).await?)
+    }
+
+    async fn update(&self, id: &str, account: UpdateAccount) -> AppResult<Account> {
+        // Deleted accounts keep their email until purged, the unique index would reject it anyway
+        if let Some(email) = &account.email {
+            let current = self.find_by_id(id).await?;
+
+            if *email != current.email && self.is_account(email).await? {
+                return Err(AppError::Conflict("Account already exists"));
+            }
+        }
+
+        match self.repository.update(id, account).await? {
+            Some(account) => Ok(account),
+            None => Err(AppError::Unauthorized()),
+        }
+    }
+
+    async fn delete(&self, id: &str) -> AppResult<()> {
+        if !self.repository.delete(id).await? {
+            return Err(AppError::Unauthorized());
+        }
 
-pub fn verify_password(password: &str, hash: &str) -> Result<()> {
-    let argon2 = Argon2::default();
-    let hash = PasswordHash::new(hash);
+        Ok(())
+    }
+
+    async fn change_password(&self, id: &str, passwords: ChangePassword) -> AppResult<Account> {
+        let account = self.find_by_id(id).await?;
+
+        // Shares the signin counter, otherwise a stolen session could guess the password here
+        let keys = self.lockout.keys(account_key(&account.email), None);
+
+        self.lockout.check(&keys).await?;
+
+        match self
+            .hasher
+            .verify(&passwords.current_password, &account.password)
+        {
+            Ok(()) => {}
+            Err(Password) => {
+                self.lockout.record_failure(&keys).await?;
+                return Err(AppError::Forbidden());
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        self.lockout.reset(&account_key(&account.email)).await?;
+
+        self.policy.check(
+            "new_password",
+            &passwords.new_password,
+            &[&account.name, &account.email],
+        )?;
+
+        self.history
+            .check("new_password", &account, &passwords.new_password)
+            .await?;
+        self.history.record(&account).await?;
 
-    argon2.verify_password(password.as_bytes(), &hash?)
+        let password = self.hasher.hash(&passwords.new_password)?;
+
+        match self.repository.update_password(id, password).await? {
+            Some(account) => Ok(account),
+            None => Err(AppError::Unauthorized()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use tokio::sync::Mutex;
 
     use super::*;
+    use crate::config::PasswordHashConfig;
+    use crate::domain::models::account::{AccountStatus, Role};
     use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::signin_attempt::mock::SigninAttemptRepositoryImpl;
+    use crate::tests::utils::config::{password_hash_config, signin_lockout_config};
+    use crate::tests::utils::crypto::{password_hasher, password_history, password_policy};
     use rstest::*;
 
     #[fixture]
-    fn service() -> AccountServiceImpl {
+    fn signin_attempt_repository() -> Arc<SigninAttemptRepositoryImpl> {
+        Arc::new(SigninAttemptRepositoryImpl {
+            attempts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    #[fixture]
+    fn service(
+        #[default(false)] require_verified_email: bool,
+        signin_attempt_repository: Arc<SigninAttemptRepositoryImpl>,
+    ) -> AccountServiceImpl {
         let repo = Arc::new(AccountRepositoryImpl {
             accounts: Mutex::new(
                 [Account {
                     id: "1".to_string(),
                     name: "Test".to_string(),
                     email: "test_account@spacecraft.com".to_string(),
-                    password: encrypt_password("p4ssw0rd").unwrap(),
+                    password: password_hasher().hash("p4ssw0rd").unwrap(),
+                    roles: vec![Role::User],
+                    permissions: vec![],
+                    password_changed_at: None,
+                    email_verified_at: None,
+                    status: AccountStatus::Active,
+                    deleted_at: None,
+                    created_at: 0,
                 }]
                 .to_vec(),
             ),
         });
-        AccountServiceImpl::new(repo.clone())
+        AccountServiceImpl::new(
+            &EmailVerificationConfig {
+                required: require_verified_email,
+                ..Default::default()
+            },
+            Arc::new(password_hasher()),
+            Arc::new(password_policy()),
+            Arc::new(password_history(3)),
+            Arc::new(SigninLockout::new(
+                &signin_lockout_config(),
+                signin_attempt_repository,
+            )),
+            repo.clone(),
+        )
+    }
+
+    fn credentials(email: &str, password: &str) -> Credentials {
+        Credentials {
+            email: email.to_string(),
+            password: password.to_string(),
+        }
     }
 
     #[rstest]
@@ -115,7 +309,7 @@ mod tests {
         let account = result.unwrap();
 
         assert_eq!(account.email, "new_account@spacecraft.com");
-        assert!(verify_password("p4ssw0rd", &account.password).is_ok());
+        assert!(service.hasher.verify("p4ssw0rd", &account.password).is_ok());
     }
 
     #[rstest]
@@ -139,25 +333,606 @@ mod tests {
     #[tokio::test]
     async fn test_signin_success(service: AccountServiceImpl) {
         let result = service
-            .signin(Credentials {
-                email: "test_account@spacecraft.com".to_string(),
-                password: "p4ssw0rd".to_string(),
-            })
+            .signin(
+                Credentials {
+                    email: "test_account@spacecraft.com".to_string(),
+                    password: "p4ssw0rd".to_string(),
+                },
+                None,
+            )
             .await;
 
         assert_eq!(result.unwrap().email, "test_account@spacecraft.com");
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_rehashes_outdated_password(service: AccountServiceImpl) {
+        let weak = Argon2Hasher::new(&PasswordHashConfig {
+            memory_cost: 8192,
+            time_cost: 1,
+            ..password_hash_config()
+        })
+        .unwrap();
+
+        let current = service.find_by_id("1").await.unwrap().password;
+        let outdated = weak.hash("p4ssw0rd").unwrap();
+
+        service
+            .repository
+            .rehash_password("1", &current, outdated.clone())
+            .await
+            .unwrap();
+
+        service
+            .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+            .await
+            .unwrap();
+
+        let account = service.find_by_id("1").await.unwrap();
+
+        assert_ne!(account.password, outdated);
+        assert!(!service.hasher.needs_rehash(&account.password));
+        assert!(service.hasher.verify("p4ssw0rd", &account.password).is_ok());
+        assert!(account.password_changed_at.is_none());
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_signin_wrong_password(service: AccountServiceImpl) {
         let result = service
-            .signin(Credentials {
-                email: "test_account@spacecraft.com".to_string(),
-                password: "wrongpassword".to_string(),
+            .signin(
+                Credentials {
+                    email: "test_account@spacecraft.com".to_string(),
+                    password: "wrongpassword".to_string(),
+                },
+                None,
+            )
+            .await;
+
+        assert_eq!(result.unwrap_err(), AppError::Unauthorized());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_find_missing(service: AccountServiceImpl) {
+        let result = service.find("missing").await;
+
+        assert_eq!(result.unwrap_err(), AppError::NotFound("Account not found"));
+    }
+
+    #[rstest]
+    #[case::all(AccountFilter::default(), 1)]
+    #[case::email(AccountFilter { email: Some("SPACECRAFT".to_string()), ..Default::default() }, 1)]
+    #[case::name(AccountFilter { name: Some("other".to_string()), ..Default::default() }, 0)]
+    #[case::created(AccountFilter { created_after: Some(1), ..Default::default() }, 0)]
+    #[tokio::test]
+    async fn test_list(
+        service: AccountServiceImpl,
+        #[case] filter: AccountFilter,
+        #[case] count: usize,
+    ) {
+        let page = service
+            .list(
+                filter,
+                PageRequest {
+                    cursor: None,
+                    limit: 20,
+                    sort: Default::default(),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.items.len(), count);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_include_deleted(service: AccountServiceImpl) {
+        service.delete("1").await.unwrap();
+
+        let list = |include_deleted| {
+            service.list(
+                AccountFilter {
+                    include_deleted,
+                    ..Default::default()
+                },
+                PageRequest {
+                    cursor: None,
+                    limit: 20,
+                    sort: Default::default(),
+                },
+            )
+        };
+
+        assert!(list(false).await.unwrap().items.is_empty());
+
+        let page = list(true).await.unwrap();
+
+        assert_eq!(page.items.len(), 1);
+        assert!(page.items[0].deleted_at.is_some());
+    }
+
+    #[rstest]
+    #[case::unknown("missing")]
+    #[case::malformed("1) OR (true")]
+    #[case::empty("")]
+    #[tokio::test]
+    async fn test_list_invalid_cursor(service: AccountServiceImpl, #[case] cursor: &str) {
+        let result = service
+            .list(
+                AccountFilter::default(),
+                PageRequest {
+                    cursor: Some(cursor.to_string()),
+                    limit: 20,
+                    sort: Default::default(),
+                },
+            )
+            .await;
+
+        assert_eq!(result.unwrap_err(), AppError::BadRequest("Invalid cursor"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_unverified_email(#[with(true)] service: AccountServiceImpl) {
+        let credentials = Credentials {
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+        };
+
+        assert_eq!(
+            service.signin(credentials.clone(), None).await.unwrap_err(),
+            AppError::Forbidden()
+        );
+
+        service.repository.verify_email("1").await.unwrap();
+
+        assert!(service.signin(credentials, None).await.is_ok());
+    }
+
+    #[rstest]
+    #[case::suspended(AccountStatus::Suspended, AppError::Suspended())]
+    #[case::deactivated(AccountStatus::Deactivated, AppError::Deactivated())]
+    #[tokio::test]
+    async fn test_signin_inactive(
+        service: AccountServiceImpl,
+        #[case] status: AccountStatus,
+        #[case] error: AppError,
+    ) {
+        service.repository.update_status("1", status).await.unwrap();
+
+        assert_eq!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .unwrap_err(),
+            error
+        );
+
+        // Wrong passwords are still reported as such, so the status does not leak
+        assert_eq!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_suspended_keeps_failures(service: AccountServiceImpl) {
+        for _ in 0..2 {
+            let _ = service
+                .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                .await;
+        }
+
+        service
+            .repository
+            .update_status("1", AccountStatus::Suspended)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+
+        let _ = service
+            .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+            .await;
+
+        assert_eq!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_account_lockout(service: AccountServiceImpl) {
+        for _ in 0..3 {
+            assert_eq!(
+                service
+                    .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                    .await
+                    .unwrap_err(),
+                AppError::Unauthorized()
+            );
+        }
+
+        let err = service
+            .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, 429);
+        assert!(
+            err.retry_after
+                .is_some_and(|seconds| seconds > 0 && seconds <= 60)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_ip_lockout(service: AccountServiceImpl) {
+        for i in 0..5 {
+            let email = format!("unknown_{i}@spacecraft.com");
+
+            assert!(
+                service
+                    .signin(credentials(&email, "wrong"), Some("10.0.0.1"))
+                    .await
+                    .is_err()
+            );
+        }
+
+        let credentials = credentials("test_account@spacecraft.com", "p4ssw0rd");
+
+        assert_eq!(
+            service
+                .signin(credentials.clone(), Some("10.0.0.1"))
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+        assert!(service.signin(credentials, Some("10.0.0.2")).await.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_success_resets_failures(service: AccountServiceImpl) {
+        for _ in 0..2 {
+            let _ = service
+                .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                .await;
+        }
+
+        assert!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .is_ok()
+        );
+
+        for _ in 0..2 {
+            let _ = service
+                .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                .await;
+        }
+
+        assert!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_signin_lockout_backoff(
+        signin_attempt_repository: Arc<SigninAttemptRepositoryImpl>,
+    ) {
+        let service = service(false, signin_attempt_repository.clone());
+
+        for _ in 0..3 {
+            let _ = service
+                .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+                .await;
+        }
+
+        // Expire the first lockout so the next failure is recorded
+        signin_attempt_repository
+            .lock(
+                "account:test_account@spacecraft.com",
+                Utc::now().timestamp(),
+            )
+            .await
+            .unwrap();
+
+        let _ = service
+            .signin(credentials("test_account@spacecraft.com", "wrong"), None)
+            .await;
+
+        let err = service
+            .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.retry_after
+                .is_some_and(|seconds| seconds > 60 && seconds <= 120)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_find_by_id(service: AccountServiceImpl) {
+        let account = service.find_by_id("1").await.unwrap();
+
+        assert_eq!(account.email, "test_account@spacecraft.com");
+        assert_eq!(account.roles, vec![Role::User]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_find_by_id_missing(service: AccountServiceImpl) {
+        let result = service.find_by_id("missing").await;
+
+        assert_eq!(result.unwrap_err(), AppError::Unauthorized());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_update_success(service: AccountServiceImpl) {
+        let account = service
+            .update(
+                "1",
+                UpdateAccount {
+                    name: Some("Updated".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(account.name, "Updated");
+        assert_eq!(account.email, "test_account@spacecraft.com");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_update_own_email(service: AccountServiceImpl) {
+        let result = service
+            .update(
+                "1",
+                UpdateAccount {
+                    email: Some("test_account@spacecraft.com".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[case::same_email("test_account@spacecraft.com", true)]
+    #[case::new_email("new_account@spacecraft.com", false)]
+    #[tokio::test]
+    async fn test_update_email_verification(
+        service: AccountServiceImpl,
+        #[case] email: &str,
+        #[case] verified: bool,
+    ) {
+        service.repository.verify_email("1").await.unwrap();
+
+        let account = service
+            .update(
+                "1",
+                UpdateAccount {
+                    email: Some(email.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(account.email_verified_at.is_some(), verified);
+    }
+
+    #[rstest]
+    #[case::live(false)]
+    #[case::deleted(true)]
+    #[tokio::test]
+    async fn test_update_email_conflict(service: AccountServiceImpl, #[case] deleted: bool) {
+        let other = service
+            .signup(CreateAccount {
+                name: "Other".to_string(),
+                email: "other_account@spacecraft.com".to_string(),
+                password: "p4ssw0rd".to_string(),
             })
+            .await
+            .unwrap();
+
+        if deleted {
+            service.delete(&other.id).await.unwrap();
+        }
+
+        let result = service
+            .update(
+                "1",
+                UpdateAccount {
+                    email: Some("other_account@spacecraft.com".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            AppError::Conflict("Account already exists")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_delete(service: AccountServiceImpl) {
+        service.delete("1").await.unwrap();
+
+        assert_eq!(
+            service.find_by_id("1").await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert_eq!(
+            service.delete("1").await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_change_password(service: AccountServiceImpl) {
+        let account = service
+            .change_password(
+                "1",
+                ChangePassword {
+                    current_password: "p4ssw0rd".to_string(),
+                    new_password: "n3wP4ssw0rd!".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(account.password_changed_at.is_some());
+        assert!(
+            service
+                .hasher
+                .verify("n3wP4ssw0rd!", &account.password)
+                .is_ok()
+        );
+
+        let result = service
+            .signin(
+                Credentials {
+                    email: "test_account@spacecraft.com".to_string(),
+                    password: "p4ssw0rd".to_string(),
+                },
+                None,
+            )
             .await;
 
         assert_eq!(result.unwrap_err(), AppError::Unauthorized());
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_change_password_wrong_current(service: AccountServiceImpl) {
+        let result = service
+            .change_password(
+                "1",
+                ChangePassword {
+                    current_password: "wrongpassword".to_string(),
+                    new_password: "n3wP4ssw0rd!".to_string(),
+                },
+            )
+            .await;
+
+        assert_eq!(result.unwrap_err(), AppError::Forbidden());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_change_password_lockout(service: AccountServiceImpl) {
+        let passwords = |current_password: &str| ChangePassword {
+            current_password: current_password.to_string(),
+            new_password: "n3wP4ssw0rd!".to_string(),
+        };
+
+        for _ in 0..3 {
+            assert_eq!(
+                service
+                    .change_password("1", passwords("wrongpassword"))
+                    .await
+                    .unwrap_err(),
+                AppError::Forbidden()
+            );
+        }
+
+        assert_eq!(
+            service
+                .change_password("1", passwords("p4ssw0rd"))
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+        assert_eq!(
+            service
+                .signin(credentials("test_account@spacecraft.com", "p4ssw0rd"), None)
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_change_password_personal_info(service: AccountServiceImpl) {
+        let err = service
+            .change_password(
+                "1",
+                ChangePassword {
+                    current_password: "p4ssw0rd".to_string(),
+                    new_password: "Test!Acc0unt#9".to_string(),
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("new_password"));
+
+        let account = service.find_by_id("1").await.unwrap();
+
+        assert!(account.password_changed_at.is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_change_password_reuse(service: AccountServiceImpl) {
+        let change = |current: &str, new: &str| ChangePassword {
+            current_password: current.to_string(),
+            new_password: new.to_string(),
+        };
+
+        service
+            .change_password("1", change("p4ssw0rd", "n3wP4ssw0rd!"))
+            .await
+            .unwrap();
+
+        for reused in ["n3wP4ssw0rd!", "p4ssw0rd"] {
+            let err = service
+                .change_password("1", change("n3wP4ssw0rd!", reused))
+                .await
+                .unwrap_err();
+
+            assert_eq!(err.code, 422);
+            assert!(err.message.contains("Password has been used recently"));
+        }
+    }
 }
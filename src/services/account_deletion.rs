@@ -0,0 +1,288 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::time;
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::config::AccountDeletionConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::Account,
+    repositories::account::AccountRepository,
+    repositories::oauth::OAuthRepository,
+    repositories::oidc::OidcRepository,
+    repositories::webauthn::WebAuthnRepository,
+    services::account_deletion::AccountDeletionService,
+};
+
+pub struct AccountDeletionServiceImpl {
+    retention: i64,
+    repository: Arc<dyn AccountRepository>,
+    webauthn_repository: Arc<dyn WebAuthnRepository>,
+    oidc_repository: Arc<dyn OidcRepository>,
+    oauth_repository: Arc<dyn OAuthRepository>,
+}
+
+impl AccountDeletionServiceImpl {
+    pub fn new(
+        config: &AccountDeletionConfig,
+        repository: Arc<dyn AccountRepository>,
+        webauthn_repository: Arc<dyn WebAuthnRepository>,
+        oidc_repository: Arc<dyn OidcRepository>,
+        oauth_repository: Arc<dyn OAuthRepository>,
+    ) -> Self {
+        Self {
+            retention: config.retention,
+            repository,
+            webauthn_repository,
+            oidc_repository,
+            oauth_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl AccountDeletionService for AccountDeletionServiceImpl {
+    // Sessions revoked on deletion stay revoked, the owner signs in again once restored
+    async fn restore(&self, id: &str) -> AppResult<Account> {
+        self.repository
+            .restore(id, Utc::now().timestamp() - self.retention)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found"))
+    }
+
+    // Also sweeps the expired rows left behind by ceremonies, sign-ins and authorizations never
+    // completed, which nothing else removes
+    async fn purge(&self) -> AppResult<usize> {
+        let purged = self
+            .repository
+            .purge(Utc::now().timestamp() - self.retention)
+            .await?;
+
+        self.webauthn_repository.prune().await?;
+        self.oidc_repository.prune().await?;
+        self.oauth_repository.prune().await?;
+
+        Ok(purged)
+    }
+}
+
+// Runs for the lifetime of the server, a failed purge is logged and retried on the next tick
+pub async fn purge_periodically(service: Arc<dyn AccountDeletionService>, period: Duration) {
+    let mut interval = time::interval(period);
+
+    loop {
+        interval.tick().await;
+
+        match service.purge().await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!(purged, "deleted accounts purged"),
+            Err(err) => tracing::warn!(error = ?err, "account purge failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::domain::models::oauth::CreateAuthorizationCode;
+    use crate::domain::models::oidc::CreateOidcState;
+    use crate::domain::models::webauthn::{Ceremony, CreateWebAuthnChallenge};
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::oauth::mock::OAuthRepositoryImpl;
+    use crate::infrastructure::repositories::oidc::mock::OidcRepositoryImpl;
+    use crate::infrastructure::repositories::webauthn::mock::WebAuthnRepositoryImpl;
+    use rstest::*;
+
+    const RETENTION: i64 = 3600;
+
+    struct Context {
+        service: AccountDeletionServiceImpl,
+        repository: Arc<AccountRepositoryImpl>,
+        webauthn_repository: Arc<WebAuthnRepositoryImpl>,
+        oidc_repository: Arc<OidcRepositoryImpl>,
+        oauth_repository: Arc<OAuthRepositoryImpl>,
+    }
+
+    fn challenge(challenge_hash: &str, expiration: i64) -> (CreateWebAuthnChallenge, bool) {
+        (
+            CreateWebAuthnChallenge {
+                account: None,
+                ceremony: Ceremony::Authentication,
+                challenge_hash: challenge_hash.to_string(),
+                expiration,
+            },
+            false,
+        )
+    }
+
+    fn state(state_hash: &str, expiration: i64) -> (CreateOidcState, bool) {
+        (
+            CreateOidcState {
+                account: None,
+                provider: "google".to_string(),
+                state_hash: state_hash.to_string(),
+                nonce: "nonce".to_string(),
+                code_verifier: "verifier".to_string(),
+                expiration,
+            },
+            true,
+        )
+    }
+
+    fn code(code_hash: &str, expiration: i64) -> (CreateAuthorizationCode, bool) {
+        (
+            CreateAuthorizationCode {
+                client: "client".to_string(),
+                account: "active".to_string(),
+                code_hash: code_hash.to_string(),
+                redirect_uri: "https://client.spacecraft.com/callback".to_string(),
+                scopes: vec![],
+                code_challenge: "challenge".to_string(),
+                expiration,
+            },
+            false,
+        )
+    }
+
+    fn account(id: &str, deleted_at: Option<i64>) -> Account {
+        Account {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            email: format!("{id}@spacecraft.com"),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at,
+            created_at: 0,
+        }
+    }
+
+    #[fixture]
+    fn context() -> Context {
+        let now = Utc::now().timestamp();
+
+        let repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![
+                account("active", None),
+                account("recent", Some(now - 60)),
+                account("expired", Some(now - RETENTION - 60)),
+            ]),
+        });
+
+        let webauthn_repository = Arc::new(WebAuthnRepositoryImpl {
+            challenges: Mutex::new(vec![
+                challenge("live", now + 60),
+                challenge("expired", now - 60),
+            ]),
+            credentials: Mutex::new(vec![]),
+        });
+
+        let oidc_repository = Arc::new(OidcRepositoryImpl {
+            states: Mutex::new(vec![state("live", now + 60), state("expired", now - 60)]),
+            identities: Mutex::new(vec![]),
+        });
+
+        let oauth_repository = Arc::new(OAuthRepositoryImpl {
+            clients: Mutex::new(vec![]),
+            codes: Mutex::new(vec![code("live", now + 60), code("expired", now - 60)]),
+            refresh_tokens: Mutex::new(vec![]),
+        });
+
+        Context {
+            service: AccountDeletionServiceImpl::new(
+                &AccountDeletionConfig {
+                    retention: RETENTION,
+                    purge_interval: 60,
+                },
+                repository.clone(),
+                webauthn_repository.clone(),
+                oidc_repository.clone(),
+                oauth_repository.clone(),
+            ),
+            repository,
+            webauthn_repository,
+            oidc_repository,
+            oauth_repository,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_restore(context: Context) {
+        let restored = context.service.restore("recent").await.unwrap();
+
+        assert_eq!(restored.deleted_at, None);
+        assert!(
+            context
+                .repository
+                .find_by_id("recent")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[rstest]
+    #[case::active("active")]
+    #[case::expired("expired")]
+    #[case::missing("missing")]
+    #[tokio::test]
+    async fn test_restore_not_found(context: Context, #[case] id: &str) {
+        assert_eq!(
+            context.service.restore(id).await.unwrap_err(),
+            AppError::NotFound("Account not found")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_purge(context: Context) {
+        assert_eq!(context.service.purge().await.unwrap(), 1);
+        assert_eq!(context.service.purge().await.unwrap(), 0);
+
+        let accounts = context.repository.accounts.lock().await;
+
+        assert_eq!(
+            accounts.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(),
+            vec!["active", "recent"]
+        );
+
+        let challenges = context.webauthn_repository.challenges.lock().await;
+
+        assert_eq!(
+            challenges
+                .iter()
+                .map(|(c, _)| c.challenge_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["live"]
+        );
+
+        let states = context.oidc_repository.states.lock().await;
+
+        assert_eq!(
+            states
+                .iter()
+                .map(|(s, _)| s.state_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["live"]
+        );
+
+        let codes = context.oauth_repository.codes.lock().await;
+
+        assert_eq!(
+            codes
+                .iter()
+                .map(|(c, _)| c.code_hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["live"]
+        );
+    }
+}
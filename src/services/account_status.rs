@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::{Account, AccountStatus},
+    models::account_audit::{AccountAuditAction, CreateAccountAudit},
+    repositories::account::AccountRepository,
+    repositories::account_audit::AccountAuditRepository,
+    services::account_status::AccountStatusService,
+    services::jsonwebtoken::JsonWebTokenService,
+};
+
+pub struct AccountStatusServiceImpl {
+    repository: Arc<dyn AccountRepository>,
+    audit_repository: Arc<dyn AccountAuditRepository>,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+}
+
+impl AccountStatusServiceImpl {
+    pub fn new(
+        repository: Arc<dyn AccountRepository>,
+        audit_repository: Arc<dyn AccountAuditRepository>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            jsonwebtoken_service,
+        }
+    }
+
+    async fn change_status(
+        &self,
+        id: &str,
+        actor: &str,
+        status: AccountStatus,
+        action: AccountAuditAction,
+        reason: Option<String>,
+    ) -> AppResult<Account> {
+        let account = self
+            .audit_repository
+            .change_status(
+                status,
+                CreateAccountAudit {
+                    account: id.to_owned(),
+                    actor: actor.to_owned(),
+                    action,
+                    reason,
+                },
+            )
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found"))?;
+
+        Ok(account)
+    }
+}
+
+#[async_trait]
+impl AccountStatusService for AccountStatusServiceImpl {
+    // Sessions are left alone, tokens are refused while the account is suspended and work again
+    // once it is reactivated. Only this replica's in-memory token cache learns of the suspension
+    // right away, other replicas keep accepting the account's tokens until their next reload, up
+    // to REVOCATION_REFRESH_SECONDS (30s) later
+    async fn suspend(&self, id: &str, actor: &str, reason: String) -> AppResult<Account> {
+        if id == actor {
+            return Err(AppError::BadRequest("Cannot suspend your own account"));
+        }
+
+        let account = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found"))?;
+
+        if account.status == AccountStatus::Suspended {
+            return Err(AppError::Conflict("Account is already suspended"));
+        }
+
+        let account = self
+            .change_status(
+                id,
+                actor,
+                AccountStatus::Suspended,
+                AccountAuditAction::Suspend,
+                Some(reason),
+            )
+            .await?;
+
+        self.jsonwebtoken_service.suspend(id);
+
+        Ok(account)
+    }
+
+    async fn reactivate(&self, id: &str, actor: &str) -> AppResult<Account> {
+        let account = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found"))?;
+
+        if account.status == AccountStatus::Active {
+            return Err(AppError::Conflict("Account is already active"));
+        }
+
+        let account = self
+            .change_status(
+                id,
+                actor,
+                AccountStatus::Active,
+                AccountAuditAction::Reactivate,
+                None,
+            )
+            .await?;
+
+        self.jsonwebtoken_service.reactivate(id);
+
+        Ok(account)
+    }
+
+    // Only the owner can deactivate, the caller revokes the sessions it signed in with. Signing in
+    // is refused until an administrator reactivates the account
+    async fn deactivate(&self, id: &str) -> AppResult<Account> {
+        let account = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found"))?;
+
+        if account.status != AccountStatus::Active {
+            return Err(AppError::Conflict("Account is not active"));
+        }
+
+        self.change_status(
+            id,
+            id,
+            AccountStatus::Deactivated,
+            AccountAuditAction::Deactivate,
+            None,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::Role;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::account_audit::mock::AccountAuditRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
+    use rstest::*;
+
+    struct Context {
+        service: AccountStatusServiceImpl,
+        audit_repository: Arc<AccountAuditRepositoryImpl>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    }
+
+    fn account(id: &str) -> Account {
+        Account {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            email: format!("{id}@spacecraft.com"),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    #[fixture]
+    fn context() -> Context {
+        let repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![account("1"), account("admin")]),
+        });
+
+        let audit_repository = Arc::new(AccountAuditRepositoryImpl {
+            accounts: repository.clone(),
+            audits: Mutex::new(vec![]),
+        });
+
+        let jsonwebtoken_service: Arc<dyn JsonWebTokenService> =
+            Arc::new(JsonWebTokenServiceImpl::new(
+                generate_keyring(),
+                &jsonwebtoken_config(),
+                Arc::new(RevokedTokenRepositoryImpl {
+                    tokens: Mutex::new(vec![]),
+                }),
+                repository.clone(),
+                Arc::new(SessionRepositoryImpl {
+                    sessions: Mutex::new(vec![]),
+                }),
+            ));
+
+        Context {
+            service: AccountStatusServiceImpl::new(
+                repository,
+                audit_repository.clone(),
+                jsonwebtoken_service.clone(),
+            ),
+            audit_repository,
+            jsonwebtoken_service,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_suspend_and_reactivate(context: Context) {
+        let access_token = context
+            .jsonwebtoken_service
+            .generate_token(&account("1"), "session")
+            .unwrap();
+
+        let suspended = context
+            .service
+            .suspend("1", "admin", "Spam".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(suspended.status, AccountStatus::Suspended);
+        assert_eq!(
+            context
+                .jsonwebtoken_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+
+        let reactivated = context.service.reactivate("1", "admin").await.unwrap();
+
+        assert_eq!(reactivated.status, AccountStatus::Active);
+        assert!(
+            context
+                .jsonwebtoken_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+
+        let audits = context.audit_repository.audits.lock().await;
+
+        assert_eq!(
+            audits
+                .iter()
+                .map(|a| (
+                    a.account.as_str(),
+                    a.actor.as_str(),
+                    a.action,
+                    a.reason.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("1", "admin", AccountAuditAction::Suspend, Some("Spam")),
+                ("1", "admin", AccountAuditAction::Reactivate, None),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::missing("missing", "admin", AppError::NotFound("Account not found"))]
+    #[case::own_account(
+        "admin",
+        "admin",
+        AppError::BadRequest("Cannot suspend your own account")
+    )]
+    #[tokio::test]
+    async fn test_suspend_rejected(
+        context: Context,
+        #[case] id: &str,
+        #[case] actor: &str,
+        #[case] error: AppError,
+    ) {
+        assert_eq!(
+            context
+                .service
+                .suspend(id, actor, "Spam".to_string())
+                .await
+                .unwrap_err(),
+            error
+        );
+        assert!(context.audit_repository.audits.lock().await.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_status_conflicts(context: Context) {
+        assert_eq!(
+            context.service.reactivate("1", "admin").await.unwrap_err(),
+            AppError::Conflict("Account is already active")
+        );
+
+        context
+            .service
+            .suspend("1", "admin", "Spam".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            context
+                .service
+                .suspend("1", "admin", "Spam".to_string())
+                .await
+                .unwrap_err(),
+            AppError::Conflict("Account is already suspended")
+        );
+        assert_eq!(context.audit_repository.audits.lock().await.len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_deactivate(context: Context) {
+        let deactivated = context.service.deactivate("1").await.unwrap();
+
+        assert_eq!(deactivated.status, AccountStatus::Deactivated);
+        assert_eq!(
+            context.service.deactivate("1").await.unwrap_err(),
+            AppError::Conflict("Account is not active")
+        );
+
+        let reactivated = context.service.reactivate("1", "admin").await.unwrap();
+
+        assert_eq!(reactivated.status, AccountStatus::Active);
+
+        let audits = context.audit_repository.audits.lock().await;
+
+        assert_eq!(
+            audits
+                .iter()
+                .map(|a| (a.account.as_str(), a.actor.as_str(), a.action))
+                .collect::<Vec<_>>(),
+            vec![
+                ("1", "1", AccountAuditAction::Deactivate),
+                ("1", "admin", AccountAuditAction::Reactivate),
+            ]
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::config::JsonWebTokenConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::{Account, Role},
+    models::api_key::{API_KEY_PREFIX, ApiKey, CreateApiKey, IssuedApiKey, NewApiKey},
+    models::jsonwebtoken::Claims,
+    repositories::account::AccountRepository,
+    repositories::api_key::ApiKeyRepository,
+    services::api_key::ApiKeyService,
+};
+use crate::services::crypto::{hash_token, random_token};
+
+// Characters kept in clear so keys can be told apart when listed
+const DISPLAY_PREFIX: usize = API_KEY_PREFIX.len() + 6;
+
+// Seconds between two writes of `last_used_at` for the same key
+const TOUCH_INTERVAL: i64 = 60;
+
+pub struct ApiKeyServiceImpl {
+    issuer: String,
+    audience: String,
+    repository: Arc<dyn ApiKeyRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+}
+
+impl ApiKeyServiceImpl {
+    pub fn new(
+        config: &JsonWebTokenConfig,
+        repository: Arc<dyn ApiKeyRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+    ) -> Self {
+        Self {
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            repository,
+            account_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyService for ApiKeyServiceImpl {
+    async fn create(&self, account: &Account, api_key: NewApiKey) -> AppResult<IssuedApiKey> {
+        // A key can never grant more than its owner currently holds
+        if let Some(scopes) = &api_key.scopes {
+            let permissions = account.effective_permissions();
+
+            if !scopes.iter().all(|scope| permissions.contains(scope)) {
+                return Err(AppError::Forbidden());
+            }
+        }
+
+        if api_key
+            .expiration
+            .is_some_and(|expiration| expiration <= Utc::now().timestamp())
+        {
+            return Err(AppError::BadRequest("Expiration must be in the future"));
+        }
+
+        let key = format!("{API_KEY_PREFIX}{}", random_token());
+
+        let created = self
+            .repository
+            .create(CreateApiKey {
+                account: account.id.clone(),
+                name: api_key.name,
+                key_hash: hash_token(&key),
+                prefix: key[..DISPLAY_PREFIX].to_owned(),
+                scopes: api_key.scopes,
+                expiration: api_key.expiration,
+            })
+            .await?;
+
+        Ok(IssuedApiKey {
+            key,
+            api_key: created,
+        })
+    }
+
+    async fn list(&self, account: &str) -> AppResult<Vec<ApiKey>> {
+        Ok(self.repository.find_by_account(account).await?)
+    }
+
+    async fn revoke(&self, account: &str, id: &str) -> AppResult<()> {
+        if !self.repository.revoke(id, account).await? {
+            return Err(AppError::NotFound("API key not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn authenticate(&self, key: &str) -> AppResult<Claims> {
+        let api_key = self
+            .repository
+            .find_by_hash(&hash_token(key))
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let now = Utc::now().timestamp();
+
+        if api_key
+            .expiration
+            .is_some_and(|expiration| expiration <= now)
+        {
+            return Err(AppError::Unauthorized());
+        }
+
+        let account = self
+            .account_repository
+            .find_by_id(&api_key.account)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        account.status.ensure_active()?;
+
+        if api_key
+            .last_used_at
+            .is_none_or(|last_used_at| last_used_at <= now - TOUCH_INTERVAL)
+        {
+            self.repository.touch(&api_key.id).await?;
+        }
+
+        // Scopes are narrowed to what the owner still holds, so demoted accounts lose them too
+        let (roles, permissions) = match api_key.scopes {
+            // Admin capabilities are never implied, they have to be granted as scopes
+            None => {
+                let owner = Account {
+                    roles: account
+                        .roles
+                        .iter()
+                        .copied()
+                        .filter(|role| *role != Role::Admin)
+                        .collect(),
+                    ..account.clone()
+                };
+
+                (owner.roles.clone(), owner.effective_permissions())
+            }
+            Some(scopes) => {
+                let permissions = account.effective_permissions();
+
+                (
+                    vec![],
+                    scopes
+                        .into_iter()
+                        .filter(|scope| permissions.contains(scope))
+                        .collect(),
+                )
+            }
+        };
+
+        Ok(Claims {
+            sub: account.id,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            exp: api_key.expiration.map(|expiration| expiration as usize),
+            nbf: now as usize,
+            iat: now as usize,
+            jti: api_key.id,
+            roles,
+            permissions,
+            client_id: None,
+            owner: None,
+            sid: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Permission, Role};
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::api_key::mock::ApiKeyRepositoryImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use rstest::*;
+
+    fn account(id: &str, roles: Vec<Role>) -> Account {
+        Account {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            email: format!("{id}@spacecraft.com"),
+            password: "p4ssw0rd".to_string(),
+            roles,
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn new_api_key(scopes: Option<Vec<Permission>>, expiration: Option<i64>) -> NewApiKey {
+        NewApiKey {
+            name: "CI".to_string(),
+            scopes,
+            expiration,
+        }
+    }
+
+    #[fixture]
+    fn service() -> ApiKeyServiceImpl {
+        ApiKeyServiceImpl::new(
+            &jsonwebtoken_config(),
+            Arc::new(ApiKeyRepositoryImpl {
+                api_keys: Mutex::new(vec![]),
+            }),
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![
+                    account("admin", vec![Role::Admin]),
+                    account("user", vec![Role::User]),
+                ]),
+            }),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_create_and_authenticate(service: ApiKeyServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+
+        let issued = service
+            .create(&admin, new_api_key(None, None))
+            .await
+            .unwrap();
+
+        assert!(issued.key.starts_with(&issued.api_key.prefix));
+        assert!(issued.api_key.prefix.starts_with(API_KEY_PREFIX));
+
+        let claims = service.authenticate(&issued.key).await.unwrap();
+
+        assert_eq!(claims.sub, "admin");
+        assert_eq!(claims.jti, issued.api_key.id);
+        assert_eq!(claims.exp, None);
+        assert!(claims.roles.is_empty());
+        assert!(claims.permissions.is_empty());
+
+        let listed = service.list("admin").await.unwrap();
+
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].last_used_at.is_some());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_scoped_key(service: ApiKeyServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+
+        let issued = service
+            .create(
+                &admin,
+                new_api_key(Some(vec![Permission::AccountsRead]), None),
+            )
+            .await
+            .unwrap();
+
+        let claims = service.authenticate(&issued.key).await.unwrap();
+
+        assert!(claims.roles.is_empty());
+        assert_eq!(claims.permissions, vec![Permission::AccountsRead]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_scope_escalation(service: ApiKeyServiceImpl) {
+        let user = account("user", vec![Role::User]);
+
+        let result = service
+            .create(
+                &user,
+                new_api_key(Some(vec![Permission::AccountsWrite]), None),
+            )
+            .await;
+
+        assert_eq!(result.unwrap_err().code, 403);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_expired_key() {
+        let repository = Arc::new(ApiKeyRepositoryImpl {
+            api_keys: Mutex::new(vec![]),
+        });
+
+        let service = ApiKeyServiceImpl::new(
+            &jsonwebtoken_config(),
+            repository.clone(),
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![account("user", vec![Role::User])]),
+            }),
+        );
+
+        let user = account("user", vec![Role::User]);
+        let past = Utc::now().timestamp() - 60;
+
+        let result = service.create(&user, new_api_key(None, Some(past))).await;
+
+        assert_eq!(result.unwrap_err().code, 400);
+
+        let issued = service
+            .create(&user, new_api_key(None, Some(past + 120)))
+            .await
+            .unwrap();
+
+        let claims = service.authenticate(&issued.key).await.unwrap();
+
+        assert_eq!(claims.exp, Some((past + 120) as usize));
+
+        // Keys lapsing after being issued are rejected too
+        repository.api_keys.lock().await[0].0.expiration = Some(past);
+
+        assert_eq!(
+            service.authenticate(&issued.key).await.unwrap_err().code,
+            401
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke(service: ApiKeyServiceImpl) {
+        let user = account("user", vec![Role::User]);
+
+        let issued = service
+            .create(&user, new_api_key(None, None))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service
+                .revoke("admin", &issued.api_key.id)
+                .await
+                .unwrap_err()
+                .code,
+            404
+        );
+
+        service.revoke("user", &issued.api_key.id).await.unwrap();
+
+        assert!(service.list("user").await.unwrap().is_empty());
+        assert_eq!(
+            service.authenticate(&issued.key).await.unwrap_err().code,
+            401
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_suspended_owner(service: ApiKeyServiceImpl) {
+        let user = account("user", vec![Role::User]);
+
+        let issued = service
+            .create(&user, new_api_key(None, None))
+            .await
+            .unwrap();
+
+        service
+            .account_repository
+            .update_status("user", AccountStatus::Suspended)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.authenticate(&issued.key).await.unwrap_err(),
+            AppError::Suspended()
+        );
+    }
+}
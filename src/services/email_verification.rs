@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::config::EmailVerificationConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::Account,
+    models::email_verification::CreateEmailVerificationToken,
+    models::mail::Mail,
+    repositories::account::{AccountRepository, FindByCol},
+    repositories::email_verification::EmailVerificationRepository,
+    services::email_verification::EmailVerificationService,
+    services::mailer::MailerService,
+};
+use crate::services::crypto::{hash_token, random_token};
+
+pub struct EmailVerificationServiceImpl {
+    ttl: i64,
+    url: String,
+    repository: Arc<dyn EmailVerificationRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+    mailer: Arc<dyn MailerService>,
+}
+
+impl EmailVerificationServiceImpl {
+    pub fn new(
+        config: &EmailVerificationConfig,
+        repository: Arc<dyn EmailVerificationRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+        mailer: Arc<dyn MailerService>,
+    ) -> Self {
+        Self {
+            ttl: config.ttl,
+            url: config.url.clone(),
+            repository,
+            account_repository,
+            mailer,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailVerificationService for EmailVerificationServiceImpl {
+    async fn send(&self, account: &Account) -> AppResult<()> {
+        // Only the latest link works, one mailed to a previous address cannot verify the current one
+        self.repository.consume_account(&account.id).await?;
+
+        let token = random_token();
+
+        let expiration = Utc::now()
+            .checked_add_signed(chrono::Duration::seconds(self.ttl))
+            .unwrap()
+            .timestamp();
+
+        self.repository
+            .create(CreateEmailVerificationToken {
+                account: account.id.clone(),
+                token_hash: hash_token(&token),
+                expiration,
+            })
+            .await?;
+
+        self.mailer
+            .send(Mail {
+                to: account.email.clone(),
+                subject: "Verify your email".to_string(),
+                body: format!(
+                    "Use the following link to verify your email: {}{}\n\nThe link expires in {} hours. If you did not create an account, ignore this email.",
+                    self.url,
+                    token,
+                    self.ttl / 3600
+                ),
+            })
+            .await
+    }
+
+    async fn resend(&self, email: &str) -> AppResult<()> {
+        // Unknown and already verified emails succeed silently so the endpoint does not reveal
+        // which accounts exist
+        match self
+            .account_repository
+            .find_one(FindByCol::Email(email.to_string()))
+            .await?
+        {
+            Some(account) if account.email_verified_at.is_none() => self.send(&account).await,
+            _ => Ok(()),
+        }
+    }
+
+    async fn verify(&self, token: &str) -> AppResult<Account> {
+        let stored = match self.repository.find_by_hash(&hash_token(token)).await? {
+            Some(stored) => stored,
+            None => return Err(AppError::Unauthorized()),
+        };
+
+        if stored.used || stored.expiration <= Utc::now().timestamp() {
+            return Err(AppError::Unauthorized());
+        }
+
+        if !self.repository.consume(&stored.id).await? {
+            return Err(AppError::Unauthorized());
+        }
+
+        match self
+            .account_repository
+            .verify_email(&stored.account)
+            .await?
+        {
+            Some(account) => Ok(account),
+            None => Err(AppError::Unauthorized()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::domain::models::email_verification::EmailVerificationToken;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::email_verification::mock::EmailVerificationRepositoryImpl;
+    use crate::services::mailer::mock::MailerServiceImpl;
+    use rstest::*;
+
+    fn account() -> Account {
+        Account {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    #[fixture]
+    fn service() -> (EmailVerificationServiceImpl, Arc<MailerServiceImpl>) {
+        let mailer = Arc::new(MailerServiceImpl {
+            mails: Mutex::new(vec![]),
+        });
+
+        let repository = Arc::new(EmailVerificationRepositoryImpl {
+            tokens: Mutex::new(HashMap::from([(
+                hash_token("expired_token"),
+                EmailVerificationToken {
+                    id: "expired".to_string(),
+                    account: "1".to_string(),
+                    expiration: Utc::now().timestamp() - 60,
+                    used: false,
+                },
+            )])),
+        });
+
+        let account_repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![account()]),
+        });
+
+        let service = EmailVerificationServiceImpl::new(
+            &EmailVerificationConfig {
+                required: true,
+                ttl: 86400,
+                url: "http://localhost/verify-email?token=".to_string(),
+            },
+            repository,
+            account_repository,
+            mailer.clone(),
+        );
+
+        (service, mailer)
+    }
+
+    fn mailed_token(mail: &Mail) -> String {
+        mail.body
+            .split("token=")
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_verify_email(service: (EmailVerificationServiceImpl, Arc<MailerServiceImpl>)) {
+        let (service, mailer) = service;
+
+        service.send(&account()).await.unwrap();
+
+        let mail = mailer.mails.lock().await[0].clone();
+
+        assert_eq!(mail.to, "test_account@spacecraft.com");
+
+        let account = service.verify(&mailed_token(&mail)).await.unwrap();
+
+        assert!(account.email_verified_at.is_some());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_verification_token_single_use(
+        service: (EmailVerificationServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.send(&account()).await.unwrap();
+
+        let token = mailed_token(&mailer.mails.lock().await[0]);
+
+        assert!(service.verify(&token).await.is_ok());
+        assert_eq!(
+            service.verify(&token).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_resend(service: (EmailVerificationServiceImpl, Arc<MailerServiceImpl>)) {
+        let (service, mailer) = service;
+
+        service.resend("test_account@spacecraft.com").await.unwrap();
+
+        let token = mailed_token(&mailer.mails.lock().await[0]);
+
+        assert!(service.verify(&token).await.is_ok());
+
+        // Verified and unknown emails get no mail and the same answer
+        service.resend("test_account@spacecraft.com").await.unwrap();
+        service.resend("unknown@spacecraft.com").await.unwrap();
+
+        assert_eq!(mailer.mails.lock().await.len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_send_consumes_previous_tokens(
+        service: (EmailVerificationServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.send(&account()).await.unwrap();
+        service.send(&account()).await.unwrap();
+
+        let mails = mailer.mails.lock().await.clone();
+
+        assert_eq!(
+            service.verify(&mailed_token(&mails[0])).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert!(service.verify(&mailed_token(&mails[1])).await.is_ok());
+    }
+
+    #[rstest]
+    #[case::unknown("unknown_token")]
+    #[case::expired("expired_token")]
+    #[tokio::test]
+    async fn test_invalid_token(
+        service: (EmailVerificationServiceImpl, Arc<MailerServiceImpl>),
+        #[case] token: &str,
+    ) {
+        let (service, _) = service;
+
+        assert_eq!(
+            service.verify(token).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use argon2::{
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version,
+    password_hash::{
+        Error::Password, PasswordHash, PasswordHasher, PasswordVerifier, Result, SaltString,
+        rand_core::OsRng,
+    },
+};
+
+use crate::config::{PasswordHashAlgorithm, PasswordHashConfig};
+
+// Recorded in the PHC string of peppered hashes, the pepper itself is never stored
+const PEPPER_KEYID: &[u8] = b"pepper";
+
+pub struct Argon2Hasher {
+    algorithm: Algorithm,
+    version: Version,
+    params: Params,
+    pepper: Option<Vec<u8>>,
+}
+
+impl Argon2Hasher {
+    pub fn new(config: &PasswordHashConfig) -> std::result::Result<Self, argon2::Error> {
+        let pepper = config
+            .pepper
+            .as_deref()
+            .filter(|pepper| !pepper.is_empty())
+            .map(|pepper| pepper.as_bytes().to_vec());
+
+        let mut params = ParamsBuilder::new();
+
+        params
+            .m_cost(config.memory_cost)
+            .t_cost(config.time_cost)
+            .p_cost(config.parallelism);
+
+        if pepper.is_some() {
+            params.keyid(KeyId::new(PEPPER_KEYID)?);
+        }
+
+        Ok(Self {
+            algorithm: match config.algorithm {
+                PasswordHashAlgorithm::Argon2d => Algorithm::Argon2d,
+                PasswordHashAlgorithm::Argon2i => Algorithm::Argon2i,
+                PasswordHashAlgorithm::Argon2id => Algorithm::Argon2id,
+            },
+            version: Version::try_from(config.version)?,
+            params: params.build()?,
+            pepper,
+        })
+    }
+
+    // Hashes carry their own parameters, so only the pepper has to be chosen here
+    fn argon2(&self, peppered: bool) -> Result<Argon2<'_>> {
+        match (peppered, &self.pepper) {
+            (false, _) => Ok(Argon2::new(
+                self.algorithm,
+                self.version,
+                self.params.clone(),
+            )),
+            (true, Some(pepper)) => Ok(Argon2::new_with_secret(
+                pepper,
+                self.algorithm,
+                self.version,
+                self.params.clone(),
+            )?),
+            (true, None) => Err(Password),
+        }
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        self.argon2(self.pepper.is_some())?
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+    }
+
+    pub fn verify(&self, password: &str, hash: &str) -> Result<()> {
+        let hash = PasswordHash::new(hash)?;
+        let peppered = !Params::try_from(&hash)?.keyid().is_empty();
+
+        self.argon2(peppered)?
+            .verify_password(password.as_bytes(), &hash)
+    }
+
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+
+        let Ok(params) = Params::try_from(&hash) else {
+            return true;
+        };
+
+        hash.algorithm != self.algorithm.ident()
+            || hash.version != Some(self.version.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+            || params.keyid() != self.params.keyid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::utils::config::password_hash_config;
+
+    fn hasher(config: PasswordHashConfig) -> Argon2Hasher {
+        Argon2Hasher::new(&config).unwrap()
+    }
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hasher = hasher(password_hash_config());
+
+        let hash = hasher.hash("p4ssw0rd").unwrap();
+
+        assert!(hasher.verify("p4ssw0rd", &hash).is_ok());
+        assert!(matches!(hasher.verify("wrong", &hash), Err(Password)));
+        assert!(!hasher.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn test_needs_rehash_on_new_params() {
+        let weak = hasher(PasswordHashConfig {
+            memory_cost: 8192,
+            time_cost: 1,
+            ..password_hash_config()
+        });
+        let strong = hasher(password_hash_config());
+
+        let hash = weak.hash("p4ssw0rd").unwrap();
+
+        assert!(strong.verify("p4ssw0rd", &hash).is_ok());
+        assert!(strong.needs_rehash(&hash));
+        assert!(!strong.needs_rehash(&strong.hash("p4ssw0rd").unwrap()));
+    }
+
+    #[test]
+    fn test_needs_rehash_on_new_algorithm() {
+        let argon2i = hasher(PasswordHashConfig {
+            algorithm: PasswordHashAlgorithm::Argon2i,
+            ..password_hash_config()
+        });
+
+        let hash = argon2i.hash("p4ssw0rd").unwrap();
+
+        assert!(hasher(password_hash_config()).needs_rehash(&hash));
+    }
+
+    #[test]
+    fn test_pepper() {
+        let plain = hasher(password_hash_config());
+        let peppered = hasher(PasswordHashConfig {
+            pepper: Some("s3cr3t".to_string()),
+            ..password_hash_config()
+        });
+        let other = hasher(PasswordHashConfig {
+            pepper: Some("other".to_string()),
+            ..password_hash_config()
+        });
+
+        let hash = peppered.hash("p4ssw0rd").unwrap();
+
+        assert!(peppered.verify("p4ssw0rd", &hash).is_ok());
+        assert!(!peppered.needs_rehash(&hash));
+        assert!(matches!(plain.verify("p4ssw0rd", &hash), Err(Password)));
+        assert!(matches!(other.verify("p4ssw0rd", &hash), Err(Password)));
+
+        // Hashes from before the pepper was configured keep working until upgraded
+        let legacy = plain.hash("p4ssw0rd").unwrap();
+
+        assert!(peppered.verify("p4ssw0rd", &legacy).is_ok());
+        assert!(peppered.needs_rehash(&legacy));
+    }
+}
@@ -1,96 +1,459 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use crate::config::JsonWebTokenConfig;
 use crate::domain::error::{AppError, AppResult};
+use crate::domain::models::account::{Account, Permission, Role};
 use crate::domain::models::jsonwebtoken::{AccessToken, Claims};
+use crate::domain::models::revoked_token::RevokedToken;
+use crate::domain::repositories::account::AccountRepository;
+use crate::domain::repositories::revoked_token::RevokedTokenRepository;
+use crate::domain::repositories::session::SessionRepository;
 use crate::domain::services::jsonwebtoken::JsonWebTokenService;
+use crate::services::keyring::Keyring;
+use actix_web::rt::time;
+use async_trait::async_trait;
 use chrono::Utc;
-use jsonwebtoken::errors::{Error as JsonWebTokenError, ErrorKind};
-use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Header, Validation, decode, decode_header, encode};
+use uuid::Uuid;
+
+pub const REVOCATION_REFRESH_SECONDS: u64 = 30;
+
+// Session activity is recorded at most once per interval, not on every request
+const TOUCH_INTERVAL: i64 = 60;
 
-#[derive(Clone)]
-pub struct KeyPair {
-    encoding: EncodingKey,
-    decoding: DecodingKey,
+#[derive(Default)]
+struct RevocationList {
+    tokens: HashMap<String, i64>,
+    issued_before: HashMap<String, i64>,
+    sessions: HashSet<String>,
+    suspended: HashSet<String>,
 }
 
-impl KeyPair {
-    pub fn from_rsa_pem(
-        private_key: Vec<u8>,
-        public_key: Vec<u8>,
-    ) -> Result<Self, JsonWebTokenError> {
-        Ok(KeyPair {
-            encoding: EncodingKey::from_rsa_pem(&private_key)?,
-            decoding: DecodingKey::from_rsa_pem(&public_key)?,
-        })
-    }
+// Optional claims tying a token to the client or session it was issued for
+#[derive(Default)]
+struct Binding<'a> {
+    client_id: Option<&'a str>,
+    owner: Option<&'a str>,
+    session: Option<&'a str>,
 }
 
 pub struct JsonWebTokenServiceImpl {
-    keys: KeyPair,
+    keys: Keyring,
+    issuer: String,
+    audience: String,
+    mfa_audience: String,
+    ttl: i64,
+    mfa_ttl: i64,
+    not_before: i64,
+    validation: Validation,
+    mfa_validation: Validation,
+    repository: Arc<dyn RevokedTokenRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+    session_repository: Arc<dyn SessionRepository>,
+    revoked: RwLock<RevocationList>,
+    touched: Mutex<HashMap<String, i64>>,
 }
 
 impl JsonWebTokenServiceImpl {
-    pub fn new(keys: KeyPair) -> Self {
-        JsonWebTokenServiceImpl { keys }
+    pub fn new(
+        keys: Keyring,
+        config: &JsonWebTokenConfig,
+        repository: Arc<dyn RevokedTokenRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+        session_repository: Arc<dyn SessionRepository>,
+    ) -> Self {
+        let mut validation = Validation::new(keys.algorithm());
+        validation.set_issuer(&[&config.issuer]);
+        validation.set_audience(&[&config.audience]);
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
+        validation.validate_nbf = true;
+        validation.leeway = config.leeway;
+
+        // MFA pending tokens use their own audience so they are never accepted as access tokens
+        let mfa_audience = format!("{}:mfa", config.audience);
+
+        let mut mfa_validation = validation.clone();
+        mfa_validation.set_audience(&[&mfa_audience]);
+
+        JsonWebTokenServiceImpl {
+            keys,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            mfa_audience,
+            ttl: config.ttl,
+            mfa_ttl: config.mfa_ttl,
+            not_before: config.not_before,
+            validation,
+            mfa_validation,
+            repository,
+            account_repository,
+            session_repository,
+            revoked: RwLock::new(RevocationList::default()),
+            touched: Mutex::new(HashMap::new()),
+        }
     }
-}
 
-impl JsonWebTokenService for JsonWebTokenServiceImpl {
-    fn generate_token(&self, id: String) -> AppResult<AccessToken> {
+    fn issue(
+        &self,
+        sub: &str,
+        audience: &str,
+        ttl: i64,
+        roles: Vec<Role>,
+        permissions: Vec<Permission>,
+        binding: Binding,
+    ) -> AppResult<AccessToken> {
         let now = Utc::now();
 
         let expiration = now
-            .checked_add_signed(chrono::Duration::hours(1))
+            .checked_add_signed(chrono::Duration::seconds(ttl))
             .unwrap()
             .timestamp();
 
         let iat = now.timestamp();
 
         let claims = Claims {
-            sub: id,
-            exp: expiration as usize,
+            sub: sub.to_owned(),
+            iss: self.issuer.clone(),
+            aud: audience.to_owned(),
+            exp: Some(expiration as usize),
+            nbf: (iat + self.not_before) as usize,
             iat: iat as usize,
+            jti: Uuid::new_v4().to_string(),
+            roles,
+            permissions,
+            client_id: binding.client_id.map(ToOwned::to_owned),
+            owner: binding.owner.map(ToOwned::to_owned),
+            sid: binding.session.map(ToOwned::to_owned),
         };
 
-        let header = Header::new(Algorithm::RS256);
+        let (kid, key) = self.keys.signing_key();
+
+        let mut header = Header::new(self.keys.algorithm());
+        header.kid = Some(kid.to_owned());
 
-        let token = encode(&header, &claims, &self.keys.encoding)
+        let token = encode(&header, &claims, key)
             .map_err(|err| AppError::InternalError().trace(&err.to_string()))?;
 
         Ok(AccessToken { token, expiration })
     }
 
-    fn validate_token(&self, token: &str) -> AppResult<Claims> {
-        match decode::<Claims>(
-            token,
-            &self.keys.decoding,
-            &Validation::new(Algorithm::RS256),
-        ) {
+    async fn validate(&self, token: &str, validation: &Validation) -> AppResult<Claims> {
+        let claims = self.decode_token(token, validation)?;
+
+        if self.is_revoked(&claims) {
+            return Err(AppError::Unauthorized());
+        }
+
+        if self.is_suspended(&claims) {
+            return Err(AppError::Suspended());
+        }
+
+        if let Some(session) = claims.sid.as_deref().filter(|sid| self.should_touch(sid)) {
+            self.session_repository.touch(session).await?;
+        }
+
+        Ok(claims)
+    }
+
+    fn decode_token(&self, token: &str, validation: &Validation) -> AppResult<Claims> {
+        let header = decode_header(token).map_err(|_| AppError::Unauthorized())?;
+
+        let key = self
+            .keys
+            .decoding_key(header.kid.as_deref())
+            .ok_or_else(AppError::Unauthorized)?;
+
+        match decode::<Claims>(token, key, validation) {
             Ok(token) => Ok(token.claims),
             Err(error) => match error.kind() {
-                ErrorKind::ExpiredSignature
-                | ErrorKind::InvalidToken
-                | ErrorKind::InvalidIssuer => Err(AppError::Unauthorized()),
-                _ => Err(AppError::InternalError().trace(&format!("{error:?}"))),
+                ErrorKind::InvalidRsaKey(_)
+                | ErrorKind::InvalidEcdsaKey
+                | ErrorKind::InvalidKeyFormat
+                | ErrorKind::MissingAlgorithm
+                | ErrorKind::Crypto(_) => {
+                    Err(AppError::InternalError().trace(&format!("{error:?}")))
+                }
+                _ => Err(AppError::Unauthorized()),
+            },
+        }
+    }
+
+    fn should_touch(&self, session: &str) -> bool {
+        let now = Utc::now().timestamp();
+        let mut touched = self.touched.lock().unwrap();
+
+        match touched.get(session) {
+            Some(at) if now - at < TOUCH_INTERVAL => false,
+            _ => {
+                touched.insert(session.to_owned(), now);
+                true
+            }
+        }
+    }
+
+    // Client credentials tokens fall with the account that owns the client
+    fn is_suspended(&self, claims: &Claims) -> bool {
+        let revoked = self.revoked.read().unwrap();
+
+        revoked.suspended.contains(&claims.sub)
+            || claims
+                .owner
+                .as_ref()
+                .is_some_and(|owner| revoked.suspended.contains(owner))
+    }
+
+    fn is_revoked(&self, claims: &Claims) -> bool {
+        let revoked = self.revoked.read().unwrap();
+
+        revoked.tokens.contains_key(&claims.jti)
+            || revoked
+                .issued_before
+                .get(&claims.sub)
+                .is_some_and(|at| (claims.iat as i64) < *at)
+            || claims
+                .sid
+                .as_ref()
+                .is_some_and(|sid| revoked.sessions.contains(sid))
+    }
+}
+
+#[async_trait]
+impl JsonWebTokenService for JsonWebTokenServiceImpl {
+    fn generate_token(&self, account: &Account, session: &str) -> AppResult<AccessToken> {
+        account.status.ensure_active()?;
+
+        self.issue(
+            &account.id,
+            &self.audience,
+            self.ttl,
+            account.roles.clone(),
+            account.effective_permissions(),
+            Binding {
+                session: Some(session),
+                ..Default::default()
             },
+        )
+    }
+
+    async fn validate_token(&self, token: &str) -> AppResult<Claims> {
+        self.validate(token, &self.validation).await
+    }
+
+    fn generate_mfa_token(&self, account: &Account) -> AppResult<AccessToken> {
+        self.issue(
+            &account.id,
+            &self.mfa_audience,
+            self.mfa_ttl,
+            vec![],
+            vec![],
+            Binding::default(),
+        )
+    }
+
+    fn generate_client_token(
+        &self,
+        sub: &str,
+        client_id: &str,
+        owner: Option<&str>,
+        permissions: Vec<Permission>,
+    ) -> AppResult<AccessToken> {
+        self.issue(
+            sub,
+            &self.audience,
+            self.ttl,
+            vec![],
+            permissions,
+            Binding {
+                client_id: Some(client_id),
+                owner,
+                session: None,
+            },
+        )
+    }
+
+    async fn validate_mfa_token(&self, token: &str) -> AppResult<Claims> {
+        self.validate(token, &self.mfa_validation).await
+    }
+
+    fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
+    }
+
+    async fn revoke_token(&self, claims: &Claims) -> AppResult<()> {
+        // Only API key claims lack `exp`, API keys are revoked through /me/api-keys instead
+        let Some(expiration) = claims.exp else {
+            return Err(AppError::Forbidden());
+        };
+
+        self.repository
+            .revoke(RevokedToken {
+                jti: claims.jti.clone(),
+                expiration: expiration as i64,
+            })
+            .await?;
+
+        self.revoked
+            .write()
+            .unwrap()
+            .tokens
+            .insert(claims.jti.clone(), expiration as i64);
+
+        Ok(())
+    }
+
+    fn revoke_issued_before(&self, sub: &str, issued_before: i64) {
+        self.revoked
+            .write()
+            .unwrap()
+            .issued_before
+            .insert(sub.to_owned(), issued_before);
+    }
+
+    // Only updates this replica, the others pick the revocation up on their next reload
+    fn revoke_session(&self, session: &str) {
+        self.revoked
+            .write()
+            .unwrap()
+            .sessions
+            .insert(session.to_owned());
+    }
+
+    // Like sessions, other replicas only see the new status on their next reload
+    fn suspend(&self, sub: &str) {
+        self.revoked
+            .write()
+            .unwrap()
+            .suspended
+            .insert(sub.to_owned());
+    }
+
+    fn reactivate(&self, sub: &str) {
+        self.revoked.write().unwrap().suspended.remove(sub);
+    }
+
+    // Picks up revocations made by other replicas, the snapshot is only swapped once every query
+    // has succeeded
+    async fn reload(&self) -> AppResult<()> {
+        self.repository.prune().await?;
+
+        let now = Utc::now().timestamp();
+        self.touched
+            .lock()
+            .unwrap()
+            .retain(|_, at| now - *at < TOUCH_INTERVAL);
+
+        let tokens = self.repository.find_active().await?;
+
+        // Tokens issued before an older password change or session revocation have already expired
+        let horizon = Utc::now().timestamp() - self.ttl - self.validation.leeway as i64;
+
+        let accounts = self
+            .account_repository
+            .find_password_changed_since(horizon)
+            .await?;
+
+        let sessions = self.session_repository.find_revoked_since(horizon).await?;
+
+        let suspended = self.account_repository.find_suspended().await?;
+
+        let mut revoked = self.revoked.write().unwrap();
+        revoked.tokens = tokens.into_iter().map(|t| (t.jti, t.expiration)).collect();
+        revoked.issued_before = accounts
+            .into_iter()
+            .filter_map(|a| a.password_changed_at.map(|at| (a.id, at)))
+            .collect();
+        revoked.sessions = sessions.into_iter().collect();
+        revoked.suspended = suspended.into_iter().collect();
+
+        Ok(())
+    }
+}
+
+// Runs for the lifetime of the server, a failed reload keeps serving the last good snapshot
+pub async fn reload_periodically(service: Arc<dyn JsonWebTokenService>, period: Duration) {
+    let mut interval = time::interval(period);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = service.reload().await {
+            tracing::warn!(error = ?err, "revocation list reload failed");
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use tokio::sync::Mutex;
+
     use super::*;
-    use crate::tests::utils::crypto::generate_keypair;
+    use crate::domain::models::account::{AccountStatus, Permission, Role};
+    use crate::domain::models::session::CreateSession;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::keyring::KeyPair;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::{generate_keypair, generate_keyring, generate_pem};
 
+    use jsonwebtoken::Algorithm;
     use rstest::*;
 
+    fn account(roles: Vec<Role>) -> Account {
+        Account {
+            id: "test_id".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles,
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn account_repository() -> Arc<AccountRepositoryImpl> {
+        Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![]),
+        })
+    }
+
+    fn session_repository() -> Arc<SessionRepositoryImpl> {
+        Arc::new(SessionRepositoryImpl {
+            sessions: Mutex::new(vec![]),
+        })
+    }
+
+    fn revoked_token_repository() -> Arc<RevokedTokenRepositoryImpl> {
+        Arc::new(RevokedTokenRepositoryImpl {
+            tokens: Mutex::new(vec![]),
+        })
+    }
+
     #[fixture]
     #[once]
     fn jwt_service() -> JsonWebTokenServiceImpl {
-        JsonWebTokenServiceImpl::new(generate_keypair())
+        JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        )
     }
 
     #[fixture]
     fn access_token(jwt_service: &JsonWebTokenServiceImpl) -> AccessToken {
-        jwt_service.generate_token("test_id".to_string()).unwrap()
+        jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap()
     }
 
     #[rstest]
@@ -105,16 +468,632 @@ mod tests {
         jwt_service: &JsonWebTokenServiceImpl,
         access_token: AccessToken,
     ) {
-        let claims = jwt_service.validate_token(&access_token.token).unwrap();
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
         assert_eq!(claims.sub, "test_id");
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoked_token(jwt_service: &JsonWebTokenServiceImpl, access_token: AccessToken) {
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        jwt_service.revoke_token(&claims).await.unwrap();
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unique_jti(jwt_service: &JsonWebTokenServiceImpl) {
+        let first = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+        let second = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        let first = jwt_service.validate_token(&first.token).await.unwrap();
+        let second = jwt_service.validate_token(&second.token).await.unwrap();
+
+        assert_ne!(first.jti, second.jti);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_invalid_token(jwt_service: &JsonWebTokenServiceImpl) {
         assert_eq!(
-            jwt_service.validate_token("invalidtoken").unwrap_err(),
+            jwt_service
+                .validate_token("invalidtoken")
+                .await
+                .unwrap_err(),
             AppError::Unauthorized()
         );
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_token_kid(access_token: AccessToken) {
+        let header = decode_header(&access_token.token).unwrap();
+
+        assert_eq!(header.kid.as_deref(), Some("test"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_key_rotation() {
+        let (old_private_key, old_public_key) = generate_pem(Algorithm::RS256);
+
+        let old_keyring = Keyring::new(
+            "old",
+            vec![
+                KeyPair::from_pem(
+                    "old",
+                    Algorithm::RS256,
+                    Some(old_private_key),
+                    old_public_key.clone(),
+                )
+                .unwrap(),
+            ],
+        )
+        .unwrap();
+
+        let new_keyring = Keyring::new(
+            "new",
+            vec![
+                generate_keypair("new", Algorithm::RS256),
+                KeyPair::from_pem("old", Algorithm::RS256, None, old_public_key).unwrap(),
+            ],
+        )
+        .unwrap();
+
+        let old_service = JsonWebTokenServiceImpl::new(
+            old_keyring,
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+        let new_service = JsonWebTokenServiceImpl::new(
+            new_keyring,
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let old_token = old_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+        let new_token = new_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert!(new_service.validate_token(&old_token.token).await.is_ok());
+        assert_eq!(
+            old_service
+                .validate_token(&new_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[case::rsa(Algorithm::RS256)]
+    #[case::ecdsa(Algorithm::ES256)]
+    #[case::eddsa(Algorithm::EdDSA)]
+    #[tokio::test]
+    async fn test_signing_algorithm(#[case] algorithm: Algorithm) {
+        let keys = Keyring::new("test", vec![generate_keypair("test", algorithm)]).unwrap();
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            keys,
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert_eq!(decode_header(&access_token.token).unwrap().alg, algorithm);
+        assert!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[case::ecdsa_as_rsa(Algorithm::ES256, Algorithm::RS256)]
+    #[case::rsa_as_eddsa(Algorithm::RS256, Algorithm::EdDSA)]
+    #[case::eddsa_as_ecdsa(Algorithm::EdDSA, Algorithm::ES256)]
+    #[tokio::test]
+    async fn test_algorithm_mismatch(#[case] issuer: Algorithm, #[case] validator: Algorithm) {
+        let issuer = JsonWebTokenServiceImpl::new(
+            Keyring::new("test", vec![generate_keypair("test", issuer)]).unwrap(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+        let validator = JsonWebTokenServiceImpl::new(
+            Keyring::new("test", vec![generate_keypair("test", validator)]).unwrap(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = issuer
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert_eq!(
+            validator
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_token_claims(jwt_service: &JsonWebTokenServiceImpl, access_token: AccessToken) {
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.iss, "test");
+        assert_eq!(claims.aud, "test");
+        assert_eq!(claims.nbf, claims.iat);
+    }
+
+    #[rstest]
+    #[case::issuer(JsonWebTokenConfig { issuer: "other".to_string(), ..jsonwebtoken_config() })]
+    #[case::audience(JsonWebTokenConfig { audience: "other".to_string(), ..jsonwebtoken_config() })]
+    #[case::not_before(JsonWebTokenConfig { not_before: 120, ..jsonwebtoken_config() })]
+    #[case::expired(JsonWebTokenConfig { ttl: -120, ..jsonwebtoken_config() })]
+    #[tokio::test]
+    async fn test_rejected_claims(#[case] config: JsonWebTokenConfig) {
+        let keys = generate_keyring();
+
+        let issuer = JsonWebTokenServiceImpl::new(
+            keys.clone(),
+            &config,
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+        let validator = JsonWebTokenServiceImpl::new(
+            keys,
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = issuer
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert_eq!(
+            validator
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_leeway() {
+        let keys = generate_keyring();
+
+        let issuer = JsonWebTokenServiceImpl::new(
+            keys.clone(),
+            &JsonWebTokenConfig {
+                ttl: -30,
+                not_before: 30,
+                ..jsonwebtoken_config()
+            },
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+        let validator = JsonWebTokenServiceImpl::new(
+            keys,
+            &JsonWebTokenConfig {
+                leeway: 60,
+                ..jsonwebtoken_config()
+            },
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = issuer
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert!(validator.validate_token(&access_token.token).await.is_ok());
+    }
+
+    #[rstest]
+    #[case::user(vec![Role::User], vec![])]
+    #[case::admin(vec![Role::Admin], vec![Permission::AccountsRead, Permission::AccountsWrite])]
+    #[tokio::test]
+    async fn test_token_roles(
+        jwt_service: &JsonWebTokenServiceImpl,
+        #[case] roles: Vec<Role>,
+        #[case] permissions: Vec<Permission>,
+    ) {
+        let access_token = jwt_service
+            .generate_token(&account(roles.clone()), "session")
+            .unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.roles, roles);
+        assert_eq!(claims.permissions, permissions);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_token_granted_permissions(jwt_service: &JsonWebTokenServiceImpl) {
+        let account = Account {
+            permissions: vec![Permission::AccountsRead],
+            ..account(vec![Role::User])
+        };
+
+        let access_token = jwt_service.generate_token(&account, "session").unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.roles, vec![Role::User]);
+        assert_eq!(claims.permissions, vec![Permission::AccountsRead]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_mfa_token(jwt_service: &JsonWebTokenServiceImpl) {
+        let mfa_token = jwt_service
+            .generate_mfa_token(&account(vec![Role::Admin]))
+            .unwrap();
+
+        let claims = jwt_service
+            .validate_mfa_token(&mfa_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.sub, "test_id");
+        assert_eq!(claims.aud, "test:mfa");
+        assert!(claims.roles.is_empty());
+        assert!(claims.permissions.is_empty());
+        assert!(mfa_token.expiration - Utc::now().timestamp() <= 300);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_mfa_token_audience(
+        jwt_service: &JsonWebTokenServiceImpl,
+        access_token: AccessToken,
+    ) {
+        let mfa_token = jwt_service
+            .generate_mfa_token(&account(vec![Role::User]))
+            .unwrap();
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&mfa_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert_eq!(
+            jwt_service
+                .validate_mfa_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_password_changed() {
+        let accounts = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![Account {
+                password_changed_at: Some(Utc::now().timestamp() + 60),
+                ..account(vec![Role::User])
+            }]),
+        });
+
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            accounts,
+            session_repository(),
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        assert!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+
+        jwt_service.reload().await.unwrap();
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_issued_before() {
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        jwt_service.revoke_issued_before(&claims.sub, claims.iat as i64 + 1);
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+
+        jwt_service.revoke_issued_before(&claims.sub, claims.iat as i64);
+
+        assert!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoked_session(jwt_service: &JsonWebTokenServiceImpl) {
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "revoked_session")
+            .unwrap();
+        let other = jwt_service
+            .generate_token(&account(vec![Role::User]), "other_session")
+            .unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.sid.as_deref(), Some("revoked_session"));
+
+        jwt_service.revoke_session("revoked_session");
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert!(jwt_service.validate_token(&other.token).await.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_session_touch_throttled() {
+        let sessions = session_repository();
+
+        let session = sessions
+            .create(CreateSession {
+                account: "test_id".to_string(),
+                user_agent: None,
+                ip: None,
+            })
+            .await
+            .unwrap();
+
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            sessions.clone(),
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), &session.id)
+            .unwrap();
+
+        let last_seen_at = || async { sessions.sessions.lock().await[0].0.last_seen_at };
+
+        sessions.sessions.lock().await[0].0.last_seen_at = 0;
+
+        jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert!(last_seen_at().await > 0);
+
+        sessions.sessions.lock().await[0].0.last_seen_at = 0;
+
+        jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        assert_eq!(last_seen_at().await, 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_session_revoked_elsewhere() {
+        let sessions = session_repository();
+
+        let session = sessions
+            .create(CreateSession {
+                account: "test_id".to_string(),
+                user_agent: None,
+                ip: None,
+            })
+            .await
+            .unwrap();
+
+        sessions.revoke(&session.id, "test_id").await.unwrap();
+
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            sessions,
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), &session.id)
+            .unwrap();
+
+        jwt_service.reload().await.unwrap();
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_suspend() {
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            account_repository(),
+            session_repository(),
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        let claims = jwt_service
+            .validate_token(&access_token.token)
+            .await
+            .unwrap();
+
+        jwt_service.suspend(&claims.sub);
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+
+        jwt_service.reactivate(&claims.sub);
+
+        assert!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_suspended_elsewhere() {
+        let suspended = Account {
+            status: AccountStatus::Suspended,
+            ..account(vec![Role::User])
+        };
+
+        let jwt_service = JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            revoked_token_repository(),
+            Arc::new(AccountRepositoryImpl {
+                accounts: Mutex::new(vec![suspended.clone()]),
+            }),
+            session_repository(),
+        );
+
+        assert_eq!(
+            jwt_service
+                .generate_token(&suspended, "session")
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+
+        let access_token = jwt_service
+            .generate_token(&account(vec![Role::User]), "session")
+            .unwrap();
+
+        jwt_service.reload().await.unwrap();
+
+        assert_eq!(
+            jwt_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+    }
 }
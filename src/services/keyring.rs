@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::errors::Error as JsonWebTokenError;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+    EllipticCurveKeyType, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+    PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use simple_asn1::{ASN1Block, OID, from_der, oid};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum KeyringError {
+    #[error(transparent)]
+    JsonWebToken(#[from] JsonWebTokenError),
+    #[error("Invalid public key {0}: {1}")]
+    InvalidPublicKey(String, String),
+    #[error("Signing key {0} is not in the keyring")]
+    MissingSigningKey(String),
+    #[error("Signing key {0} has no private key")]
+    MissingPrivateKey(String),
+    #[error("Unsupported signing algorithm {0:?}")]
+    UnsupportedAlgorithm(Algorithm),
+    #[error("Key {0} does not use the {1:?} signing algorithm")]
+    AlgorithmMismatch(String, Algorithm),
+}
+
+#[derive(Clone)]
+pub struct KeyPair {
+    kid: String,
+    algorithm: Algorithm,
+    encoding: Option<EncodingKey>,
+    decoding: DecodingKey,
+    jwk: Jwk,
+}
+
+impl KeyPair {
+    pub fn from_pem(
+        kid: &str,
+        algorithm: Algorithm,
+        private_key: Option<Vec<u8>>,
+        public_key: Vec<u8>,
+    ) -> Result<Self, KeyringError> {
+        // Shared secrets cannot be published in the JWKS
+        if matches!(
+            algorithm,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(KeyringError::UnsupportedAlgorithm(algorithm));
+        }
+
+        let jwk = public_jwk(kid, algorithm, &public_key)
+            .map_err(|err| KeyringError::InvalidPublicKey(kid.to_owned(), err))?;
+
+        let (encoding, decoding) = match algorithm {
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => (
+                private_key
+                    .map(|key| EncodingKey::from_rsa_pem(&key))
+                    .transpose()?,
+                DecodingKey::from_rsa_pem(&public_key)?,
+            ),
+            Algorithm::ES256 | Algorithm::ES384 => (
+                private_key
+                    .map(|key| EncodingKey::from_ec_pem(&key))
+                    .transpose()?,
+                DecodingKey::from_ec_pem(&public_key)?,
+            ),
+            _ => (
+                private_key
+                    .map(|key| EncodingKey::from_ed_pem(&key))
+                    .transpose()?,
+                DecodingKey::from_ed_pem(&public_key)?,
+            ),
+        };
+
+        Ok(KeyPair {
+            kid: kid.to_owned(),
+            algorithm,
+            encoding,
+            decoding,
+            jwk,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Keyring {
+    signing_kid: String,
+    keys: HashMap<String, KeyPair>,
+}
+
+impl Keyring {
+    pub fn new(signing_kid: &str, keys: Vec<KeyPair>) -> Result<Self, KeyringError> {
+        let keys: HashMap<String, KeyPair> =
+            keys.into_iter().map(|key| (key.kid.clone(), key)).collect();
+
+        let algorithm = match keys.get(signing_kid) {
+            None => return Err(KeyringError::MissingSigningKey(signing_kid.to_owned())),
+            Some(key) if key.encoding.is_none() => {
+                return Err(KeyringError::MissingPrivateKey(signing_kid.to_owned()));
+            }
+            Some(key) => key.algorithm,
+        };
+
+        // Tokens are validated against a single algorithm, so every key must share it
+        if let Some(key) = keys.values().find(|key| key.algorithm != algorithm) {
+            return Err(KeyringError::AlgorithmMismatch(key.kid.clone(), algorithm));
+        }
+
+        Ok(Keyring {
+            signing_kid: signing_kid.to_owned(),
+            keys,
+        })
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.keys[&self.signing_kid].algorithm
+    }
+
+    pub fn signing_key(&self) -> (&str, &EncodingKey) {
+        let key = &self.keys[&self.signing_kid];
+
+        (&key.kid, key.encoding.as_ref().unwrap())
+    }
+
+    // Tokens issued before key ids were introduced carry no `kid`: check them against the signing key
+    pub fn decoding_key(&self, kid: Option<&str>) -> Option<&DecodingKey> {
+        self.keys
+            .get(kid.unwrap_or(&self.signing_kid))
+            .map(|key| &key.decoding)
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<Jwk> = self.keys.values().map(|key| key.jwk.clone()).collect();
+
+        keys.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+
+        JwkSet { keys }
+    }
+}
+
+struct SubjectPublicKeyInfo {
+    algorithm: OID,
+    parameters: Option<OID>,
+    key: Vec<u8>,
+}
+
+fn public_jwk(kid: &str, algorithm: Algorithm, public_key: &[u8]) -> Result<Jwk, String> {
+    let pem = pem::parse(public_key).map_err(|err| err.to_string())?;
+
+    let info = match pem.tag() {
+        "RSA PUBLIC KEY" => SubjectPublicKeyInfo {
+            algorithm: oid!(1, 2, 840, 113549, 1, 1, 1),
+            parameters: None,
+            key: pem.contents().to_vec(),
+        },
+        "PUBLIC KEY" => subject_public_key_info(pem.contents())?,
+        tag => return Err(format!("unsupported PEM tag {tag}")),
+    };
+
+    let parameters = match algorithm {
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512
+            if info.algorithm == oid!(1, 2, 840, 113549, 1, 1, 1) =>
+        {
+            rsa_parameters(&info.key)?
+        }
+        Algorithm::ES256
+            if info.algorithm == oid!(1, 2, 840, 10045, 2, 1)
+                && info.parameters == Some(oid!(1, 2, 840, 10045, 3, 1, 7)) =>
+        {
+            ec_parameters(EllipticCurve::P256, 32, &info.key)?
+        }
+        Algorithm::ES384
+            if info.algorithm == oid!(1, 2, 840, 10045, 2, 1)
+                && info.parameters == Some(oid!(1, 3, 132, 0, 34)) =>
+        {
+            ec_parameters(EllipticCurve::P384, 48, &info.key)?
+        }
+        Algorithm::EdDSA if info.algorithm == oid!(1, 3, 101, 112) => {
+            AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(&info.key),
+            })
+        }
+        _ => {
+            return Err(format!(
+                "key type does not match the {algorithm:?} algorithm"
+            ));
+        }
+    };
+
+    Ok(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm(algorithm)),
+            key_id: Some(kid.to_owned()),
+            ..Default::default()
+        },
+        algorithm: parameters,
+    })
+}
+
+fn subject_public_key_info(der: &[u8]) -> Result<SubjectPublicKeyInfo, String> {
+    match from_der(der).map_err(|err| err.to_string())?.as_slice() {
+        [ASN1Block::Sequence(_, info)] => match info.as_slice() {
+            [
+                ASN1Block::Sequence(_, algorithm),
+                ASN1Block::BitString(_, _, key),
+            ] => match algorithm.as_slice() {
+                [ASN1Block::ObjectIdentifier(_, algorithm), parameters @ ..] => {
+                    Ok(SubjectPublicKeyInfo {
+                        algorithm: algorithm.clone(),
+                        parameters: match parameters {
+                            [ASN1Block::ObjectIdentifier(_, parameters)] => {
+                                Some(parameters.clone())
+                            }
+                            _ => None,
+                        },
+                        key: key.clone(),
+                    })
+                }
+                _ => Err("malformed AlgorithmIdentifier".to_owned()),
+            },
+            _ => Err("malformed SubjectPublicKeyInfo".to_owned()),
+        },
+        _ => Err("malformed SubjectPublicKeyInfo".to_owned()),
+    }
+}
+
+fn rsa_parameters(key: &[u8]) -> Result<AlgorithmParameters, String> {
+    match from_der(key).map_err(|err| err.to_string())?.as_slice() {
+        [ASN1Block::Sequence(_, items)] => match items.as_slice() {
+            [ASN1Block::Integer(_, n), ASN1Block::Integer(_, e)] => {
+                Ok(AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(n.to_bytes_be().1),
+                    e: URL_SAFE_NO_PAD.encode(e.to_bytes_be().1),
+                }))
+            }
+            _ => Err("malformed RSA public key".to_owned()),
+        },
+        _ => Err("malformed RSA public key".to_owned()),
+    }
+}
+
+fn ec_parameters(
+    curve: EllipticCurve,
+    size: usize,
+    key: &[u8],
+) -> Result<AlgorithmParameters, String> {
+    // Only uncompressed points (0x04 || X || Y) are supported
+    match key.split_first() {
+        Some((0x04, point)) if point.len() == 2 * size => Ok(AlgorithmParameters::EllipticCurve(
+            EllipticCurveKeyParameters {
+                key_type: EllipticCurveKeyType::EC,
+                curve,
+                x: URL_SAFE_NO_PAD.encode(&point[..size]),
+                y: URL_SAFE_NO_PAD.encode(&point[size..]),
+            },
+        )),
+        _ => Err("unsupported elliptic curve point encoding".to_owned()),
+    }
+}
+
+fn key_algorithm(algorithm: Algorithm) -> KeyAlgorithm {
+    match algorithm {
+        Algorithm::RS384 => KeyAlgorithm::RS384,
+        Algorithm::RS512 => KeyAlgorithm::RS512,
+        Algorithm::PS256 => KeyAlgorithm::PS256,
+        Algorithm::PS384 => KeyAlgorithm::PS384,
+        Algorithm::PS512 => KeyAlgorithm::PS512,
+        Algorithm::ES256 => KeyAlgorithm::ES256,
+        Algorithm::ES384 => KeyAlgorithm::ES384,
+        Algorithm::EdDSA => KeyAlgorithm::EdDSA,
+        _ => KeyAlgorithm::RS256,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::utils::crypto::{generate_keypair, generate_pem};
+
+    use rstest::*;
+
+    #[test]
+    fn test_missing_signing_key() {
+        let result = Keyring::new("missing", vec![generate_keypair("test", Algorithm::RS256)]);
+
+        assert!(matches!(result, Err(KeyringError::MissingSigningKey(_))));
+    }
+
+    #[test]
+    fn test_signing_key_without_private_key() {
+        let (_, public_key) = generate_pem(Algorithm::RS256);
+        let key = KeyPair::from_pem("test", Algorithm::RS256, None, public_key).unwrap();
+
+        let result = Keyring::new("test", vec![key]);
+
+        assert!(matches!(result, Err(KeyringError::MissingPrivateKey(_))));
+    }
+
+    #[test]
+    fn test_unsupported_algorithm() {
+        let (private_key, public_key) = generate_pem(Algorithm::RS256);
+
+        let result = KeyPair::from_pem("test", Algorithm::HS256, Some(private_key), public_key);
+
+        assert!(matches!(
+            result,
+            Err(KeyringError::UnsupportedAlgorithm(Algorithm::HS256))
+        ));
+    }
+
+    #[rstest]
+    #[case::rsa_as_ecdsa(Algorithm::RS256, Algorithm::ES256)]
+    #[case::ecdsa_as_eddsa(Algorithm::ES256, Algorithm::EdDSA)]
+    #[case::eddsa_as_rsa(Algorithm::EdDSA, Algorithm::RS256)]
+    fn test_key_type_mismatch(#[case] key_type: Algorithm, #[case] algorithm: Algorithm) {
+        let (_, public_key) = generate_pem(key_type);
+
+        let result = KeyPair::from_pem("test", algorithm, None, public_key);
+
+        assert!(matches!(result, Err(KeyringError::InvalidPublicKey(_, _))));
+    }
+
+    #[test]
+    fn test_mixed_algorithms() {
+        let result = Keyring::new(
+            "rsa",
+            vec![
+                generate_keypair("rsa", Algorithm::RS256),
+                generate_keypair("eddsa", Algorithm::EdDSA),
+            ],
+        );
+
+        assert!(matches!(
+            result,
+            Err(KeyringError::AlgorithmMismatch(_, Algorithm::RS256))
+        ));
+    }
+
+    #[rstest]
+    #[case::rsa(Algorithm::RS256)]
+    #[case::ecdsa(Algorithm::ES256)]
+    #[case::eddsa(Algorithm::EdDSA)]
+    fn test_jwks(#[case] algorithm: Algorithm) {
+        let (_, public_key) = generate_pem(algorithm);
+        let retired = KeyPair::from_pem("retired", algorithm, None, public_key).unwrap();
+
+        let keyring = Keyring::new(
+            "active",
+            vec![generate_keypair("active", algorithm), retired],
+        )
+        .unwrap();
+
+        let jwks = keyring.jwks();
+
+        assert_eq!(jwks.keys.len(), 2);
+        assert!(jwks.find("active").is_some());
+        assert!(jwks.find("retired").is_some());
+
+        for jwk in &jwks.keys {
+            assert!(DecodingKey::from_jwk(jwk).is_ok());
+        }
+    }
+}
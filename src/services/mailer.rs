@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+
+use crate::config::MailerConfig;
+use crate::domain::error::{AppError, AppResult};
+use crate::domain::models::mail::Mail;
+use crate::domain::services::mailer::MailerService;
+
+// Only records that a mail went out, bodies carry reset and verification tokens and stay out of
+// the logs
+pub struct LogMailerServiceImpl;
+
+#[async_trait]
+impl MailerService for LogMailerServiceImpl {
+    async fn send(&self, mail: Mail) -> AppResult<()> {
+        tracing::info!(to = %mail.to, subject = %mail.subject, "mail sent");
+
+        Ok(())
+    }
+}
+
+// Hands outgoing mail to a transactional mail API as a JSON POST
+pub struct HttpMailerServiceImpl {
+    from: String,
+    endpoint: String,
+    api_key: Option<String>,
+    client: Client,
+}
+
+impl HttpMailerServiceImpl {
+    pub fn new(config: &MailerConfig) -> Self {
+        Self {
+            from: config.from.clone(),
+            endpoint: config.endpoint.clone(),
+            api_key: config.api_key.clone(),
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl MailerService for HttpMailerServiceImpl {
+    async fn send(&self, mail: Mail) -> AppResult<()> {
+        let mut request = self.client.post(&self.endpoint).json(&json!({
+            "from": self.from,
+            "to": mail.to,
+            "subject": mail.subject,
+            "text": mail.body,
+        }));
+
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        request
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|err| AppError::ServiceUnavailable().trace(&err.to_string()))?;
+
+        tracing::info!(to = %mail.to, subject = %mail.subject, "mail sent");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    pub struct MailerServiceImpl {
+        pub mails: Mutex<Vec<Mail>>,
+    }
+
+    #[async_trait]
+    impl MailerService for MailerServiceImpl {
+        async fn send(&self, mail: Mail) -> AppResult<()> {
+            self.mails.lock().await.push(mail);
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::config::MailerBackend;
+
+    fn mail() -> Mail {
+        Mail {
+            to: "test_account@spacecraft.com".to_string(),
+            subject: "Reset your password".to_string(),
+            body: "token".to_string(),
+        }
+    }
+
+    fn mailer(server: &MockServer) -> HttpMailerServiceImpl {
+        HttpMailerServiceImpl::new(&MailerConfig {
+            backend: MailerBackend::Http,
+            from: "no-reply@spacecraft.com".to_string(),
+            endpoint: format!("{}/send", server.uri()),
+            api_key: Some("api_key".to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_http_send() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/send"))
+            .and(header("authorization", "Bearer api_key"))
+            .and(body_json(json!({
+                "from": "no-reply@spacecraft.com",
+                "to": "test_account@spacecraft.com",
+                "subject": "Reset your password",
+                "text": "token",
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(mailer(&server).send(mail()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_http_send_rejected() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let err = mailer(&server).send(mail()).await.unwrap_err();
+
+        assert_eq!(err.code, 503);
+    }
+}
@@ -1,2 +1,21 @@
 pub mod account;
+pub mod account_deletion;
+pub mod account_status;
+pub mod api_key;
+pub mod crypto;
+pub mod email_verification;
+pub mod hasher;
 pub mod jsonwebtoken;
+pub mod keyring;
+pub mod mailer;
+pub mod oauth;
+pub mod oidc;
+pub mod password_history;
+pub mod password_policy;
+pub mod password_reset;
+pub mod rate_limit;
+pub mod refresh_token;
+pub mod session;
+pub mod signin_lockout;
+pub mod totp;
+pub mod webauthn;
@@ -0,0 +1,1054 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Url;
+use uuid::Uuid;
+
+use crate::config::OAuthConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::{Account, Permission},
+    models::oauth::{
+        AuthorizationRequest, ClientCredentials, CreateAuthorizationCode, CreateOAuthClient,
+        CreateOAuthRefreshToken, GrantType, IssuedOAuthClient, NewOAuthClient, OAuthClient,
+        OAuthToken, TokenGrant, TokenIntrospection, TokenType,
+    },
+    repositories::account::AccountRepository,
+    repositories::oauth::OAuthRepository,
+    services::jsonwebtoken::JsonWebTokenService,
+    services::oauth::OAuthService,
+};
+use crate::services::crypto::{hash_token, random_token};
+
+// Error codes from RFC 6749, section 5.2
+const INVALID_GRANT: &str = "invalid_grant";
+const INVALID_SCOPE: &str = "invalid_scope";
+const UNAUTHORIZED_CLIENT: &str = "unauthorized_client";
+
+pub struct OAuthServiceImpl {
+    code_ttl: i64,
+    refresh_ttl: i64,
+    repository: Arc<dyn OAuthRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+}
+
+impl OAuthServiceImpl {
+    pub fn new(
+        config: &OAuthConfig,
+        repository: Arc<dyn OAuthRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    ) -> Self {
+        Self {
+            code_ttl: config.code_ttl,
+            refresh_ttl: config.refresh_ttl,
+            repository,
+            account_repository,
+            jsonwebtoken_service,
+        }
+    }
+
+    async fn authenticate_client(&self, credentials: &ClientCredentials) -> AppResult<OAuthClient> {
+        let client = self
+            .repository
+            .find_client(&credentials.client_id)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let authenticated = match (&client.secret_hash, &credentials.client_secret) {
+            (Some(secret_hash), Some(secret)) => *secret_hash == hash_token(secret),
+            (None, None) => true,
+            _ => false,
+        };
+
+        if !authenticated {
+            return Err(AppError::Unauthorized());
+        }
+
+        Ok(client)
+    }
+
+    async fn find_account(&self, id: &str) -> AppResult<Account> {
+        self.account_repository
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::BadRequest(INVALID_GRANT))
+    }
+
+    async fn issue_refresh_token(
+        &self,
+        client: &str,
+        account: &str,
+        family: String,
+        scopes: Vec<Permission>,
+    ) -> AppResult<String> {
+        let token = random_token();
+
+        self.repository
+            .create_refresh_token(CreateOAuthRefreshToken {
+                client: client.to_owned(),
+                account: account.to_owned(),
+                token_hash: hash_token(&token),
+                family,
+                scopes,
+                expiration: Utc::now().timestamp() + self.refresh_ttl,
+            })
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn authorization_code_grant(
+        &self,
+        client: &OAuthClient,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> AppResult<OAuthToken> {
+        let stored = self
+            .repository
+            .consume_code(&hash_token(code))
+            .await?
+            .ok_or(AppError::BadRequest(INVALID_GRANT))?;
+
+        // The verifier proves the token request comes from whoever started the authorization
+        if stored.client != client.id
+            || stored.redirect_uri != redirect_uri
+            || stored.code_challenge != hash_token(code_verifier)
+        {
+            return Err(AppError::BadRequest(INVALID_GRANT));
+        }
+
+        let account = self.find_account(&stored.account).await?;
+
+        let refresh_token = match client.grant_types.contains(&GrantType::RefreshToken) {
+            true => Some(
+                self.issue_refresh_token(
+                    &client.id,
+                    &account.id,
+                    Uuid::new_v4().to_string(),
+                    stored.scopes.clone(),
+                )
+                .await?,
+            ),
+            false => None,
+        };
+
+        self.access_token(&account.id, client, &stored.scopes, &account, refresh_token)
+    }
+
+    async fn client_credentials_grant(
+        &self,
+        client: &OAuthClient,
+        scopes: Option<Vec<Permission>>,
+    ) -> AppResult<OAuthToken> {
+        // Public clients cannot keep a secret, so they can never act on their own behalf
+        if client.secret_hash.is_none() {
+            return Err(AppError::BadRequest(UNAUTHORIZED_CLIENT));
+        }
+
+        let scopes = scopes.unwrap_or_else(|| client.scopes.clone());
+
+        if !scopes.iter().all(|scope| client.scopes.contains(scope)) {
+            return Err(AppError::BadRequest(INVALID_SCOPE));
+        }
+
+        let owner = self
+            .account_repository
+            .find_by_id(&client.owner)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        self.access_token(&client.id, client, &scopes, &owner, None)
+    }
+
+    async fn refresh_token_grant(
+        &self,
+        client: &OAuthClient,
+        refresh_token: &str,
+        scopes: Option<Vec<Permission>>,
+    ) -> AppResult<OAuthToken> {
+        let stored = self
+            .repository
+            .find_refresh_token(&hash_token(refresh_token))
+            .await?
+            .filter(|stored| stored.client == client.id)
+            .ok_or(AppError::BadRequest(INVALID_GRANT))?;
+
+        if stored.revoked || stored.expiration <= Utc::now().timestamp() {
+            return Err(AppError::BadRequest(INVALID_GRANT));
+        }
+
+        let scopes = scopes.unwrap_or_else(|| stored.scopes.clone());
+
+        if !scopes.iter().all(|scope| stored.scopes.contains(scope)) {
+            return Err(AppError::BadRequest(INVALID_SCOPE));
+        }
+
+        // A rotated token presented again means it has leaked: kill the whole family
+        if stored.rotated || !self.repository.rotate_refresh_token(&stored.id).await? {
+            self.repository
+                .revoke_refresh_family(&stored.family)
+                .await?;
+            return Err(AppError::BadRequest(INVALID_GRANT));
+        }
+
+        let account = self.find_account(&stored.account).await?;
+
+        let refresh_token = self
+            .issue_refresh_token(&client.id, &account.id, stored.family, stored.scopes)
+            .await?;
+
+        self.access_token(&account.id, client, &scopes, &account, Some(refresh_token))
+    }
+
+    // Granted scopes are narrowed to what the account still holds, as with scoped API keys. When
+    // the client acts on its own behalf the account is its owner and is carried as such
+    fn access_token(
+        &self,
+        sub: &str,
+        client: &OAuthClient,
+        scopes: &[Permission],
+        account: &Account,
+        refresh_token: Option<String>,
+    ) -> AppResult<OAuthToken> {
+        account.status.ensure_active()?;
+
+        let permissions = account.effective_permissions();
+
+        let scopes: Vec<Permission> = scopes
+            .iter()
+            .filter(|scope| permissions.contains(scope))
+            .copied()
+            .collect();
+
+        let owner = (sub != account.id).then_some(account.id.as_str());
+
+        let access_token = self.jsonwebtoken_service.generate_client_token(
+            sub,
+            &client.id,
+            owner,
+            scopes.clone(),
+        )?;
+
+        Ok(OAuthToken {
+            access_token,
+            refresh_token,
+            scopes,
+        })
+    }
+}
+
+#[async_trait]
+impl OAuthService for OAuthServiceImpl {
+    async fn register_client(
+        &self,
+        owner: &Account,
+        client: NewOAuthClient,
+    ) -> AppResult<IssuedOAuthClient> {
+        // A client can never be granted more than its owner currently holds
+        let permissions = owner.effective_permissions();
+
+        if !client
+            .scopes
+            .iter()
+            .all(|scope| permissions.contains(scope))
+        {
+            return Err(AppError::Forbidden());
+        }
+
+        if client.grant_types.contains(&GrantType::ClientCredentials) && !client.confidential {
+            return Err(AppError::BadRequest(
+                "Public clients cannot use the client credentials grant",
+            ));
+        }
+
+        if client.grant_types.contains(&GrantType::AuthorizationCode)
+            && client.redirect_uris.is_empty()
+        {
+            return Err(AppError::BadRequest(
+                "Authorization code clients need at least one redirect URI",
+            ));
+        }
+
+        let client_secret = client.confidential.then(random_token);
+
+        let created = self
+            .repository
+            .create_client(CreateOAuthClient {
+                owner: owner.id.clone(),
+                name: client.name,
+                secret_hash: client_secret.as_deref().map(hash_token),
+                redirect_uris: client.redirect_uris,
+                grant_types: client.grant_types,
+                scopes: client.scopes,
+            })
+            .await?;
+
+        Ok(IssuedOAuthClient {
+            client_secret,
+            client: created,
+        })
+    }
+
+    async fn list_clients(&self, owner: &str) -> AppResult<Vec<OAuthClient>> {
+        Ok(self.repository.find_clients_by_owner(owner).await?)
+    }
+
+    async fn delete_client(&self, owner: &str, id: &str) -> AppResult<()> {
+        if !self.repository.delete_client(id, owner).await? {
+            return Err(AppError::NotFound("OAuth client not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn validate_authorization(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> AppResult<OAuthClient> {
+        let client = self
+            .repository
+            .find_client(&request.client_id)
+            .await?
+            .ok_or(AppError::NotFound("OAuth client not found"))?;
+
+        if !client.grant_types.contains(&GrantType::AuthorizationCode) {
+            return Err(AppError::BadRequest(UNAUTHORIZED_CLIENT));
+        }
+
+        // Only exact matches, so codes are never delivered to an unregistered location
+        if !client.redirect_uris.contains(&request.redirect_uri) {
+            return Err(AppError::BadRequest("Redirect URI is not registered"));
+        }
+
+        if request.code_challenge_method != "S256" {
+            return Err(AppError::BadRequest(
+                "Only the S256 code challenge method is supported",
+            ));
+        }
+
+        if !request
+            .scopes
+            .iter()
+            .all(|scope| client.scopes.contains(scope))
+        {
+            return Err(AppError::BadRequest(INVALID_SCOPE));
+        }
+
+        Ok(client)
+    }
+
+    async fn authorize(
+        &self,
+        account: &str,
+        request: AuthorizationRequest,
+        approved: bool,
+    ) -> AppResult<String> {
+        let client = self.validate_authorization(&request).await?;
+
+        let mut url = Url::parse(&request.redirect_uri)
+            .map_err(|_| AppError::BadRequest("Redirect URI is not registered"))?;
+
+        if approved {
+            let code = random_token();
+
+            self.repository
+                .create_code(CreateAuthorizationCode {
+                    client: client.id,
+                    account: account.to_owned(),
+                    code_hash: hash_token(&code),
+                    redirect_uri: request.redirect_uri.clone(),
+                    scopes: request.scopes,
+                    code_challenge: request.code_challenge,
+                    expiration: Utc::now().timestamp() + self.code_ttl,
+                })
+                .await?;
+
+            url.query_pairs_mut().append_pair("code", &code);
+        } else {
+            url.query_pairs_mut().append_pair("error", "access_denied");
+        }
+
+        if let Some(state) = &request.state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+
+        Ok(url.to_string())
+    }
+
+    async fn token(
+        &self,
+        credentials: ClientCredentials,
+        grant: TokenGrant,
+    ) -> AppResult<OAuthToken> {
+        let client = self.authenticate_client(&credentials).await?;
+
+        let grant_type = match &grant {
+            TokenGrant::AuthorizationCode { .. } => GrantType::AuthorizationCode,
+            TokenGrant::ClientCredentials { .. } => GrantType::ClientCredentials,
+            TokenGrant::RefreshToken { .. } => GrantType::RefreshToken,
+        };
+
+        if !client.grant_types.contains(&grant_type) {
+            return Err(AppError::BadRequest(UNAUTHORIZED_CLIENT));
+        }
+
+        match grant {
+            TokenGrant::AuthorizationCode {
+                code,
+                redirect_uri,
+                code_verifier,
+            } => {
+                self.authorization_code_grant(&client, &code, &redirect_uri, &code_verifier)
+                    .await
+            }
+            TokenGrant::ClientCredentials { scopes } => {
+                self.client_credentials_grant(&client, scopes).await
+            }
+            TokenGrant::RefreshToken {
+                refresh_token,
+                scopes,
+            } => {
+                self.refresh_token_grant(&client, &refresh_token, scopes)
+                    .await
+            }
+        }
+    }
+
+    async fn introspect(
+        &self,
+        credentials: ClientCredentials,
+        token: &str,
+    ) -> AppResult<Option<TokenIntrospection>> {
+        let client = self.authenticate_client(&credentials).await?;
+
+        // Public clients have no secret, so anyone could introspect under their id
+        if client.secret_hash.is_none() {
+            return Err(AppError::Unauthorized());
+        }
+
+        // Refresh tokens are only ever disclosed to the client they were issued to
+        if let Some(stored) = self
+            .repository
+            .find_refresh_token(&hash_token(token))
+            .await?
+        {
+            let active = stored.client == client.id
+                && !stored.rotated
+                && !stored.revoked
+                && stored.expiration > Utc::now().timestamp();
+
+            return Ok(active.then(|| TokenIntrospection {
+                token_type: TokenType::RefreshToken,
+                client_id: Some(stored.client),
+                sub: stored.account,
+                scopes: stored.scopes,
+                expiration: Some(stored.expiration),
+                issued_at: None,
+            }));
+        }
+
+        match self.jsonwebtoken_service.validate_token(token).await {
+            Ok(claims) => Ok(Some(TokenIntrospection {
+                token_type: TokenType::AccessToken,
+                client_id: claims.client_id,
+                sub: claims.sub,
+                scopes: claims.permissions,
+                expiration: claims.exp.map(|exp| exp as i64),
+                issued_at: Some(claims.iat as i64),
+            })),
+            // Tokens of suspended accounts are reported as inactive rather than failing the call
+            Err(err) if err.code == 401 || err.code == 423 => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Unknown tokens and tokens issued to other clients are ignored, as RFC 7009 allows
+    async fn revoke(&self, credentials: ClientCredentials, token: &str) -> AppResult<()> {
+        let client = self.authenticate_client(&credentials).await?;
+
+        if let Some(stored) = self
+            .repository
+            .find_refresh_token(&hash_token(token))
+            .await?
+        {
+            if stored.client == client.id {
+                self.repository
+                    .revoke_refresh_family(&stored.family)
+                    .await?;
+            }
+
+            return Ok(());
+        }
+
+        match self.jsonwebtoken_service.validate_token(token).await {
+            Ok(claims) if claims.client_id.as_deref() == Some(client.id.as_str()) => {
+                self.jsonwebtoken_service.revoke_token(&claims).await
+            }
+            Ok(_) => Ok(()),
+            Err(err) if err.code == 401 => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::oauth::mock::OAuthRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
+    use rstest::*;
+
+    const REDIRECT_URI: &str = "https://client.spacecraft.com/callback";
+    const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+    fn account(id: &str, roles: Vec<Role>) -> Account {
+        Account {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            email: format!("{id}@spacecraft.com"),
+            password: "p4ssw0rd".to_string(),
+            roles,
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn new_client(confidential: bool, grant_types: Vec<GrantType>) -> NewOAuthClient {
+        NewOAuthClient {
+            name: "Dashboard".to_string(),
+            confidential,
+            redirect_uris: vec![REDIRECT_URI.to_string()],
+            grant_types,
+            scopes: vec![Permission::AccountsRead],
+        }
+    }
+
+    fn authorization_request(client_id: &str) -> AuthorizationRequest {
+        AuthorizationRequest {
+            client_id: client_id.to_string(),
+            redirect_uri: REDIRECT_URI.to_string(),
+            scopes: vec![Permission::AccountsRead],
+            state: Some("xyz".to_string()),
+            code_challenge: hash_token(CODE_VERIFIER),
+            code_challenge_method: "S256".to_string(),
+        }
+    }
+
+    fn credentials(issued: &IssuedOAuthClient) -> ClientCredentials {
+        ClientCredentials {
+            client_id: issued.client.id.clone(),
+            client_secret: issued.client_secret.clone(),
+        }
+    }
+
+    fn query_param(url: &str, name: &str) -> Option<String> {
+        Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    #[fixture]
+    fn service() -> OAuthServiceImpl {
+        let account_repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![
+                account("admin", vec![Role::Admin]),
+                account("user", vec![Role::User]),
+            ]),
+        });
+
+        let jsonwebtoken_service = Arc::new(JsonWebTokenServiceImpl::new(
+            generate_keyring(),
+            &jsonwebtoken_config(),
+            Arc::new(RevokedTokenRepositoryImpl {
+                tokens: Mutex::new(vec![]),
+            }),
+            account_repository.clone(),
+            Arc::new(SessionRepositoryImpl {
+                sessions: Mutex::new(vec![]),
+            }),
+        ));
+
+        OAuthServiceImpl::new(
+            &OAuthConfig {
+                code_ttl: 60,
+                refresh_ttl: 3600,
+            },
+            Arc::new(OAuthRepositoryImpl {
+                clients: Mutex::new(vec![]),
+                codes: Mutex::new(vec![]),
+                refresh_tokens: Mutex::new(vec![]),
+            }),
+            account_repository,
+            jsonwebtoken_service,
+        )
+    }
+
+    async fn authorization_code(service: &OAuthServiceImpl, client_id: &str) -> String {
+        let url = service
+            .authorize("admin", authorization_request(client_id), true)
+            .await
+            .unwrap();
+
+        assert_eq!(query_param(&url, "state").as_deref(), Some("xyz"));
+
+        query_param(&url, "code").unwrap()
+    }
+
+    fn code_grant(code: &str, code_verifier: &str) -> TokenGrant {
+        TokenGrant::AuthorizationCode {
+            code: code.to_string(),
+            redirect_uri: REDIRECT_URI.to_string(),
+            code_verifier: code_verifier.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorization_code_flow(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(
+                    false,
+                    vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert!(issued.client_secret.is_none());
+
+        let code = authorization_code(&service, &issued.client.id).await;
+
+        let token = service
+            .token(credentials(&issued), code_grant(&code, CODE_VERIFIER))
+            .await
+            .unwrap();
+
+        assert_eq!(token.scopes, vec![Permission::AccountsRead]);
+        assert!(token.refresh_token.is_some());
+
+        // Introspection is reserved to clients that can authenticate with a secret
+        let err = service
+            .introspect(credentials(&issued), &token.access_token.token)
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 401);
+
+        let resource = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+
+        let introspection = service
+            .introspect(credentials(&resource), &token.access_token.token)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(introspection.token_type, TokenType::AccessToken);
+        assert_eq!(introspection.sub, "admin");
+        assert_eq!(introspection.client_id, Some(issued.client.id.clone()));
+        assert_eq!(introspection.scopes, vec![Permission::AccountsRead]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorization_code_bad_verifier(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(false, vec![GrantType::AuthorizationCode]),
+            )
+            .await
+            .unwrap();
+
+        let code = authorization_code(&service, &issued.client.id).await;
+
+        let err = service
+            .token(
+                credentials(&issued),
+                code_grant(&code, "wrong-verifier-wrong-verifier-wrong-verifier"),
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+        assert_eq!(err.message, INVALID_GRANT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorization_code_replay(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(false, vec![GrantType::AuthorizationCode]),
+            )
+            .await
+            .unwrap();
+
+        let code = authorization_code(&service, &issued.client.id).await;
+
+        let token = service
+            .token(credentials(&issued), code_grant(&code, CODE_VERIFIER))
+            .await
+            .unwrap();
+
+        assert!(token.refresh_token.is_none());
+
+        let err = service
+            .token(credentials(&issued), code_grant(&code, CODE_VERIFIER))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorization_denied(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(false, vec![GrantType::AuthorizationCode]),
+            )
+            .await
+            .unwrap();
+
+        let url = service
+            .authorize("admin", authorization_request(&issued.client.id), false)
+            .await
+            .unwrap();
+
+        assert!(url.starts_with(REDIRECT_URI));
+        assert_eq!(query_param(&url, "error").as_deref(), Some("access_denied"));
+        assert!(query_param(&url, "code").is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorization_unregistered_redirect(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(false, vec![GrantType::AuthorizationCode]),
+            )
+            .await
+            .unwrap();
+
+        let mut request = authorization_request(&issued.client.id);
+        request.redirect_uri = "https://attacker.com/callback".to_string();
+
+        let err = service
+            .validate_authorization(&request)
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_client_credentials(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+
+        let token = service
+            .token(
+                credentials(&issued),
+                TokenGrant::ClientCredentials { scopes: None },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(token.scopes, vec![Permission::AccountsRead]);
+        assert!(token.refresh_token.is_none());
+
+        let mut wrong = credentials(&issued);
+        wrong.client_secret = Some("wrong".to_string());
+
+        let err = service
+            .token(wrong, TokenGrant::ClientCredentials { scopes: None })
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 401);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_client_credentials_suspended_owner(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+
+        let token = service
+            .token(
+                credentials(&issued),
+                TokenGrant::ClientCredentials { scopes: None },
+            )
+            .await
+            .unwrap()
+            .access_token;
+
+        // The token's subject is the client, the owner is checked through its own claim
+        service.jsonwebtoken_service.suspend("admin");
+
+        assert_eq!(
+            service
+                .jsonwebtoken_service
+                .validate_token(&token.token)
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+
+        service
+            .account_repository
+            .update_status("admin", AccountStatus::Suspended)
+            .await
+            .unwrap();
+
+        let err = service
+            .token(
+                credentials(&issued),
+                TokenGrant::ClientCredentials { scopes: None },
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err, AppError::Suspended());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_public_client_credentials(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+
+        let err = service
+            .register_client(
+                &admin,
+                new_client(false, vec![GrantType::ClientCredentials]),
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_scope_escalation(service: OAuthServiceImpl) {
+        let user = account("user", vec![Role::User]);
+
+        let err = service
+            .register_client(&user, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 403);
+
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+
+        let err = service
+            .token(
+                credentials(&issued),
+                TokenGrant::ClientCredentials {
+                    scopes: Some(vec![Permission::AccountsWrite]),
+                },
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+        assert_eq!(err.message, INVALID_SCOPE);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_refresh_token_rotation_and_reuse(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(
+                    true,
+                    vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+                ),
+            )
+            .await
+            .unwrap();
+
+        let code = authorization_code(&service, &issued.client.id).await;
+
+        let first = service
+            .token(credentials(&issued), code_grant(&code, CODE_VERIFIER))
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+
+        let refresh = |refresh_token: &str| TokenGrant::RefreshToken {
+            refresh_token: refresh_token.to_string(),
+            scopes: None,
+        };
+
+        let second = service
+            .token(credentials(&issued), refresh(&first))
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+
+        let err = service
+            .token(credentials(&issued), refresh(&first))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+
+        // Reusing the rotated token revokes every token of the family
+        let err = service
+            .token(credentials(&issued), refresh(&second))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, 400);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_refresh_token(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(
+                &admin,
+                new_client(
+                    true,
+                    vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
+                ),
+            )
+            .await
+            .unwrap();
+
+        let code = authorization_code(&service, &issued.client.id).await;
+
+        let refresh_token = service
+            .token(credentials(&issued), code_grant(&code, CODE_VERIFIER))
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+
+        let introspection = service
+            .introspect(credentials(&issued), &refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(introspection.token_type, TokenType::RefreshToken);
+
+        service
+            .revoke(credentials(&issued), &refresh_token)
+            .await
+            .unwrap();
+
+        assert!(
+            service
+                .introspect(credentials(&issued), &refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_access_token(service: OAuthServiceImpl) {
+        let admin = account("admin", vec![Role::Admin]);
+        let issued = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+        let other = service
+            .register_client(&admin, new_client(true, vec![GrantType::ClientCredentials]))
+            .await
+            .unwrap();
+
+        let token = service
+            .token(
+                credentials(&issued),
+                TokenGrant::ClientCredentials { scopes: None },
+            )
+            .await
+            .unwrap()
+            .access_token
+            .token;
+
+        // Revocation by another client is silently ignored
+        service.revoke(credentials(&other), &token).await.unwrap();
+
+        assert!(
+            service
+                .introspect(credentials(&issued), &token)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        service.revoke(credentials(&issued), &token).await.unwrap();
+
+        assert!(
+            service
+                .introspect(credentials(&issued), &token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,700 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use crate::config::{EmailVerificationConfig, OidcConfig, OidcProviderConfig};
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::{Account, CreateAccount},
+    models::oidc::{CreateAccountIdentity, CreateOidcState, OidcAuthorization},
+    repositories::account::AccountRepository,
+    repositories::oidc::OidcRepository,
+    services::email_verification::EmailVerificationService,
+    services::oidc::OidcService,
+};
+use crate::services::crypto::{hash_token, random_token};
+use crate::services::hasher::Argon2Hasher;
+
+// ID tokens signed with a shared secret cannot be verified against the provider's keys
+const ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+const LEEWAY: u64 = 60;
+
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+}
+
+pub struct OidcServiceImpl {
+    ttl: i64,
+    require_verified_email: bool,
+    providers: HashMap<String, OidcProviderConfig>,
+    client: Client,
+    metadata: RwLock<HashMap<String, ProviderMetadata>>,
+    hasher: Arc<Argon2Hasher>,
+    email_verification_service: Arc<dyn EmailVerificationService>,
+    repository: Arc<dyn OidcRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+}
+
+impl OidcServiceImpl {
+    pub fn new(
+        config: &OidcConfig,
+        verification_config: &EmailVerificationConfig,
+        hasher: Arc<Argon2Hasher>,
+        email_verification_service: Arc<dyn EmailVerificationService>,
+        repository: Arc<dyn OidcRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+    ) -> Self {
+        Self {
+            ttl: config.ttl,
+            require_verified_email: verification_config.required,
+            providers: config
+                .providers
+                .iter()
+                .map(|provider| (provider.name.clone(), provider.clone()))
+                .collect(),
+            client: Client::new(),
+            metadata: RwLock::new(HashMap::new()),
+            hasher,
+            email_verification_service,
+            repository,
+            account_repository,
+        }
+    }
+
+    fn provider(&self, name: &str) -> AppResult<&OidcProviderConfig> {
+        self.providers
+            .get(name)
+            .ok_or(AppError::NotFound("Identity provider not found"))
+    }
+
+    // Discovery documents are fetched once per provider and kept for the process lifetime
+    async fn metadata(&self, provider: &OidcProviderConfig) -> AppResult<ProviderMetadata> {
+        if let Some(metadata) = self.metadata.read().unwrap().get(&provider.name) {
+            return Ok(metadata.clone());
+        }
+
+        let metadata: ProviderMetadata = self
+            .client
+            .get(format!(
+                "{}/.well-known/openid-configuration",
+                provider.issuer.trim_end_matches('/')
+            ))
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)?;
+
+        if metadata.issuer != provider.issuer {
+            return Err(AppError::ServiceUnavailable().trace("Identity provider issuer mismatch"));
+        }
+
+        self.metadata
+            .write()
+            .unwrap()
+            .insert(provider.name.clone(), metadata.clone());
+
+        Ok(metadata)
+    }
+
+    async fn exchange_code(
+        &self,
+        provider: &OidcProviderConfig,
+        metadata: &ProviderMetadata,
+        code: &str,
+        code_verifier: &str,
+    ) -> AppResult<String> {
+        let res = self
+            .client
+            .post(&metadata.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &provider.redirect_uri),
+                ("client_id", &provider.client_id),
+                ("client_secret", &provider.client_secret),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .map_err(unavailable)?;
+
+        if !res.status().is_success() {
+            return Err(AppError::Unauthorized());
+        }
+
+        let token: TokenResponse = res.json().await.map_err(|_| AppError::Unauthorized())?;
+
+        Ok(token.id_token)
+    }
+
+    async fn verify_id_token(
+        &self,
+        provider: &OidcProviderConfig,
+        metadata: &ProviderMetadata,
+        id_token: &str,
+        nonce: &str,
+    ) -> AppResult<IdTokenClaims> {
+        let header = decode_header(id_token).map_err(|_| AppError::Unauthorized())?;
+
+        if !ALGORITHMS.contains(&header.alg) {
+            return Err(AppError::Unauthorized());
+        }
+
+        let jwks: JwkSet = self
+            .client
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)?;
+
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None => jwks.keys.first(),
+        }
+        .ok_or_else(AppError::Unauthorized)?;
+
+        let key = DecodingKey::from_jwk(jwk).map_err(|_| AppError::Unauthorized())?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = LEEWAY;
+
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|_| AppError::Unauthorized())?
+            .claims;
+
+        // Binds the ID token to the authorization request that started the flow
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(AppError::Unauthorized());
+        }
+
+        Ok(claims)
+    }
+
+    // A matching email is not enough to take over an account, existing accounts are linked from
+    // a signed in session through the link flow
+    async fn create_account(&self, provider: &str, claims: IdTokenClaims) -> AppResult<Account> {
+        let email = claims.email.ok_or(AppError::BadRequest(
+            "Identity provider did not share an email address",
+        ))?;
+
+        // Deleted accounts keep their email until purged, the unique index would reject it anyway
+        if self.account_repository.is_account(&email).await? {
+            return Err(AppError::Conflict("Account already exists"));
+        }
+
+        let name = claims
+            .name
+            .filter(|name| name.chars().count() > 2)
+            .unwrap_or_else(|| email.clone());
+
+        // The account is only reachable through the provider until a password is set
+        let account = self
+            .account_repository
+            .signup(CreateAccount {
+                name,
+                email: email.clone(),
+                password: self.hasher.hash(&random_token())?,
+            })
+            .await?;
+
+        let account = match claims.email_verified {
+            true => self
+                .account_repository
+                .verify_email(&account.id)
+                .await?
+                .unwrap_or(account),
+            // Verified the same way as a password signup, a failed mail can be requested again
+            false => {
+                if let Err(err) = self.email_verification_service.send(&account).await {
+                    tracing::warn!(error = ?err, "verification email failed");
+                }
+
+                account
+            }
+        };
+
+        self.repository
+            .create_identity(CreateAccountIdentity {
+                account: account.id.clone(),
+                provider: provider.to_owned(),
+                subject: claims.sub,
+                email: Some(email),
+            })
+            .await?;
+
+        Ok(account)
+    }
+
+    async fn link_account(
+        &self,
+        provider: &str,
+        claims: IdTokenClaims,
+        account: &str,
+    ) -> AppResult<Account> {
+        let account = self
+            .account_repository
+            .find_by_id(account)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        self.repository
+            .create_identity(CreateAccountIdentity {
+                account: account.id.clone(),
+                provider: provider.to_owned(),
+                subject: claims.sub,
+                email: claims.email,
+            })
+            .await?;
+
+        Ok(account)
+    }
+}
+
+#[async_trait]
+impl OidcService for OidcServiceImpl {
+    async fn authorize(
+        &self,
+        provider: &str,
+        account: Option<&str>,
+    ) -> AppResult<OidcAuthorization> {
+        let provider = self.provider(provider)?;
+        let metadata = self.metadata(provider).await?;
+
+        let state = random_token();
+        let nonce = random_token();
+        let code_verifier = random_token();
+        let expiration = Utc::now().timestamp() + self.ttl;
+
+        self.repository
+            .create_state(CreateOidcState {
+                account: account.map(str::to_owned),
+                provider: provider.name.clone(),
+                state_hash: hash_token(&state),
+                nonce: nonce.clone(),
+                code_verifier: code_verifier.clone(),
+                expiration,
+            })
+            .await?;
+
+        let mut url = Url::parse(&metadata.authorization_endpoint)
+            .map_err(|err| AppError::ServiceUnavailable().trace(&err.to_string()))?;
+
+        // The S256 code challenge is the unpadded base64url SHA-256 digest of the verifier
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &provider.client_id)
+            .append_pair("redirect_uri", &provider.redirect_uri)
+            .append_pair("scope", &provider.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &hash_token(&code_verifier))
+            .append_pair("code_challenge_method", "S256");
+
+        Ok(OidcAuthorization {
+            url: url.to_string(),
+            state,
+            expiration,
+        })
+    }
+
+    async fn callback(&self, provider: &str, code: &str, state: &str) -> AppResult<Account> {
+        let provider = self.provider(provider)?;
+
+        let stored = self
+            .repository
+            .consume_state(&hash_token(state), &provider.name)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let metadata = self.metadata(provider).await?;
+
+        let id_token = self
+            .exchange_code(provider, &metadata, code, &stored.code_verifier)
+            .await?;
+
+        let claims = self
+            .verify_id_token(provider, &metadata, &id_token, &stored.nonce)
+            .await?;
+
+        let identity = self
+            .repository
+            .find_identity(&provider.name, &claims.sub)
+            .await?;
+
+        let account = match (identity, stored.account) {
+            (Some(identity), Some(account)) if identity.account != account => {
+                return Err(AppError::Conflict(
+                    "Identity already linked to another account",
+                ));
+            }
+            (Some(identity), _) => self
+                .account_repository
+                .find_by_id(&identity.account)
+                .await?
+                .ok_or_else(AppError::Unauthorized)?,
+            (None, Some(account)) => self.link_account(&provider.name, claims, &account).await?,
+            (None, None) => self.create_account(&provider.name, claims).await?,
+        };
+
+        // Same gate as a password signin, deleted accounts are already left out by the lookup
+        account.status.ensure_active()?;
+
+        if self.require_verified_email && account.email_verified_at.is_none() {
+            return Err(AppError::Forbidden());
+        }
+
+        Ok(account)
+    }
+}
+
+fn unavailable(err: reqwest::Error) -> AppError {
+    AppError::ServiceUnavailable().trace(&err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::email_verification::mock::EmailVerificationRepositoryImpl;
+    use crate::infrastructure::repositories::oidc::mock::OidcRepositoryImpl;
+    use crate::services::email_verification::EmailVerificationServiceImpl;
+    use crate::services::mailer::mock::MailerServiceImpl;
+    use crate::tests::utils::crypto::password_hasher;
+    use crate::tests::utils::oidc::{AuthorizationRequest, MockOidcProvider};
+
+    fn account() -> Account {
+        Account {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn service(provider: &MockOidcProvider, accounts: Vec<Account>) -> OidcServiceImpl {
+        let mailer = Arc::new(MailerServiceImpl {
+            mails: Mutex::new(vec![]),
+        });
+
+        new_service(provider, false, accounts, mailer)
+    }
+
+    fn new_service(
+        provider: &MockOidcProvider,
+        require_verified_email: bool,
+        accounts: Vec<Account>,
+        mailer: Arc<MailerServiceImpl>,
+    ) -> OidcServiceImpl {
+        let verification_config = EmailVerificationConfig {
+            required: require_verified_email,
+            ttl: 86400,
+            url: "http://localhost/verify?token=".to_string(),
+        };
+
+        let account_repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(accounts),
+        });
+
+        let email_verification_service = Arc::new(EmailVerificationServiceImpl::new(
+            &verification_config,
+            Arc::new(EmailVerificationRepositoryImpl {
+                tokens: Mutex::new(HashMap::new()),
+            }),
+            account_repository.clone(),
+            mailer,
+        ));
+
+        OidcServiceImpl::new(
+            &OidcConfig {
+                ttl: 600,
+                providers: vec![provider.config("mock")],
+            },
+            &verification_config,
+            Arc::new(password_hasher()),
+            email_verification_service,
+            Arc::new(OidcRepositoryImpl {
+                states: Mutex::new(vec![]),
+                identities: Mutex::new(vec![]),
+            }),
+            account_repository,
+        )
+    }
+
+    async fn authorize(service: &OidcServiceImpl) -> AuthorizationRequest {
+        AuthorizationRequest::parse(&service.authorize("mock", None).await.unwrap().url)
+    }
+
+    async fn authorize_link(service: &OidcServiceImpl, account: &str) -> AuthorizationRequest {
+        AuthorizationRequest::parse(&service.authorize("mock", Some(account)).await.unwrap().url)
+    }
+
+    #[tokio::test]
+    async fn test_authorization_url() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![]);
+
+        let authorization = service.authorize("mock", None).await.unwrap();
+        let request = AuthorizationRequest::parse(&authorization.url);
+
+        assert!(authorization.url.contains("response_type=code"));
+        assert!(authorization.url.contains("client_id=surreal-actix"));
+        assert_eq!(request.code_challenge_method, "S256");
+        assert_eq!(request.state, authorization.state);
+        assert_ne!(request.state, request.nonce);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![]);
+
+        let result = service.authorize("unknown", None).await;
+
+        assert_eq!(result.unwrap_err().code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_callback_links_account() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize_link(&service, "1").await;
+        let claims = provider.claims("sub-1", &request.nonce, "other@spacecraft.com", false);
+        provider.expect_token("code-1", &request, &claims).await;
+
+        let linked = service
+            .callback("mock", "code-1", &request.state)
+            .await
+            .unwrap();
+
+        assert_eq!(linked.id, "1");
+
+        // The second signin resolves the account through the stored identity
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, "changed@spacecraft.com", false);
+        provider.expect_token("code-2", &request, &claims).await;
+
+        let signed_in = service
+            .callback("mock", "code-2", &request.state)
+            .await
+            .unwrap();
+
+        assert_eq!(signed_in.id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_callback_creates_account() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![]);
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, "new@spacecraft.com", true);
+        provider.expect_token("code", &request, &claims).await;
+
+        let account = service
+            .callback("mock", "code", &request.state)
+            .await
+            .unwrap();
+
+        assert_eq!(account.email, "new@spacecraft.com");
+        assert_eq!(account.name, "Oidc Account");
+        assert!(account.email_verified_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_callback_existing_email_conflict() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, &account().email, true);
+        provider.expect_token("code", &request, &claims).await;
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 409);
+    }
+
+    #[tokio::test]
+    async fn test_callback_identity_linked_to_another_account() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![]);
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, "new@spacecraft.com", true);
+        provider.expect_token("code-1", &request, &claims).await;
+
+        service
+            .callback("mock", "code-1", &request.state)
+            .await
+            .unwrap();
+
+        let request = authorize_link(&service, "1").await;
+        let claims = provider.claims("sub-1", &request.nonce, "new@spacecraft.com", true);
+        provider.expect_token("code-2", &request, &claims).await;
+
+        let result = service.callback("mock", "code-2", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 409);
+    }
+
+    #[tokio::test]
+    async fn test_callback_unverified_email() {
+        let provider = MockOidcProvider::start().await;
+        let mailer = Arc::new(MailerServiceImpl {
+            mails: Mutex::new(vec![]),
+        });
+        let service = new_service(&provider, true, vec![], mailer.clone());
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, "new@spacecraft.com", false);
+        provider.expect_token("code", &request, &claims).await;
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 403);
+
+        // The account is created unverified and gets a verification mail like a password signup
+        let mails = mailer.mails.lock().await;
+
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].to, "new@spacecraft.com");
+    }
+
+    #[tokio::test]
+    async fn test_callback_suspended_account() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(
+            &provider,
+            vec![Account {
+                status: AccountStatus::Suspended,
+                ..account()
+            }],
+        );
+
+        let request = authorize_link(&service, "1").await;
+        let claims = provider.claims("sub-1", &request.nonce, &account().email, true);
+        provider.expect_token("code", &request, &claims).await;
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 423);
+    }
+
+    #[tokio::test]
+    async fn test_callback_replayed_state() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", &request.nonce, &account().email, true);
+        provider.expect_token("code", &request, &claims).await;
+
+        service
+            .callback("mock", "code", &request.state)
+            .await
+            .unwrap();
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 401);
+    }
+
+    #[tokio::test]
+    async fn test_callback_nonce_mismatch() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize(&service).await;
+        let claims = provider.claims("sub-1", "other-nonce", &account().email, true);
+        provider.expect_token("code", &request, &claims).await;
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 401);
+    }
+
+    #[tokio::test]
+    async fn test_callback_invalid_audience() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize(&service).await;
+        let mut claims = provider.claims("sub-1", &request.nonce, &account().email, true);
+        claims["aud"] = "another-client".into();
+        provider.expect_token("code", &request, &claims).await;
+
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 401);
+    }
+
+    #[tokio::test]
+    async fn test_callback_rejected_code() {
+        let provider = MockOidcProvider::start().await;
+        let service = service(&provider, vec![account()]);
+
+        let request = authorize(&service).await;
+
+        // No token response is mounted, so the provider answers the exchange with a 404
+        let result = service.callback("mock", "code", &request.state).await;
+
+        assert_eq!(result.unwrap_err().code, 401);
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use crate::config::PasswordHistoryConfig;
+use crate::domain::error::AppResult;
+use crate::domain::models::account::Account;
+use crate::domain::repositories::password_history::PasswordHistoryRepository;
+use crate::services::hasher::Argon2Hasher;
+use crate::services::password_policy::rejection;
+
+pub struct PasswordHistory {
+    size: usize,
+    hasher: Arc<Argon2Hasher>,
+    repository: Arc<dyn PasswordHistoryRepository>,
+}
+
+impl PasswordHistory {
+    pub fn new(
+        config: &PasswordHistoryConfig,
+        hasher: Arc<Argon2Hasher>,
+        repository: Arc<dyn PasswordHistoryRepository>,
+    ) -> Self {
+        Self {
+            size: config.size,
+            hasher,
+            repository,
+        }
+    }
+
+    // The current password is the newest entry, the repository only holds the ones it replaced
+    pub async fn check(
+        &self,
+        field: &'static str,
+        account: &Account,
+        password: &str,
+    ) -> AppResult<()> {
+        if self.size == 0 {
+            return Ok(());
+        }
+
+        let mut hashes = self
+            .repository
+            .find_recent(&account.id, self.size - 1)
+            .await?;
+
+        hashes.push(account.password.clone());
+
+        if hashes
+            .iter()
+            .any(|hash| self.hasher.verify(password, hash).is_ok())
+        {
+            return Err(rejection(field, "Password has been used recently"));
+        }
+
+        Ok(())
+    }
+
+    // Takes the account as it is before the change, and runs first so a failed write cannot let
+    // the replaced password slip out of the history
+    pub async fn record(&self, account: &Account) -> AppResult<()> {
+        Ok(self
+            .repository
+            .add(
+                &account.id,
+                account.password.clone(),
+                self.size.saturating_sub(1),
+            )
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::tests::utils::crypto::{password_hasher, password_history as history};
+
+    fn account(password: &str) -> Account {
+        Account {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: password_hasher().hash(password).unwrap(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_current_password() {
+        let history = history(3);
+
+        let err = history
+            .check("new_password", &account("p4ssw0rd1"), "p4ssw0rd1")
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("new_password"));
+        assert!(err.message.contains("Password has been used recently"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_recent_passwords_and_prunes_older_ones() {
+        let history = history(3);
+
+        for password in ["p4ssw0rd1", "p4ssw0rd2", "p4ssw0rd3"] {
+            history.record(&account(password)).await.unwrap();
+        }
+
+        let current = account("p4ssw0rd4");
+
+        for (password, reused) in [
+            ("p4ssw0rd4", true),
+            ("p4ssw0rd3", true),
+            ("p4ssw0rd2", true),
+            ("p4ssw0rd1", false),
+        ] {
+            let result = history.check("password", &current, password).await;
+
+            assert_eq!(result.is_err(), reused);
+        }
+
+        let kept = history.repository.find_recent("1", 10).await.unwrap();
+
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_disabled() {
+        let history = history(0);
+        let current = account("p4ssw0rd1");
+
+        history.record(&current).await.unwrap();
+
+        assert!(
+            history
+                .check("password", &current, "p4ssw0rd1")
+                .await
+                .is_ok()
+        );
+        assert!(
+            history
+                .repository
+                .find_recent("1", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::borrow::Cow;
+use std::fs;
+
+use validator::{ValidationError, ValidationErrors};
+
+use crate::config::PasswordPolicyConfig;
+use crate::domain::error::{AppError, AppResult};
+
+// Personal details shorter than this match too many unrelated passwords
+const MIN_PERSONAL_TOKEN: usize = 3;
+
+pub struct PasswordPolicy {
+    min_entropy: f64,
+    reject_personal_info: bool,
+    // Lowercased and sorted so lookups are a binary search
+    breached: Vec<String>,
+}
+
+impl PasswordPolicy {
+    pub fn new(config: &PasswordPolicyConfig) -> std::io::Result<Self> {
+        let breached = match &config.breached_passwords {
+            Some(path) => fs::read_to_string(path)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_lowercase)
+                .collect(),
+            None => vec![],
+        };
+
+        Ok(Self::with_breached(config, breached))
+    }
+
+    pub fn with_breached(config: &PasswordPolicyConfig, mut breached: Vec<String>) -> Self {
+        breached.sort_unstable();
+        breached.dedup();
+
+        Self {
+            min_entropy: config.min_entropy,
+            reject_personal_info: config.reject_personal_info,
+            breached,
+        }
+    }
+
+    // `personal` holds the account's name and email, which make for easily guessed passwords
+    pub fn check(&self, field: &'static str, password: &str, personal: &[&str]) -> AppResult<()> {
+        let lowercase = password.to_lowercase();
+
+        let message = if self.breached.binary_search(&lowercase).is_ok() {
+            Some("Password has appeared in a data breach")
+        } else if self.reject_personal_info && contains_personal_info(&lowercase, personal) {
+            Some("Password must not contain your name or email")
+        } else if entropy(password) < self.min_entropy {
+            Some("Password is too easy to guess")
+        } else {
+            None
+        };
+
+        match message {
+            Some(message) => Err(rejection(field, message)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Reported like request validation failures so clients show it next to the password field
+pub fn rejection(field: &'static str, message: &'static str) -> AppError {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        field,
+        ValidationError::new("0").with_message(Cow::from(message)),
+    );
+
+    AppError::from(errors)
+}
+
+fn contains_personal_info(password: &str, personal: &[&str]) -> bool {
+    personal
+        .iter()
+        // Only the local part of an email says something about its owner
+        .map(|value| value.split('@').next().unwrap_or_default())
+        .flat_map(|value| value.split(|c: char| !c.is_alphanumeric()))
+        .filter(|token| token.chars().count() >= MIN_PERSONAL_TOKEN)
+        .any(|token| password.contains(&token.to_lowercase()))
+}
+
+// Bits of entropy over the character classes in use, where repeated and sequential characters
+// ("aaaa", "abcd", "4321") add nothing beyond the first one
+fn entropy(password: &str) -> f64 {
+    let mut pool = 0;
+    let mut classes = [false; 5];
+    let mut length = 0;
+    let mut previous: Option<char> = None;
+
+    for c in password.chars() {
+        let class = match c {
+            'a'..='z' => 0,
+            'A'..='Z' => 1,
+            '0'..='9' => 2,
+            c if c.is_ascii() => 3,
+            _ => 4,
+        };
+
+        if !classes[class] {
+            classes[class] = true;
+            pool += [26, 26, 10, 33, 100][class];
+        }
+
+        let predictable = previous.is_some_and(|p| (c as i64 - p as i64).abs() <= 1);
+
+        if !predictable {
+            length += 1;
+        }
+
+        previous = Some(c);
+    }
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    length as f64 * (pool as f64).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::utils::config::password_policy_config;
+
+    fn policy(breached: Vec<&str>) -> PasswordPolicy {
+        PasswordPolicy::with_breached(
+            &PasswordPolicyConfig {
+                min_entropy: 50.0,
+                ..password_policy_config()
+            },
+            breached.into_iter().map(str::to_lowercase).collect(),
+        )
+    }
+
+    #[test]
+    fn test_strong_password() {
+        let policy = policy(vec![]);
+
+        assert!(
+            policy
+                .check(
+                    "password",
+                    "stR0ngP4ssw0rd!",
+                    &["Test", "test@spacecraft.com"]
+                )
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_weak_password() {
+        let policy = policy(vec![]);
+
+        let err = policy.check("password", "Aa1!aaaa", &[]).unwrap_err();
+
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Password is too easy to guess"));
+        assert!(policy.check("password", "Abcdefgh1234!", &[]).is_err());
+    }
+
+    #[test]
+    fn test_personal_info() {
+        let policy = policy(vec![]);
+
+        let err = policy
+            .check(
+                "new_password",
+                "Sp4ceJohnDoe!2024",
+                &["John Doe", "jdoe@spacecraft.com"],
+            )
+            .unwrap_err();
+
+        assert!(err.message.contains("new_password"));
+        assert!(err.message.contains("must not contain your name or email"));
+
+        assert!(
+            policy
+                .check(
+                    "password",
+                    "Xq7!jdoeRt#92kLm",
+                    &["John", "jdoe@spacecraft.com"]
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_breached_password() {
+        let policy = policy(vec!["P@ssw0rd123!"]);
+
+        let err = policy.check("password", "p@ssw0rd123!", &[]).unwrap_err();
+
+        assert!(
+            err.message
+                .contains("Password has appeared in a data breach")
+        );
+    }
+
+    #[test]
+    fn test_load_breached_list() {
+        let policy = PasswordPolicy::new(&PasswordPolicyConfig {
+            breached_passwords: Some("config/breached_passwords.txt".to_string()),
+            ..password_policy_config()
+        })
+        .unwrap();
+
+        assert!(policy.check("password", "Password123!", &[]).is_err());
+    }
+
+    #[test]
+    fn test_entropy() {
+        assert_eq!(entropy(""), 0.0);
+        assert!(entropy("aaaaaaaa") < entropy("axqmwzrk"));
+        assert!(entropy("12345678") < entropy("19283746"));
+    }
+}
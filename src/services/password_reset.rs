@@ -0,0 +1,398 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::config::PasswordResetConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::Account,
+    models::mail::Mail,
+    models::password_reset::{CreatePasswordResetToken, ResetPassword},
+    repositories::account::{AccountRepository, FindByCol},
+    repositories::password_reset::PasswordResetRepository,
+    services::mailer::MailerService,
+    services::password_reset::PasswordResetService,
+};
+use crate::services::crypto::{hash_token, random_token};
+use crate::services::hasher::Argon2Hasher;
+use crate::services::password_history::PasswordHistory;
+use crate::services::password_policy::PasswordPolicy;
+
+pub struct PasswordResetServiceImpl {
+    ttl: i64,
+    url: String,
+    hasher: Arc<Argon2Hasher>,
+    policy: Arc<PasswordPolicy>,
+    history: Arc<PasswordHistory>,
+    repository: Arc<dyn PasswordResetRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+    mailer: Arc<dyn MailerService>,
+}
+
+impl PasswordResetServiceImpl {
+    pub fn new(
+        config: &PasswordResetConfig,
+        hasher: Arc<Argon2Hasher>,
+        policy: Arc<PasswordPolicy>,
+        history: Arc<PasswordHistory>,
+        repository: Arc<dyn PasswordResetRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+        mailer: Arc<dyn MailerService>,
+    ) -> Self {
+        Self {
+            ttl: config.ttl,
+            url: config.url.clone(),
+            hasher,
+            policy,
+            history,
+            repository,
+            account_repository,
+            mailer,
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetService for PasswordResetServiceImpl {
+    async fn forgot(&self, email: &str) -> AppResult<()> {
+        // Unknown emails succeed silently so the endpoint does not reveal which accounts exist
+        let account = match self
+            .account_repository
+            .find_one(FindByCol::Email(email.to_string()))
+            .await?
+        {
+            Some(account) => account,
+            None => return Ok(()),
+        };
+
+        let token = random_token();
+
+        let expiration = Utc::now()
+            .checked_add_signed(chrono::Duration::seconds(self.ttl))
+            .unwrap()
+            .timestamp();
+
+        self.repository
+            .create(CreatePasswordResetToken {
+                account: account.id,
+                token_hash: hash_token(&token),
+                expiration,
+            })
+            .await?;
+
+        self.mailer
+            .send(Mail {
+                to: account.email,
+                subject: "Reset your password".to_string(),
+                body: format!(
+                    "Use the following link to reset your password: {}{}\n\nThe link expires in {} minutes. If you did not request a password reset, ignore this email.",
+                    self.url,
+                    token,
+                    self.ttl / 60
+                ),
+            })
+            .await
+    }
+
+    async fn reset(&self, reset: ResetPassword) -> AppResult<Account> {
+        let stored = match self
+            .repository
+            .find_by_hash(&hash_token(&reset.token))
+            .await?
+        {
+            Some(stored) => stored,
+            None => return Err(AppError::Unauthorized()),
+        };
+
+        if stored.used || stored.expiration <= Utc::now().timestamp() {
+            return Err(AppError::Unauthorized());
+        }
+
+        let account = self
+            .account_repository
+            .find_by_id(&stored.account)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        // Checked before the token is consumed so a rejected password does not burn it
+        self.policy.check(
+            "password",
+            &reset.password,
+            &[&account.name, &account.email],
+        )?;
+        self.history
+            .check("password", &account, &reset.password)
+            .await?;
+
+        // Mark the token as used before touching the password so concurrent requests cannot reuse it
+        if !self.repository.consume(&stored.id).await? {
+            return Err(AppError::Unauthorized());
+        }
+
+        self.history.record(&account).await?;
+
+        let password = self.hasher.hash(&reset.password)?;
+
+        let account = self
+            .account_repository
+            .update_password(&stored.account, password)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        // Links from earlier requests would otherwise still reset the new password
+        self.repository.consume_account(&account.id).await?;
+
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::domain::models::password_reset::PasswordResetToken;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::password_reset::mock::PasswordResetRepositoryImpl;
+    use crate::services::mailer::mock::MailerServiceImpl;
+    use crate::tests::utils::crypto::{password_hasher, password_history, password_policy};
+    use rstest::*;
+
+    #[fixture]
+    fn service() -> (PasswordResetServiceImpl, Arc<MailerServiceImpl>) {
+        let mailer = Arc::new(MailerServiceImpl {
+            mails: Mutex::new(vec![]),
+        });
+
+        let repository = Arc::new(PasswordResetRepositoryImpl {
+            tokens: Mutex::new(HashMap::from([(
+                hash_token("expired_token"),
+                PasswordResetToken {
+                    id: "expired".to_string(),
+                    account: "1".to_string(),
+                    expiration: Utc::now().timestamp() - 60,
+                    used: false,
+                },
+            )])),
+        });
+
+        let account_repository = Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![Account {
+                id: "1".to_string(),
+                name: "Test".to_string(),
+                email: "test_account@spacecraft.com".to_string(),
+                password: password_hasher().hash("p4ssw0rd").unwrap(),
+                roles: vec![Role::User],
+                permissions: vec![],
+                password_changed_at: None,
+                email_verified_at: None,
+                status: AccountStatus::Active,
+                deleted_at: None,
+                created_at: 0,
+            }]),
+        });
+
+        let service = PasswordResetServiceImpl::new(
+            &PasswordResetConfig {
+                ttl: 900,
+                url: "http://localhost/reset?token=".to_string(),
+            },
+            Arc::new(password_hasher()),
+            Arc::new(password_policy()),
+            Arc::new(password_history(3)),
+            repository,
+            account_repository,
+            mailer.clone(),
+        );
+
+        (service, mailer)
+    }
+
+    fn mailed_token(mail: &Mail) -> String {
+        mail.body
+            .split("token=")
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .to_string()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_forgot_unknown_email(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.forgot("unknown@spacecraft.com").await.unwrap();
+
+        assert!(mailer.mails.lock().await.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reset_password(service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>)) {
+        let (service, mailer) = service;
+
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+
+        let mail = mailer.mails.lock().await[0].clone();
+
+        assert_eq!(mail.to, "test_account@spacecraft.com");
+
+        let account = service
+            .reset(ResetPassword {
+                token: mailed_token(&mail),
+                password: "n3wP4ssw0rd!".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert!(
+            password_hasher()
+                .verify("n3wP4ssw0rd!", &account.password)
+                .is_ok()
+        );
+        assert!(account.password_changed_at.is_some());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reset_token_single_use(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+
+        let token = mailed_token(&mailer.mails.lock().await[0]);
+
+        let reset = ResetPassword {
+            token,
+            password: "n3wP4ssw0rd!".to_string(),
+        };
+
+        assert!(service.reset(reset.clone()).await.is_ok());
+        assert_eq!(
+            service.reset(reset).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reset_rejected_password_keeps_token(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+
+        let token = mailed_token(&mailer.mails.lock().await[0]);
+
+        let err = service
+            .reset(ResetPassword {
+                token: token.clone(),
+                password: "Aaaaaaa1!".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, 422);
+
+        assert!(
+            service
+                .reset(ResetPassword {
+                    token,
+                    password: "n3wP4ssw0rd!".to_string(),
+                })
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reset_reused_password(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+
+        let token = mailed_token(&mailer.mails.lock().await[0]);
+
+        let err = service
+            .reset(ResetPassword {
+                token,
+                password: "p4ssw0rd".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, 422);
+        assert!(err.message.contains("Password has been used recently"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reset_consumes_other_tokens(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+    ) {
+        let (service, mailer) = service;
+
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+        service.forgot("test_account@spacecraft.com").await.unwrap();
+
+        let (first, second) = {
+            let mails = mailer.mails.lock().await;
+            (mailed_token(&mails[0]), mailed_token(&mails[1]))
+        };
+
+        assert!(
+            service
+                .reset(ResetPassword {
+                    token: second,
+                    password: "n3wP4ssw0rd!".to_string(),
+                })
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            service
+                .reset(ResetPassword {
+                    token: first,
+                    password: "4n0th3rP4ssw0rd!".to_string(),
+                })
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown("unknown_token")]
+    #[case::expired("expired_token")]
+    #[tokio::test]
+    async fn test_invalid_token(
+        service: (PasswordResetServiceImpl, Arc<MailerServiceImpl>),
+        #[case] token: &str,
+    ) {
+        let (service, _) = service;
+
+        let result = service
+            .reset(ResetPassword {
+                token: token.to_string(),
+                password: "n3wP4ssw0rd!".to_string(),
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err(), AppError::Unauthorized());
+    }
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::time;
+use async_trait::async_trait;
+
+use crate::config::{RateLimitConfig, RateLimitKey, RateLimitRuleConfig};
+use crate::domain::{
+    error::AppResult,
+    models::rate_limit::{RateLimit, RateLimitRequest},
+    repositories::rate_limit::RateLimitRepository,
+    services::rate_limit::RateLimitService,
+};
+
+pub struct RateLimitServiceImpl {
+    rules: Vec<RateLimitRuleConfig>,
+    repository: Arc<dyn RateLimitRepository>,
+}
+
+impl RateLimitServiceImpl {
+    pub fn new(config: &RateLimitConfig, repository: Arc<dyn RateLimitRepository>) -> Self {
+        Self {
+            rules: config.rules.clone(),
+            repository,
+        }
+    }
+
+    fn matching_rules<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> impl Iterator<Item = &'a RateLimitRuleConfig> + 'a {
+        self.rules
+            .iter()
+            .filter(move |rule| in_scope(&rule.scope, path))
+    }
+}
+
+#[async_trait]
+impl RateLimitService for RateLimitServiceImpl {
+    fn keys_by_subject(&self, path: &str) -> bool {
+        self.matching_rules(path)
+            .any(|rule| rule.key == RateLimitKey::Sub)
+    }
+
+    async fn acquire(&self, request: &RateLimitRequest) -> AppResult<Option<RateLimit>> {
+        let mut rate_limit: Option<RateLimit> = None;
+
+        for rule in self.matching_rules(&request.path) {
+            // Anonymous requests to subject keyed scopes are limited by client IP instead
+            let key = match (rule.key, &request.sub, &request.ip) {
+                (RateLimitKey::Sub, Some(sub), _) => format!("sub:{sub}"),
+                (RateLimitKey::Sub | RateLimitKey::Ip, _, Some(ip)) => format!("ip:{ip}"),
+                (RateLimitKey::Route, _, _) => format!("route:{}", request.route),
+                _ => continue,
+            };
+
+            let refill_rate = rule.capacity as f64 / rule.period.max(1) as f64;
+
+            let bucket = self
+                .repository
+                .acquire(
+                    &format!("{}|{}", rule.scope, key),
+                    rule.capacity,
+                    refill_rate,
+                )
+                .await?;
+
+            let limit = RateLimit {
+                limit: rule.capacity,
+                remaining: bucket.tokens.floor() as u32,
+                reset: ((rule.capacity as f64 - bucket.tokens) / refill_rate).ceil() as u64,
+                retry_after: (!bucket.allowed)
+                    .then(|| ((1.0 - bucket.tokens) / refill_rate).ceil().max(1.0) as u64),
+            };
+
+            rate_limit = match rate_limit {
+                Some(current) if !restricts_more(&limit, &current) => Some(current),
+                _ => Some(limit),
+            };
+        }
+
+        Ok(rate_limit)
+    }
+
+    // A bucket left alone for a whole period is full again, dropping it loses nothing
+    async fn prune(&self) -> AppResult<()> {
+        let idle = match self.rules.iter().map(|rule| rule.period).max() {
+            Some(period) => period as i64,
+            None => return Ok(()),
+        };
+
+        Ok(self.repository.prune(idle).await?)
+    }
+}
+
+pub const RATE_LIMIT_PRUNE_SECONDS: u64 = 300;
+
+// Runs for the lifetime of the server, a failed prune is logged and retried on the next tick
+pub async fn prune_periodically(service: Arc<dyn RateLimitService>, period: Duration) {
+    let mut interval = time::interval(period);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = service.prune().await {
+            tracing::warn!(error = ?err, "rate limit prune failed");
+        }
+    }
+}
+
+pub fn in_scope(scope: &str, path: &str) -> bool {
+    match path.strip_prefix(scope.trim_end_matches('/')) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+fn restricts_more(limit: &RateLimit, current: &RateLimit) -> bool {
+    match (limit.retry_after, current.retry_after) {
+        (Some(limit), Some(current)) => limit > current,
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (None, None) => limit.remaining < current.remaining,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repositories::rate_limit::MemoryRateLimitRepositoryImpl;
+    use rstest::*;
+
+    fn rule(scope: &str, key: RateLimitKey, capacity: u32) -> RateLimitRuleConfig {
+        RateLimitRuleConfig {
+            scope: scope.to_string(),
+            key,
+            capacity,
+            period: 60,
+        }
+    }
+
+    fn request(path: &str, ip: Option<&str>, sub: Option<&str>) -> RateLimitRequest {
+        RateLimitRequest {
+            path: path.to_string(),
+            route: path.to_string(),
+            ip: ip.map(str::to_string),
+            sub: sub.map(str::to_string),
+        }
+    }
+
+    #[fixture]
+    fn service() -> RateLimitServiceImpl {
+        RateLimitServiceImpl::new(
+            &RateLimitConfig {
+                rules: vec![
+                    rule("/api/v1", RateLimitKey::Ip, 5),
+                    rule("/api/v1/signin", RateLimitKey::Ip, 2),
+                    rule("/api/v1/me", RateLimitKey::Sub, 3),
+                    rule("/api/v1/password/forgot", RateLimitKey::Route, 1),
+                ],
+                ..Default::default()
+            },
+            Arc::new(MemoryRateLimitRepositoryImpl::default()),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unmatched_scope(service: RateLimitServiceImpl) {
+        let rate_limit = service
+            .acquire(&request("/swagger-ui/index.html", Some("10.0.0.1"), None))
+            .await
+            .unwrap();
+
+        assert!(rate_limit.is_none());
+        assert!(!service.keys_by_subject("/api/v1/signinx"));
+        assert!(service.keys_by_subject("/api/v1/me/mfa/totp"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_most_restrictive_rule(service: RateLimitServiceImpl) {
+        let signin = request("/api/v1/signin", Some("10.0.0.1"), None);
+
+        let rate_limit = service.acquire(&signin).await.unwrap().unwrap();
+
+        assert_eq!(rate_limit.limit, 2);
+        assert_eq!(rate_limit.remaining, 1);
+        assert_eq!(rate_limit.retry_after, None);
+
+        service.acquire(&signin).await.unwrap();
+
+        let rate_limit = service.acquire(&signin).await.unwrap().unwrap();
+
+        assert_eq!(rate_limit.remaining, 0);
+        assert!(rate_limit.retry_after.is_some_and(|seconds| seconds <= 30));
+
+        let other_ip = request("/api/v1/signin", Some("10.0.0.2"), None);
+
+        assert_eq!(
+            service
+                .acquire(&other_ip)
+                .await
+                .unwrap()
+                .unwrap()
+                .retry_after,
+            None
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_subject_key(service: RateLimitServiceImpl) {
+        for _ in 0..3 {
+            let me = request("/api/v1/me", Some("10.0.0.1"), Some("1"));
+
+            assert_eq!(
+                service.acquire(&me).await.unwrap().unwrap().retry_after,
+                None
+            );
+        }
+
+        let same_sub = request("/api/v1/me", Some("10.0.0.2"), Some("1"));
+        let other_sub = request("/api/v1/me", Some("10.0.0.2"), Some("2"));
+
+        assert!(
+            service
+                .acquire(&same_sub)
+                .await
+                .unwrap()
+                .unwrap()
+                .retry_after
+                .is_some()
+        );
+        assert_eq!(
+            service
+                .acquire(&other_sub)
+                .await
+                .unwrap()
+                .unwrap()
+                .retry_after,
+            None
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_route_key(service: RateLimitServiceImpl) {
+        let forgot = request("/api/v1/password/forgot", Some("10.0.0.1"), None);
+        let other_ip = request("/api/v1/password/forgot", Some("10.0.0.2"), None);
+
+        assert_eq!(
+            service.acquire(&forgot).await.unwrap().unwrap().retry_after,
+            None
+        );
+        assert!(
+            service
+                .acquire(&other_ip)
+                .await
+                .unwrap()
+                .unwrap()
+                .retry_after
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use crate::config::JsonWebTokenConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::refresh_token::{CreateRefreshToken, RefreshToken},
+    repositories::refresh_token::RefreshTokenRepository,
+    services::refresh_token::RefreshTokenService,
+};
+use crate::services::crypto::{hash_token, random_token};
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct RefreshTokenServiceImpl {
+    ttl: i64,
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl RefreshTokenServiceImpl {
+    pub fn new(config: &JsonWebTokenConfig, repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self {
+            ttl: config.refresh_ttl,
+            repository,
+        }
+    }
+
+    async fn create(&self, account: String, family: String) -> AppResult<RefreshToken> {
+        let token = random_token();
+
+        let expiration = Utc::now()
+            .checked_add_signed(chrono::Duration::seconds(self.ttl))
+            .unwrap()
+            .timestamp();
+
+        let stored = self
+            .repository
+            .create(CreateRefreshToken {
+                account,
+                token_hash: hash_token(&token),
+                family,
+                expiration,
+            })
+            .await?;
+
+        Ok(RefreshToken {
+            account: stored.account,
+            session: stored.family,
+            token,
+            expiration: stored.expiration,
+        })
+    }
+}
+
+#[async_trait]
+impl RefreshTokenService for RefreshTokenServiceImpl {
+    async fn issue(&self, account: String, session: String) -> AppResult<RefreshToken> {
+        self.create(account, session).await
+    }
+
+    async fn rotate(&self, token: &str) -> AppResult<RefreshToken> {
+        let stored = match self.repository.find_by_hash(&hash_token(token)).await? {
+            Some(stored) => stored,
+            None => return Err(AppError::Unauthorized()),
+        };
+
+        if stored.revoked || stored.expiration <= Utc::now().timestamp() {
+            return Err(AppError::Unauthorized());
+        }
+
+        // A rotated token presented again means it has leaked: kill the whole family
+        if stored.rotated || !self.repository.rotate(&stored.id).await? {
+            self.repository.revoke_family(&stored.family).await?;
+            return Err(AppError::Unauthorized());
+        }
+
+        self.create(stored.account, stored.family).await
+    }
+
+    async fn revoke_account(&self, account: &str) -> AppResult<()> {
+        Ok(self.repository.revoke_account(account).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::refresh_token::StoredRefreshToken;
+    use crate::infrastructure::repositories::refresh_token::mock::RefreshTokenRepositoryImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use rstest::*;
+
+    #[fixture]
+    fn repository() -> Arc<RefreshTokenRepositoryImpl> {
+        Arc::new(RefreshTokenRepositoryImpl {
+            tokens: Mutex::new(HashMap::from([(
+                hash_token("expired_token"),
+                StoredRefreshToken {
+                    id: "expired".to_string(),
+                    account: "1".to_string(),
+                    family: "expired_family".to_string(),
+                    expiration: Utc::now().timestamp() - 60,
+                    rotated: false,
+                    revoked: false,
+                },
+            )])),
+        })
+    }
+
+    #[fixture]
+    fn service(repository: Arc<RefreshTokenRepositoryImpl>) -> RefreshTokenServiceImpl {
+        RefreshTokenServiceImpl::new(&jsonwebtoken_config(), repository)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_issue_token(service: RefreshTokenServiceImpl) {
+        let now = Utc::now().timestamp();
+        let refresh_token = service
+            .issue("1".to_string(), "session".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(refresh_token.account, "1");
+        assert_eq!(refresh_token.session, "session");
+        assert!(refresh_token.expiration >= now + jsonwebtoken_config().refresh_ttl);
+        assert!(
+            refresh_token.expiration <= Utc::now().timestamp() + jsonwebtoken_config().refresh_ttl
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_rotate_token(service: RefreshTokenServiceImpl) {
+        let refresh_token = service
+            .issue("1".to_string(), "session".to_string())
+            .await
+            .unwrap();
+
+        let rotated = service.rotate(&refresh_token.token).await.unwrap();
+
+        assert_eq!(rotated.account, "1");
+        assert_eq!(rotated.session, "session");
+        assert_ne!(rotated.token, refresh_token.token);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_reuse_revokes_family(service: RefreshTokenServiceImpl) {
+        let refresh_token = service
+            .issue("1".to_string(), "session".to_string())
+            .await
+            .unwrap();
+        let rotated = service.rotate(&refresh_token.token).await.unwrap();
+
+        assert_eq!(
+            service.rotate(&refresh_token.token).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert_eq!(
+            service.rotate(&rotated.token).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown("unknown_token")]
+    #[case::expired("expired_token")]
+    #[tokio::test]
+    async fn test_invalid_token(service: RefreshTokenServiceImpl, #[case] token: &str) {
+        assert_eq!(
+            service.rotate(token).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_account(service: RefreshTokenServiceImpl) {
+        let first = service
+            .issue("1".to_string(), "session".to_string())
+            .await
+            .unwrap();
+        let second = service
+            .issue("1".to_string(), "second_session".to_string())
+            .await
+            .unwrap();
+        let other = service
+            .issue("2".to_string(), "other".to_string())
+            .await
+            .unwrap();
+
+        service.revoke_account("1").await.unwrap();
+
+        for token in [first, second] {
+            assert_eq!(
+                service.rotate(&token.token).await.unwrap_err(),
+                AppError::Unauthorized()
+            );
+        }
+        assert!(service.rotate(&other.token).await.is_ok());
+    }
+}
@@ -0,0 +1,362 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::session::{CreateSession, Session},
+    repositories::oauth::OAuthRepository,
+    repositories::refresh_token::RefreshTokenRepository,
+    repositories::session::SessionRepository,
+    services::jsonwebtoken::JsonWebTokenService,
+    services::session::SessionService,
+};
+
+pub struct SessionServiceImpl {
+    repository: Arc<dyn SessionRepository>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    oauth_repository: Arc<dyn OAuthRepository>,
+    jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+}
+
+impl SessionServiceImpl {
+    pub fn new(
+        repository: Arc<dyn SessionRepository>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+        oauth_repository: Arc<dyn OAuthRepository>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    ) -> Self {
+        Self {
+            repository,
+            refresh_token_repository,
+            oauth_repository,
+            jsonwebtoken_service,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionService for SessionServiceImpl {
+    async fn create(&self, session: CreateSession) -> AppResult<Session> {
+        Ok(self.repository.create(session).await?)
+    }
+
+    // A session is over once its refresh token has expired, even if it was never signed out
+    async fn list(&self, account: &str) -> AppResult<Vec<Session>> {
+        let live = self
+            .refresh_token_repository
+            .find_live_families(account)
+            .await?;
+
+        Ok(self
+            .repository
+            .find_by_account(account)
+            .await?
+            .into_iter()
+            .filter(|session| live.contains(&session.id))
+            .collect())
+    }
+
+    // The refresh token family carries the session id, so the session cannot be renewed either
+    async fn revoke(&self, account: &str, id: &str) -> AppResult<()> {
+        if !self.repository.revoke(id, account).await? {
+            return Err(AppError::NotFound("Session not found"));
+        }
+
+        self.refresh_token_repository.revoke_family(id).await?;
+
+        self.jsonwebtoken_service.revoke_session(id);
+
+        Ok(())
+    }
+
+    // Grants made to OAuth clients go too, their refresh tokens are not tied to a session
+    async fn revoke_account(&self, account: &str) -> AppResult<()> {
+        let sessions = self.repository.revoke_account(account).await?;
+
+        self.refresh_token_repository
+            .revoke_account(account)
+            .await?;
+
+        self.oauth_repository.revoke_account(account).await?;
+
+        for session in sessions {
+            self.jsonwebtoken_service.revoke_session(&session);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{Account, AccountStatus, Role};
+    use crate::domain::models::oauth::CreateOAuthRefreshToken;
+    use crate::domain::services::refresh_token::RefreshTokenService;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::oauth::mock::OAuthRepositoryImpl;
+    use crate::infrastructure::repositories::refresh_token::mock::RefreshTokenRepositoryImpl;
+    use crate::infrastructure::repositories::revoked_token::mock::RevokedTokenRepositoryImpl;
+    use crate::infrastructure::repositories::session::mock::SessionRepositoryImpl;
+    use crate::services::jsonwebtoken::JsonWebTokenServiceImpl;
+    use crate::services::refresh_token::RefreshTokenServiceImpl;
+    use crate::tests::utils::config::jsonwebtoken_config;
+    use crate::tests::utils::crypto::generate_keyring;
+    use rstest::*;
+
+    struct Context {
+        service: SessionServiceImpl,
+        refresh_token_service: RefreshTokenServiceImpl,
+        refresh_token_repository: Arc<RefreshTokenRepositoryImpl>,
+        oauth_repository: Arc<OAuthRepositoryImpl>,
+        jsonwebtoken_service: Arc<dyn JsonWebTokenService>,
+    }
+
+    impl Context {
+        // What a signin does: a session along with the refresh token that renews it
+        async fn signin(&self, account: &str) -> Session {
+            let session = self.service.create(new_session(account)).await.unwrap();
+
+            self.refresh_token_service
+                .issue(account.to_string(), session.id.clone())
+                .await
+                .unwrap();
+
+            session
+        }
+    }
+
+    fn account(id: &str) -> Account {
+        Account {
+            id: id.to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn new_session(account: &str) -> CreateSession {
+        CreateSession {
+            account: account.to_string(),
+            user_agent: Some("Mozilla/5.0".to_string()),
+            ip: Some("10.0.0.1".to_string()),
+        }
+    }
+
+    #[fixture]
+    fn context() -> Context {
+        let repository = Arc::new(SessionRepositoryImpl {
+            sessions: Mutex::new(vec![]),
+        });
+
+        let refresh_token_repository = Arc::new(RefreshTokenRepositoryImpl {
+            tokens: Mutex::new(HashMap::new()),
+        });
+
+        let oauth_repository = Arc::new(OAuthRepositoryImpl {
+            clients: Mutex::new(vec![]),
+            codes: Mutex::new(vec![]),
+            refresh_tokens: Mutex::new(vec![]),
+        });
+
+        let jsonwebtoken_service: Arc<dyn JsonWebTokenService> =
+            Arc::new(JsonWebTokenServiceImpl::new(
+                generate_keyring(),
+                &jsonwebtoken_config(),
+                Arc::new(RevokedTokenRepositoryImpl {
+                    tokens: Mutex::new(vec![]),
+                }),
+                Arc::new(AccountRepositoryImpl {
+                    accounts: Mutex::new(vec![]),
+                }),
+                repository.clone(),
+            ));
+
+        Context {
+            service: SessionServiceImpl::new(
+                repository,
+                refresh_token_repository.clone(),
+                oauth_repository.clone(),
+                jsonwebtoken_service.clone(),
+            ),
+            refresh_token_service: RefreshTokenServiceImpl::new(
+                &jsonwebtoken_config(),
+                refresh_token_repository.clone(),
+            ),
+            refresh_token_repository,
+            oauth_repository,
+            jsonwebtoken_service,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_create_and_list(context: Context) {
+        let first = context.signin("1").await;
+        let second = context.signin("1").await;
+        context.signin("2").await;
+
+        let sessions = context.service.list("1").await.unwrap();
+
+        assert_eq!(
+            sessions.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec![second.id.as_str(), first.id.as_str()]
+        );
+        assert_eq!(sessions[0].user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(sessions[0].ip.as_deref(), Some("10.0.0.1"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list_expired(context: Context) {
+        let expired = context.signin("1").await;
+        let live = context.signin("1").await;
+
+        context
+            .refresh_token_repository
+            .tokens
+            .lock()
+            .await
+            .values_mut()
+            .filter(|token| token.family == expired.id)
+            .for_each(|token| token.expiration = Utc::now().timestamp() - 60);
+
+        let sessions = context.service.list("1").await.unwrap();
+
+        assert_eq!(
+            sessions.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec![live.id.as_str()]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke(context: Context) {
+        let session = context.service.create(new_session("1")).await.unwrap();
+
+        let access_token = context
+            .jsonwebtoken_service
+            .generate_token(&account("1"), &session.id)
+            .unwrap();
+        let refresh_token = context
+            .refresh_token_service
+            .issue("1".to_string(), session.id.clone())
+            .await
+            .unwrap();
+
+        context.service.revoke("1", &session.id).await.unwrap();
+
+        assert!(context.service.list("1").await.unwrap().is_empty());
+        assert_eq!(
+            context
+                .jsonwebtoken_service
+                .validate_token(&access_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+        assert_eq!(
+            context
+                .refresh_token_service
+                .rotate(&refresh_token.token)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_other_account(context: Context) {
+        let session = context.signin("1").await;
+
+        assert_eq!(
+            context.service.revoke("2", &session.id).await.unwrap_err(),
+            AppError::NotFound("Session not found")
+        );
+        assert_eq!(
+            context.service.revoke("1", "unknown").await.unwrap_err(),
+            AppError::NotFound("Session not found")
+        );
+        assert_eq!(context.service.list("1").await.unwrap().len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_revoke_account(context: Context) {
+        let first = context.signin("1").await;
+        let second = context.signin("1").await;
+        let other = context.signin("2").await;
+
+        for account in ["1", "2"] {
+            context
+                .oauth_repository
+                .create_refresh_token(CreateOAuthRefreshToken {
+                    client: "client".to_string(),
+                    account: account.to_string(),
+                    token_hash: format!("hash{account}"),
+                    family: format!("family{account}"),
+                    scopes: vec![],
+                    expiration: i64::MAX,
+                })
+                .await
+                .unwrap();
+        }
+
+        context.service.revoke_account("1").await.unwrap();
+
+        assert!(context.service.list("1").await.unwrap().is_empty());
+
+        for (account, revoked) in [("1", true), ("2", false)] {
+            let token = context
+                .oauth_repository
+                .find_refresh_token(&format!("hash{account}"))
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(token.revoked, revoked);
+        }
+
+        for session in [first, second] {
+            let access_token = context
+                .jsonwebtoken_service
+                .generate_token(&account("1"), &session.id)
+                .unwrap();
+
+            assert!(
+                context
+                    .jsonwebtoken_service
+                    .validate_token(&access_token.token)
+                    .await
+                    .is_err()
+            );
+        }
+
+        let access_token = context
+            .jsonwebtoken_service
+            .generate_token(&account("2"), &other.id)
+            .unwrap();
+
+        assert!(
+            context
+                .jsonwebtoken_service
+                .validate_token(&access_token.token)
+                .await
+                .is_ok()
+        );
+    }
+}
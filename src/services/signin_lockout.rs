@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::config::SigninLockoutConfig;
+use crate::domain::error::{AppError, AppResult};
+use crate::domain::repositories::signin_attempt::SigninAttemptRepository;
+
+pub struct SigninLockout {
+    account_threshold: u32,
+    ip_threshold: u32,
+    window: i64,
+    lockout: i64,
+    max_lockout: i64,
+    repository: Arc<dyn SigninAttemptRepository>,
+}
+
+impl SigninLockout {
+    pub fn new(config: &SigninLockoutConfig, repository: Arc<dyn SigninAttemptRepository>) -> Self {
+        Self {
+            account_threshold: config.account_threshold,
+            ip_threshold: config.ip_threshold,
+            window: config.window,
+            lockout: config.lockout,
+            max_lockout: config.max_lockout,
+            repository,
+        }
+    }
+
+    // Failures are tracked per account key, whether or not the account exists, and per client IP
+    pub fn keys(&self, key: String, client_ip: Option<&str>) -> Vec<(String, u32)> {
+        let mut keys = vec![(key, self.account_threshold)];
+
+        if let Some(ip) = client_ip {
+            keys.push((format!("ip:{ip}"), self.ip_threshold));
+        }
+
+        keys
+    }
+
+    pub async fn check(&self, keys: &[(String, u32)]) -> AppResult<()> {
+        let now = Utc::now().timestamp();
+        let mut retry_after = 0;
+
+        for (key, _) in keys {
+            if let Some(locked_until) = self
+                .repository
+                .find(key)
+                .await?
+                .and_then(|attempt| attempt.locked_until)
+            {
+                retry_after = retry_after.max(locked_until - now);
+            }
+        }
+
+        if retry_after > 0 {
+            return Err(AppError::TooManyRequests().retry_after(retry_after as u64));
+        }
+
+        Ok(())
+    }
+
+    // Every failure past the threshold doubles the lockout, up to the configured maximum
+    pub async fn record_failure(&self, keys: &[(String, u32)]) -> AppResult<()> {
+        let now = Utc::now().timestamp();
+
+        for (key, threshold) in keys {
+            let attempt = self
+                .repository
+                .record_failure(key, now - self.window)
+                .await?;
+
+            if attempt.failures >= *threshold {
+                let exponent = (attempt.failures - threshold).min(31);
+                let lockout = self
+                    .lockout
+                    .saturating_mul(1 << exponent)
+                    .min(self.max_lockout);
+
+                self.repository.lock(key, now + lockout).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Only the account key is cleared, a client IP stays suspect whatever account it got into
+    pub async fn reset(&self, key: &str) -> AppResult<()> {
+        Ok(self.repository.reset(key).await?)
+    }
+}
+
+pub fn account_key(email: &str) -> String {
+    format!("account:{}", email.to_lowercase())
+}
+
+// Second factor failures are counted apart, so signing in with the password again clears nothing
+pub fn mfa_key(account: &str) -> String {
+    format!("mfa:{account}")
+}
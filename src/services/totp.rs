@@ -0,0 +1,410 @@
+use std::sync::Arc;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use async_trait::async_trait;
+use chrono::Utc;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::config::TotpConfig;
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::Account,
+    models::totp::{CreateTotpCredential, TotpCredential, TotpEnrollment},
+    repositories::totp::TotpRepository,
+    services::totp::TotpService,
+};
+use crate::services::crypto::hash_token;
+use crate::services::signin_lockout::{SigninLockout, mfa_key};
+
+const DIGITS: usize = 6;
+const SKEW: u8 = 1;
+const STEP: u64 = 30;
+
+pub struct TotpServiceImpl {
+    issuer: String,
+    recovery_codes: usize,
+    lockout: Arc<SigninLockout>,
+    repository: Arc<dyn TotpRepository>,
+}
+
+impl TotpServiceImpl {
+    pub fn new(
+        config: &TotpConfig,
+        lockout: Arc<SigninLockout>,
+        repository: Arc<dyn TotpRepository>,
+    ) -> Self {
+        Self {
+            issuer: config.issuer.clone(),
+            recovery_codes: config.recovery_codes,
+            lockout,
+            repository,
+        }
+    }
+
+    fn totp(&self, secret: &Secret, account_name: &str) -> AppResult<TOTP> {
+        let secret = secret
+            .to_bytes()
+            .map_err(|err| AppError::InternalError().trace(&err.to_string()))?;
+
+        TOTP::new(
+            Algorithm::SHA1,
+            DIGITS,
+            SKEW,
+            STEP,
+            secret,
+            Some(self.issuer.clone()),
+            account_name.to_owned(),
+        )
+        .map_err(|err| AppError::InternalError().trace(&err.to_string()))
+    }
+
+    // Each step within the skew is checked on its own, to know which one the code belongs to
+    fn step(&self, credential: &TotpCredential, code: &str) -> AppResult<Option<u64>> {
+        let mut totp = self.totp(&Secret::Encoded(credential.secret.clone()), "")?;
+        totp.skew = 0;
+
+        let current = Utc::now().timestamp() as u64 / STEP;
+
+        Ok(
+            (current.saturating_sub(SKEW as u64)..=current + SKEW as u64)
+                .find(|step| totp.check(code, step * STEP)),
+        )
+    }
+
+    // A code is accepted once, codes from its step or an earlier one are refused afterwards
+    async fn accept(&self, credential: &TotpCredential, code: &str) -> AppResult<bool> {
+        match self.step(credential, code)? {
+            Some(step) => Ok(self
+                .repository
+                .accept_step(&credential.account, step)
+                .await?),
+            None => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl TotpService for TotpServiceImpl {
+    async fn enroll(&self, account: &Account) -> AppResult<TotpEnrollment> {
+        if self.is_enabled(&account.id).await? {
+            return Err(AppError::Conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        let mut secret = [0u8; 20];
+        OsRng.fill_bytes(&mut secret);
+
+        let totp = self.totp(&Secret::Raw(secret.to_vec()), &account.email)?;
+
+        let recovery_codes: Vec<String> =
+            (0..self.recovery_codes).map(|_| recovery_code()).collect();
+
+        self.repository
+            .save(CreateTotpCredential {
+                account: account.id.clone(),
+                secret: totp.get_secret_base32(),
+                recovery_codes: recovery_codes.iter().map(|code| hash_token(code)).collect(),
+            })
+            .await?;
+
+        Ok(TotpEnrollment {
+            secret: totp.get_secret_base32(),
+            uri: totp.get_url(),
+            recovery_codes,
+        })
+    }
+
+    async fn confirm(&self, account: &str, code: &str) -> AppResult<()> {
+        let credential = match self.repository.find_by_account(account).await? {
+            Some(credential) => credential,
+            None => {
+                return Err(AppError::BadRequest(
+                    "Two-factor authentication enrollment has not been started",
+                ));
+            }
+        };
+
+        if credential.confirmed {
+            return Err(AppError::Conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        if !self.accept(&credential, code).await? {
+            return Err(AppError::Unauthorized());
+        }
+
+        if !self.repository.confirm(account).await? {
+            return Err(AppError::Conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn is_enabled(&self, account: &str) -> AppResult<bool> {
+        Ok(self
+            .repository
+            .find_by_account(account)
+            .await?
+            .is_some_and(|credential| credential.confirmed))
+    }
+
+    // Failures share the signin lockout, once locked the caller has to start over from the password
+    async fn verify(&self, account: &str, code: &str, client_ip: Option<&str>) -> AppResult<()> {
+        let keys = self.lockout.keys(mfa_key(account), client_ip);
+
+        self.lockout.check(&keys).await?;
+
+        let credential = match self.repository.find_by_account(account).await? {
+            Some(credential) if credential.confirmed => credential,
+            _ => return Err(AppError::Unauthorized()),
+        };
+
+        // Recovery codes are single-use and removed once redeemed
+        let code_hash = hash_token(&code.trim().to_lowercase());
+
+        if self.accept(&credential, code).await?
+            || self
+                .repository
+                .consume_recovery_code(account, &code_hash)
+                .await?
+        {
+            return self.lockout.reset(&mfa_key(account)).await;
+        }
+
+        self.lockout.record_failure(&keys).await?;
+        self.lockout.check(&keys).await?;
+
+        Err(AppError::Unauthorized())
+    }
+}
+
+fn recovery_code() -> String {
+    let mut bytes = [0u8; 5];
+    OsRng.fill_bytes(&mut bytes);
+
+    let code: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+
+    format!("{}-{}", &code[..5], &code[5..])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::infrastructure::repositories::signin_attempt::mock::SigninAttemptRepositoryImpl;
+    use crate::infrastructure::repositories::totp::mock::TotpRepositoryImpl;
+    use crate::tests::utils::config::signin_lockout_config;
+    use rstest::*;
+
+    fn account() -> Account {
+        Account {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    // The code of the step `offset` steps away from the current one
+    fn code(enrollment: &TotpEnrollment, offset: i64) -> String {
+        TOTP::from_url(&enrollment.uri)
+            .unwrap()
+            .generate((Utc::now().timestamp() + offset * STEP as i64) as u64)
+    }
+
+    #[fixture]
+    fn service() -> TotpServiceImpl {
+        TotpServiceImpl::new(
+            &TotpConfig {
+                issuer: "test".to_string(),
+                recovery_codes: 3,
+            },
+            Arc::new(SigninLockout::new(
+                &signin_lockout_config(),
+                Arc::new(SigninAttemptRepositoryImpl {
+                    attempts: Mutex::new(HashMap::new()),
+                }),
+            )),
+            Arc::new(TotpRepositoryImpl {
+                credentials: Mutex::new(vec![]),
+            }),
+        )
+    }
+
+    #[fixture]
+    async fn enrolled(service: TotpServiceImpl) -> (TotpServiceImpl, TotpEnrollment) {
+        let enrollment = service.enroll(&account()).await.unwrap();
+
+        service.confirm("1", &code(&enrollment, -1)).await.unwrap();
+
+        (service, enrollment)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_enroll(service: TotpServiceImpl) {
+        let enrollment = service.enroll(&account()).await.unwrap();
+
+        assert!(enrollment.uri.starts_with("otpauth://totp/test:"));
+        assert!(enrollment.uri.contains(&enrollment.secret));
+        assert_eq!(enrollment.recovery_codes.len(), 3);
+        assert!(!service.is_enabled("1").await.unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_confirm(service: TotpServiceImpl) {
+        let enrollment = service.enroll(&account()).await.unwrap();
+
+        assert_eq!(
+            service.confirm("1", "000000").await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+
+        service.confirm("1", &code(&enrollment, 0)).await.unwrap();
+
+        assert!(service.is_enabled("1").await.unwrap());
+        assert_eq!(
+            service.enroll(&account()).await.unwrap_err(),
+            AppError::Conflict("Two-factor authentication is already enabled")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_confirm_without_enrollment(service: TotpServiceImpl) {
+        assert_eq!(
+            service.confirm("1", "000000").await.unwrap_err(),
+            AppError::BadRequest("Two-factor authentication enrollment has not been started")
+        );
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn test_verify_code(#[future] enrolled: (TotpServiceImpl, TotpEnrollment)) {
+        let (service, enrollment) = enrolled;
+
+        assert!(
+            service
+                .verify("1", &code(&enrollment, 0), None)
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            service
+                .verify("2", &code(&enrollment, 0), None)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn test_verify_replay(#[future] enrolled: (TotpServiceImpl, TotpEnrollment)) {
+        let (service, enrollment) = enrolled;
+
+        service
+            .verify("1", &code(&enrollment, 0), None)
+            .await
+            .unwrap();
+
+        // The code used to confirm the enrollment belongs to an earlier step
+        for offset in [0, -1] {
+            assert_eq!(
+                service
+                    .verify("1", &code(&enrollment, offset), None)
+                    .await
+                    .unwrap_err(),
+                AppError::Unauthorized()
+            );
+        }
+
+        assert!(
+            service
+                .verify("1", &code(&enrollment, 1), None)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn test_verify_lockout(#[future] enrolled: (TotpServiceImpl, TotpEnrollment)) {
+        let (service, enrollment) = enrolled;
+
+        for _ in 0..2 {
+            assert_eq!(
+                service
+                    .verify("1", "wrong-code", Some("127.0.0.1"))
+                    .await
+                    .unwrap_err(),
+                AppError::Unauthorized()
+            );
+        }
+
+        assert_eq!(
+            service
+                .verify("1", "wrong-code", Some("127.0.0.1"))
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+        assert_eq!(
+            service
+                .verify("1", &code(&enrollment, 0), Some("127.0.0.1"))
+                .await
+                .unwrap_err()
+                .code,
+            429
+        );
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn test_verify_recovery_code(#[future] enrolled: (TotpServiceImpl, TotpEnrollment)) {
+        let (service, enrollment) = enrolled;
+
+        let recovery_code = &enrollment.recovery_codes[0];
+
+        assert!(service.verify("1", recovery_code, None).await.is_ok());
+        assert_eq!(
+            service.verify("1", recovery_code, None).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_verify_unconfirmed(service: TotpServiceImpl) {
+        let enrollment = service.enroll(&account()).await.unwrap();
+
+        assert_eq!(
+            service
+                .verify("1", &code(&enrollment, 0), None)
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+}
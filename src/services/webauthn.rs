@@ -0,0 +1,765 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use ciborium::Value;
+use p256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::{EmailVerificationConfig, WebAuthnConfig};
+use crate::domain::{
+    error::{AppError, AppResult},
+    models::account::Account,
+    models::webauthn::{
+        AssertCredential, AuthenticationOptions, Ceremony, CreateWebAuthnChallenge,
+        CreateWebAuthnCredential, RegisterCredential, RegistrationOptions, WebAuthnChallenge,
+    },
+    repositories::account::{AccountRepository, FindByCol},
+    repositories::webauthn::WebAuthnRepository,
+    services::webauthn::WebAuthnService,
+};
+use crate::services::crypto::{hash_token, random_token};
+
+const USER_PRESENT: u8 = 0x01;
+const USER_VERIFIED: u8 = 0x04;
+const ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+pub struct WebAuthnServiceImpl {
+    rp_id: String,
+    rp_name: String,
+    origin: String,
+    ttl: i64,
+    require_verified_email: bool,
+    repository: Arc<dyn WebAuthnRepository>,
+    account_repository: Arc<dyn AccountRepository>,
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData {
+    rp_id_hash: Vec<u8>,
+    flags: u8,
+    sign_count: u32,
+    attested_credential: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl WebAuthnServiceImpl {
+    pub fn new(
+        config: &WebAuthnConfig,
+        verification_config: &EmailVerificationConfig,
+        repository: Arc<dyn WebAuthnRepository>,
+        account_repository: Arc<dyn AccountRepository>,
+    ) -> Self {
+        Self {
+            rp_id: config.rp_id.clone(),
+            rp_name: config.rp_name.clone(),
+            origin: config.origin.clone(),
+            ttl: config.ttl,
+            require_verified_email: verification_config.required,
+            repository,
+            account_repository,
+        }
+    }
+
+    async fn create_challenge(
+        &self,
+        account: Option<String>,
+        ceremony: Ceremony,
+    ) -> AppResult<String> {
+        let challenge = random_token();
+
+        let expiration = Utc::now()
+            .checked_add_signed(chrono::Duration::seconds(self.ttl))
+            .unwrap()
+            .timestamp();
+
+        self.repository
+            .create_challenge(CreateWebAuthnChallenge {
+                account,
+                ceremony,
+                challenge_hash: hash_token(&challenge),
+                expiration,
+            })
+            .await?;
+
+        Ok(challenge)
+    }
+
+    // Checks the collected client data and redeems the challenge it was signed for
+    async fn consume_client_data(
+        &self,
+        client_data_json: &[u8],
+        ceremony: Ceremony,
+    ) -> AppResult<Option<WebAuthnChallenge>> {
+        let client_data: ClientData = match serde_json::from_slice(client_data_json) {
+            Ok(client_data) => client_data,
+            Err(_) => return Ok(None),
+        };
+
+        let kind = match ceremony {
+            Ceremony::Registration => "webauthn.create",
+            Ceremony::Authentication => "webauthn.get",
+        };
+
+        if client_data.kind != kind || client_data.origin != self.origin {
+            return Ok(None);
+        }
+
+        Ok(self
+            .repository
+            .consume_challenge(&hash_token(&client_data.challenge), ceremony)
+            .await?)
+    }
+
+    fn verify_authenticator_data(&self, authenticator_data: &AuthenticatorData) -> bool {
+        authenticator_data.rp_id_hash == Sha256::digest(self.rp_id.as_bytes()).as_slice()
+            && authenticator_data.flags & USER_PRESENT != 0
+            && authenticator_data.flags & USER_VERIFIED != 0
+    }
+}
+
+#[async_trait]
+impl WebAuthnService for WebAuthnServiceImpl {
+    async fn start_registration(&self, account: &Account) -> AppResult<RegistrationOptions> {
+        let exclude_credentials = self
+            .repository
+            .find_credentials_by_account(&account.id)
+            .await?
+            .into_iter()
+            .map(|credential| credential.credential_id)
+            .collect();
+
+        let challenge = self
+            .create_challenge(Some(account.id.clone()), Ceremony::Registration)
+            .await?;
+
+        Ok(RegistrationOptions {
+            challenge,
+            rp_id: self.rp_id.clone(),
+            rp_name: self.rp_name.clone(),
+            user_id: URL_SAFE_NO_PAD.encode(account.id.as_bytes()),
+            user_name: account.email.clone(),
+            user_display_name: account.name.clone(),
+            timeout: self.ttl * 1000,
+            exclude_credentials,
+        })
+    }
+
+    async fn finish_registration(
+        &self,
+        account: &str,
+        credential: RegisterCredential,
+    ) -> AppResult<()> {
+        let invalid = || AppError::BadRequest("Invalid WebAuthn registration");
+
+        let client_data_json = decode(&credential.client_data_json).ok_or_else(invalid)?;
+
+        let challenge = self
+            .consume_client_data(&client_data_json, Ceremony::Registration)
+            .await?
+            .ok_or_else(invalid)?;
+
+        if challenge.account.as_deref() != Some(account) {
+            return Err(invalid());
+        }
+
+        let authenticator_data = decode(&credential.attestation_object)
+            .as_deref()
+            .and_then(attested_authenticator_data)
+            .and_then(|data| parse_authenticator_data(&data))
+            .ok_or_else(invalid)?;
+
+        if !self.verify_authenticator_data(&authenticator_data)
+            || authenticator_data.flags & ATTESTED_CREDENTIAL_DATA == 0
+        {
+            return Err(invalid());
+        }
+
+        let (credential_id, public_key) =
+            authenticator_data.attested_credential.ok_or_else(invalid)?;
+
+        if decode(&credential.credential_id) != Some(credential_id.clone()) {
+            return Err(invalid());
+        }
+
+        let public_key = cose_public_key(&public_key).ok_or_else(invalid)?;
+
+        let credential_id = URL_SAFE_NO_PAD.encode(credential_id);
+
+        if self
+            .repository
+            .find_credential(&credential_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict("Credential already registered"));
+        }
+
+        self.repository
+            .create_credential(CreateWebAuthnCredential {
+                account: account.to_owned(),
+                credential_id,
+                public_key: URL_SAFE_NO_PAD.encode(public_key),
+                sign_count: authenticator_data.sign_count,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_authentication(&self, email: Option<&str>) -> AppResult<AuthenticationOptions> {
+        let account = match email {
+            Some(email) => {
+                self.account_repository
+                    .find_one(FindByCol::Email(email.to_string()))
+                    .await?
+            }
+            None => None,
+        };
+
+        // Unknown emails get an empty credential list, the same as a known account without passkeys
+        let allow_credentials = match &account {
+            Some(account) => self
+                .repository
+                .find_credentials_by_account(&account.id)
+                .await?
+                .into_iter()
+                .map(|credential| credential.credential_id)
+                .collect(),
+            None => vec![],
+        };
+
+        let challenge = self
+            .create_challenge(account.map(|account| account.id), Ceremony::Authentication)
+            .await?;
+
+        Ok(AuthenticationOptions {
+            challenge,
+            rp_id: self.rp_id.clone(),
+            timeout: self.ttl * 1000,
+            allow_credentials,
+        })
+    }
+
+    async fn finish_authentication(&self, credential: AssertCredential) -> AppResult<Account> {
+        let client_data_json =
+            decode(&credential.client_data_json).ok_or_else(AppError::Unauthorized)?;
+
+        let challenge = self
+            .consume_client_data(&client_data_json, Ceremony::Authentication)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let stored = self
+            .repository
+            .find_credential(&credential.credential_id)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        if challenge
+            .account
+            .is_some_and(|account| account != stored.account)
+        {
+            return Err(AppError::Unauthorized());
+        }
+
+        if credential
+            .user_handle
+            .as_deref()
+            .is_some_and(|user_handle| {
+                decode(user_handle).as_deref() != Some(stored.account.as_bytes())
+            })
+        {
+            return Err(AppError::Unauthorized());
+        }
+
+        let raw_authenticator_data =
+            decode(&credential.authenticator_data).ok_or_else(AppError::Unauthorized)?;
+
+        let authenticator_data =
+            parse_authenticator_data(&raw_authenticator_data).ok_or_else(AppError::Unauthorized)?;
+
+        if !self.verify_authenticator_data(&authenticator_data) {
+            return Err(AppError::Unauthorized());
+        }
+
+        let public_key = decode(&stored.public_key)
+            .and_then(|key| VerifyingKey::from_sec1_bytes(&key).ok())
+            .ok_or_else(|| AppError::InternalError().trace("Invalid stored WebAuthn public key"))?;
+
+        let signature = decode(&credential.signature)
+            .and_then(|signature| Signature::from_der(&signature).ok())
+            .ok_or_else(AppError::Unauthorized)?;
+
+        let mut signed = raw_authenticator_data;
+        signed.extend_from_slice(&Sha256::digest(&client_data_json));
+
+        public_key
+            .verify(&signed, &signature)
+            .map_err(|_| AppError::Unauthorized())?;
+
+        // A counter that does not increase signals a cloned authenticator
+        let sign_count = authenticator_data.sign_count;
+
+        if (sign_count != 0 || stored.sign_count != 0) && sign_count <= stored.sign_count {
+            return Err(AppError::Unauthorized());
+        }
+
+        if !self
+            .repository
+            .update_sign_count(&stored.id, sign_count)
+            .await?
+        {
+            return Err(AppError::Unauthorized());
+        }
+
+        // Same gate as a password signin, deleted accounts are already left out by the lookup
+        let account = self
+            .account_repository
+            .find_by_id(&stored.account)
+            .await?
+            .ok_or_else(AppError::Unauthorized)?;
+
+        account.status.ensure_active()?;
+
+        if self.require_verified_email && account.email_verified_at.is_none() {
+            return Err(AppError::Forbidden());
+        }
+
+        Ok(account)
+    }
+}
+
+fn decode(value: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD.decode(value.trim_end_matches('=')).ok()
+}
+
+// The attestation statement is not verified: registration requests "none" attestation
+fn attested_authenticator_data(attestation_object: &[u8]) -> Option<Vec<u8>> {
+    match ciborium::from_reader::<Value, _>(attestation_object).ok()? {
+        Value::Map(entries) => entries
+            .into_iter()
+            .find_map(|(key, value)| match (key, value) {
+                (Value::Text(key), Value::Bytes(data)) if key == "authData" => Some(data),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+fn parse_authenticator_data(data: &[u8]) -> Option<AuthenticatorData> {
+    if data.len() < 37 {
+        return None;
+    }
+
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes(data[33..37].try_into().ok()?);
+
+    let attested_credential = if flags & ATTESTED_CREDENTIAL_DATA != 0 {
+        // aaguid (16 bytes) || credential id length (2 bytes) || credential id || COSE key
+        let length = u16::from_be_bytes(data.get(53..55)?.try_into().ok()?) as usize;
+        let credential_id = data.get(55..55 + length)?.to_vec();
+        let public_key = data.get(55 + length..)?.to_vec();
+
+        Some((credential_id, public_key))
+    } else {
+        None
+    };
+
+    Some(AuthenticatorData {
+        rp_id_hash: data[..32].to_vec(),
+        flags,
+        sign_count,
+        attested_credential,
+    })
+}
+
+// Only ES256 keys (COSE kty EC2, alg -7, curve P-256) are accepted, returned as a SEC1 point
+fn cose_public_key(key: &[u8]) -> Option<Vec<u8>> {
+    let entries = match ciborium::from_reader::<Value, _>(key).ok()? {
+        Value::Map(entries) => entries,
+        _ => return None,
+    };
+
+    let get = |label: i128| {
+        entries.iter().find_map(|(key, value)| match key {
+            Value::Integer(key) if i128::from(*key) == label => Some(value),
+            _ => None,
+        })
+    };
+
+    let integer = |label: i128| match get(label) {
+        Some(Value::Integer(value)) => Some(i128::from(*value)),
+        _ => None,
+    };
+
+    let coordinate = |label: i128| match get(label) {
+        Some(Value::Bytes(value)) if value.len() == 32 => Some(value.clone()),
+        _ => None,
+    };
+
+    if integer(1)? != 2 || integer(3)? != -7 || integer(-1)? != 1 {
+        return None;
+    }
+
+    let mut point = vec![0x04];
+    point.extend(coordinate(-2)?);
+    point.extend(coordinate(-3)?);
+
+    VerifyingKey::from_sec1_bytes(&point).ok()?;
+
+    Some(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::models::account::{AccountStatus, Role};
+    use crate::domain::repositories::account::AccountRepository;
+    use crate::infrastructure::repositories::account::mock::AccountRepositoryImpl;
+    use crate::infrastructure::repositories::webauthn::mock::WebAuthnRepositoryImpl;
+    use crate::tests::utils::webauthn::SoftAuthenticator;
+    use rstest::*;
+
+    fn account() -> Account {
+        Account {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            email: "test_account@spacecraft.com".to_string(),
+            password: "p4ssw0rd".to_string(),
+            roles: vec![Role::User],
+            permissions: vec![],
+            password_changed_at: None,
+            email_verified_at: None,
+            status: AccountStatus::Active,
+            deleted_at: None,
+            created_at: 0,
+        }
+    }
+
+    fn new_service(
+        require_verified_email: bool,
+        account_repository: Arc<AccountRepositoryImpl>,
+    ) -> WebAuthnServiceImpl {
+        WebAuthnServiceImpl::new(
+            &WebAuthnConfig {
+                rp_id: "localhost".to_string(),
+                rp_name: "test".to_string(),
+                origin: "http://localhost:8080".to_string(),
+                ttl: 300,
+            },
+            &EmailVerificationConfig {
+                required: require_verified_email,
+                ttl: 86400,
+                url: "http://localhost/verify?token=".to_string(),
+            },
+            Arc::new(WebAuthnRepositoryImpl {
+                challenges: Mutex::new(vec![]),
+                credentials: Mutex::new(vec![]),
+            }),
+            account_repository,
+        )
+    }
+
+    fn account_repository() -> Arc<AccountRepositoryImpl> {
+        Arc::new(AccountRepositoryImpl {
+            accounts: Mutex::new(vec![account()]),
+        })
+    }
+
+    #[fixture]
+    fn service() -> WebAuthnServiceImpl {
+        new_service(false, account_repository())
+    }
+
+    fn authenticator() -> SoftAuthenticator {
+        SoftAuthenticator::new("localhost", "http://localhost:8080")
+    }
+
+    async fn register(service: &WebAuthnServiceImpl, authenticator: &mut SoftAuthenticator) {
+        let options = service.start_registration(&account()).await.unwrap();
+
+        service
+            .finish_registration("1", authenticator.register(&options.challenge))
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_registration_options(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service.start_registration(&account()).await.unwrap();
+
+        assert_eq!(options.rp_id, "localhost");
+        assert_eq!(options.user_id, URL_SAFE_NO_PAD.encode("1"));
+        assert_eq!(
+            options.exclude_credentials,
+            vec![authenticator.credential_id()]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authentication(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service
+            .start_authentication(Some("test_account@spacecraft.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            options.allow_credentials,
+            vec![authenticator.credential_id()]
+        );
+
+        let account = service
+            .finish_authentication(authenticator.assert(&options.challenge, Some("1")))
+            .await
+            .unwrap();
+
+        assert_eq!(account.id, "1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_discoverable_authentication(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        assert!(options.allow_credentials.is_empty());
+
+        let account = service
+            .finish_authentication(authenticator.assert(&options.challenge, Some("1")))
+            .await
+            .unwrap();
+
+        assert_eq!(account.id, "1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_challenge_single_use(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        let assertion = authenticator.assert(&options.challenge, None);
+
+        assert!(
+            service
+                .finish_authentication(assertion.clone())
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            service.finish_authentication(assertion).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unknown_challenge(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        assert_eq!(
+            service
+                .finish_authentication(authenticator.assert(&random_token(), None))
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_wrong_origin(service: WebAuthnServiceImpl) {
+        let mut authenticator = SoftAuthenticator::new("localhost", "http://evil.com");
+
+        let options = service.start_registration(&account()).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_registration("1", authenticator.register(&options.challenge))
+                .await
+                .unwrap_err(),
+            AppError::BadRequest("Invalid WebAuthn registration")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_wrong_relying_party(service: WebAuthnServiceImpl) {
+        let mut authenticator = SoftAuthenticator::new("evil.com", "http://localhost:8080");
+
+        let options = service.start_registration(&account()).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_registration("1", authenticator.register(&options.challenge))
+                .await
+                .unwrap_err(),
+            AppError::BadRequest("Invalid WebAuthn registration")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unregistered_authenticator(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_authentication(authenticator.assert(&options.challenge, None))
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_cloned_authenticator(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let mut clone = authenticator.clone();
+
+        let options = service.start_authentication(None).await.unwrap();
+        service
+            .finish_authentication(authenticator.assert(&options.challenge, None))
+            .await
+            .unwrap();
+
+        let options = service.start_authentication(None).await.unwrap();
+        assert_eq!(
+            service
+                .finish_authentication(clone.assert(&options.challenge, None))
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_tampered_signature(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        let assertion = AssertCredential {
+            client_data_json: URL_SAFE_NO_PAD.encode(format!(
+                r#"{{"type":"webauthn.get","challenge":"{}","origin":"http://localhost:8080","crossOrigin":true}}"#,
+                options.challenge
+            )),
+            ..authenticator.assert(&options.challenge, None)
+        };
+
+        assert_eq!(
+            service.finish_authentication(assertion).await.unwrap_err(),
+            AppError::Unauthorized()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_user_not_verified(service: WebAuthnServiceImpl) {
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        authenticator.user_verified = false;
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_authentication(authenticator.assert(&options.challenge, None))
+                .await
+                .unwrap_err(),
+            AppError::Unauthorized()
+        );
+
+        let options = service.start_registration(&account()).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_registration("1", authenticator.register(&options.challenge))
+                .await
+                .unwrap_err(),
+            AppError::BadRequest("Invalid WebAuthn registration")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suspended_account() {
+        let account_repository = account_repository();
+        let service = new_service(false, account_repository.clone());
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        account_repository
+            .update_status("1", AccountStatus::Suspended)
+            .await
+            .unwrap();
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_authentication(authenticator.assert(&options.challenge, None))
+                .await
+                .unwrap_err(),
+            AppError::Suspended()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unverified_email() {
+        let service = new_service(true, account_repository());
+        let mut authenticator = authenticator();
+
+        register(&service, &mut authenticator).await;
+
+        let options = service.start_authentication(None).await.unwrap();
+
+        assert_eq!(
+            service
+                .finish_authentication(authenticator.assert(&options.challenge, None))
+                .await
+                .unwrap_err(),
+            AppError::Forbidden()
+        );
+    }
+}
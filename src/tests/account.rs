@@ -4,8 +4,8 @@ use rstest::*;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::tests::utils::seed::seed_account;
-use crate::tests::{Error, TestContext, context};
+use crate::tests::utils::seed::{seed_account, seed_token};
+use crate::tests::{Error, TestContext, context, csrf_header, request_cookie};
 
 use crate::app;
 use actix_web::test;
@@ -25,6 +25,8 @@ pub struct Account {
 pub struct AccessToken {
     token: String,
     expires_at: i64,
+    refresh_token: String,
+    refresh_expires_at: i64,
 }
 
 #[rstest]
@@ -81,6 +83,39 @@ async fn test_signup_twice(#[future] context: TestContext) {
     let _ = context.db.container.stop().await;
 }
 
+#[rstest]
+#[case::breached("Password123!", "Password has appeared in a data breach")]
+#[case::personal_info("Sp4cecraft!Orbit7", "Password must not contain your name or email")]
+#[case::low_entropy("Aaaaaaa1!", "Password is too easy to guess")]
+#[awt]
+#[actix_web::test]
+async fn test_signup_password_policy(
+    #[future] context: TestContext,
+    #[case] password: String,
+    #[case] message: String,
+) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signup")
+        .set_json(json!({
+                "name": "Orbit Spacecraft",
+                "email": "orbit@email.com",
+                "password": password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let err: Error = test::read_body_json(res).await;
+
+    assert_eq!(err.code, 422);
+    assert!(err.message.contains(&message));
+
+    let _ = context.db.container.stop().await;
+}
+
 #[rstest]
 #[awt]
 #[actix_web::test]
@@ -111,6 +146,7 @@ async fn test_success_signin(#[future] context: TestContext) {
     assert_eq!(cookie.name(), "Authorization");
     assert_eq!(cookie.value(), access_token.token);
     assert!(access_token.expires_at > 0);
+    assert!(access_token.refresh_expires_at > access_token.expires_at);
     assert_eq!(
         cookie
             .expires()
@@ -121,6 +157,18 @@ async fn test_success_signin(#[future] context: TestContext) {
         access_token.expires_at
     );
 
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": access_token.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let refreshed: AccessToken = test::read_body_json(res).await;
+
+    assert_ne!(refreshed.refresh_token, access_token.refresh_token);
+
     let _ = context.db.container.stop().await;
 }
 
@@ -156,3 +204,202 @@ async fn test_invalid_signin(
 
     let _ = context.db.container.stop().await;
 }
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_signin_lockout(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    for _ in 0..5 {
+        let res = TestRequest::post()
+            .uri("/api/v1/signin")
+            .peer_addr("10.0.0.1:4000".parse().unwrap())
+            .set_json(json!({
+                "email": account.email,
+                "password": "wr0ngP4ssw0rd!",
+            }))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .peer_addr("10.0.0.2:4000".parse().unwrap())
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let retry_after: u64 = res
+        .headers()
+        .get("retry-after")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    assert!(retry_after > 0 && retry_after <= 60);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 429);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_signout(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signout")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let header = res.headers().get("set-cookie").unwrap();
+    let removal = Cookie::parse_encoded(header.to_str().unwrap().to_owned()).unwrap();
+
+    assert_eq!(removal.name(), "Authorization");
+    assert_eq!(removal.value(), "");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signout")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_cookie_auth_requires_csrf_token(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let cookies: Vec<Cookie> = res
+        .headers()
+        .get_all("set-cookie")
+        .map(|header| Cookie::parse_encoded(header.to_str().unwrap().to_owned()).unwrap())
+        .collect();
+
+    let named = |name: &str| {
+        cookies
+            .iter()
+            .find(|cookie| cookie.name() == name)
+            .unwrap()
+            .clone()
+    };
+
+    let cookie = named("Authorization");
+    let csrf_cookie = named("csrf_token");
+
+    assert!(!csrf_cookie.http_only().unwrap_or(false));
+
+    for token in [None, Some("forged")] {
+        let mut req = TestRequest::post()
+            .uri("/api/v1/signout")
+            .cookie(cookie.clone());
+
+        if let Some(token) = token {
+            req = req.insert_header(("X-CSRF-Token", token));
+        }
+
+        let res = req.send_request(&app).await;
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signout")
+        .insert_header(("X-CSRF-Token", csrf_cookie.value()))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_verify_email(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    seed_token(
+        &context.db.connection,
+        "email_verification_token",
+        &account,
+        "verification_token",
+    )
+    .await;
+
+    for status in [StatusCode::NO_CONTENT, StatusCode::UNAUTHORIZED] {
+        let res = TestRequest::get()
+            .uri("/api/v1/verify-email?token=verification_token")
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), status);
+    }
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[case::known_email("test_account@email.com")]
+#[case::unknown_email("fake_account@email.com")]
+#[awt]
+#[actix_web::test]
+async fn test_resend_verification(#[future] context: TestContext, #[case] email: String) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/verify-email/resend")
+        .set_json(json!({ "email": email }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,611 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::{Surreal, engine::remote::ws::Client, sql::Thing};
+
+use crate::domain::models::account::Account;
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{Error, TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminAccount {
+    id: String,
+    email: String,
+    roles: Vec<String>,
+    status: String,
+    deleted_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Audit {
+    actor: Thing,
+    action: String,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountPage {
+    items: Vec<AdminAccount>,
+    next_cursor: Option<String>,
+}
+
+async fn promote(conn: &Surreal<Client>, account: &Account) {
+    conn.query("UPDATE type::thing('account', $id) SET roles = ['admin']")
+        .bind(("id", account.id.clone()))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+}
+
+async fn seed_others(conn: &Surreal<Client>) {
+    conn.query(
+        r#"
+        CREATE account CONTENT { name: 'Alpha', email: 'alpha@spacecraft.com', password: 'x' };
+        CREATE account CONTENT { name: 'Bravo', email: 'bravo@spacecraft.com', password: 'x' };
+        CREATE account CONTENT { name: 'Charlie', email: 'charlie@other.com', password: 'x' };
+        "#,
+    )
+    .await
+    .unwrap()
+    .check()
+    .unwrap();
+}
+
+async fn seed_user(conn: &Surreal<Client>, email: &str, password: &str) -> String {
+    let thing: Option<Thing> = conn
+        .query(
+            "RETURN (CREATE account CONTENT {
+                name: 'Other Account',
+                email: $email,
+                password: crypto::argon2::generate($password)
+            })[0].id",
+        )
+        .bind(("email", email.to_owned()))
+        .bind(("password", password.to_owned()))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    thing.unwrap().id.to_string()
+}
+
+async fn count_rows(conn: &Surreal<Client>, table: &str, field: &str, id: &str) -> usize {
+    let count: Option<usize> = conn
+        .query("(SELECT count() FROM type::table($table) WHERE type::field($field) = type::thing('account', $id) GROUP ALL)[0].count OR 0")
+        .bind(("table", table.to_owned()))
+        .bind(("field", field.to_owned()))
+        .bind(("id", id.to_owned()))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    count.unwrap_or_default()
+}
+
+fn emails(page: &AccountPage) -> Vec<&str> {
+    page.items.iter().map(|a| a.email.as_str()).collect()
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_list_accounts(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+    promote(&context.db.connection, &account).await;
+    seed_others(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?sort=email&order=asc&limit=2")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let first: AccountPage = test::read_body_json(res).await;
+
+    assert_eq!(
+        emails(&first),
+        vec!["alpha@spacecraft.com", "bravo@spacecraft.com"]
+    );
+    assert_eq!(first.next_cursor.as_ref(), Some(&first.items[1].id));
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/admin/accounts?sort=email&order=asc&limit=2&cursor={}",
+            first.next_cursor.unwrap()
+        ))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let second: AccountPage = test::read_body_json(res).await;
+
+    assert_eq!(
+        emails(&second),
+        vec!["charlie@other.com", "test_account@email.com"]
+    );
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(second.items[1].roles, vec!["admin"]);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?email=SPACECRAFT&sort=name&order=desc")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let filtered: AccountPage = test::read_body_json(res).await;
+
+    assert_eq!(
+        emails(&filtered),
+        vec!["bravo@spacecraft.com", "alpha@spacecraft.com"]
+    );
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?created_before=0")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let empty: AccountPage = test::read_body_json(res).await;
+
+    assert!(empty.items.is_empty());
+
+    context
+        .db
+        .connection
+        .query("UPDATE account SET deleted_at = time::now() WHERE email = 'charlie@other.com'")
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?email=other")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let live: AccountPage = test::read_body_json(res).await;
+
+    assert!(live.items.is_empty());
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?email=other&include_deleted=true")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let deleted: AccountPage = test::read_body_json(res).await;
+
+    assert_eq!(emails(&deleted), vec!["charlie@other.com"]);
+    assert!(deleted.items[0].deleted_at.is_some());
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?cursor=unknown")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts?limit=0")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_get_account(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+    promote(&context.db.connection, &account).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::get()
+        .uri(&format!("/api/v1/admin/accounts/{}", account.id))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let found: AdminAccount = test::read_body_json(res).await;
+
+    assert_eq!(found.email, account.email);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts/unknown")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.message, "Account not found");
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_admin_forbidden(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/admin/accounts")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = TestRequest::get()
+        .uri(&format!("/api/v1/admin/accounts/{}", account.id))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_suspend_and_reactivate(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let admin = seed_account(&context.db.connection).await;
+    promote(&context.db.connection, &admin).await;
+
+    let user = seed_user(&context.db.connection, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    let credentials = json!({
+        "email": "other@email.com",
+        "password": "stR0ngP4ssw0rd!",
+    });
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(&credentials)
+        .send_request(&app)
+        .await;
+
+    let signin: AccessToken = test::read_body_json(res).await;
+    let bearer = ("Authorization", format!("Bearer {}", signin.token));
+
+    let cookie = request_cookie(&app, &admin.email, &admin.password).await;
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/suspend"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie.clone())
+        .set_json(json!({ "reason": "Spam" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let suspended: AdminAccount = test::read_body_json(res).await;
+    assert_eq!(suspended.status, "suspended");
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(bearer.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::LOCKED);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 423);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(&credentials)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::LOCKED);
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/suspend"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie.clone())
+        .set_json(json!({ "reason": "Spam" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/reactivate"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(bearer)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let audits: Vec<Audit> = context
+        .db
+        .connection
+        .query("SELECT * FROM account_audit WHERE account = type::thing('account', $id) ORDER BY created_at")
+        .bind(("id", user))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    assert_eq!(
+        audits
+            .iter()
+            .map(|a| (a.action.as_str(), a.reason.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("suspend", Some("Spam")), ("reactivate", None)]
+    );
+    assert!(audits.iter().all(|a| a.actor.id.to_string() == admin.id));
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_suspend_forbidden(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let user = seed_user(&context.db.connection, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/suspend"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "reason": "Spam" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_restore_account(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let admin = seed_account(&context.db.connection).await;
+    promote(&context.db.connection, &admin).await;
+
+    let user = seed_user(&context.db.connection, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    let credentials = json!({
+        "email": "other@email.com",
+        "password": "stR0ngP4ssw0rd!",
+    });
+
+    let user_cookie = request_cookie(&app, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    let res = TestRequest::delete()
+        .uri("/api/v1/me")
+        .insert_header(csrf_header(&user_cookie))
+        .cookie(user_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(&credentials)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let cookie = request_cookie(&app, &admin.email, &admin.password).await;
+
+    let res = TestRequest::get()
+        .uri(&format!("/api/v1/admin/accounts/{user}"))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/restore"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let restored: AdminAccount = test::read_body_json(res).await;
+    assert_eq!(restored.email, "other@email.com");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(&credentials)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/restore"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.message, "Account not found");
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_purge_deleted_accounts(#[future] context: TestContext) {
+    let account_deletion_service = context.container.account_deletion_service.clone();
+
+    let app = test::init_service(app::create(context.container)).await;
+
+    let admin = seed_account(&context.db.connection).await;
+    promote(&context.db.connection, &admin).await;
+
+    let user = seed_user(&context.db.connection, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    context
+        .db
+        .connection
+        .query("CREATE account_audit CONTENT { account: type::thing('account', $id), actor: type::thing('account', $admin), action: 'suspend' }")
+        .bind(("id", user.clone()))
+        .bind(("admin", admin.id.clone()))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let user_cookie = request_cookie(&app, "other@email.com", "stR0ngP4ssw0rd!").await;
+
+    // A failed signin after the last successful one leaves a lockout counter behind
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": "other@email.com",
+            "password": "wr0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::delete()
+        .uri("/api/v1/me")
+        .insert_header(csrf_header(&user_cookie))
+        .cookie(user_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    // Still inside the retention period
+    assert_eq!(account_deletion_service.purge().await.unwrap(), 0);
+    assert_eq!(
+        count_rows(&context.db.connection, "account_session", "account", &user).await,
+        1
+    );
+
+    context
+        .db
+        .connection
+        .query("UPDATE type::thing('account', $id) SET deleted_at = time::now() - 31d")
+        .bind(("id", user.clone()))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    assert_eq!(account_deletion_service.purge().await.unwrap(), 1);
+
+    assert_eq!(
+        count_rows(&context.db.connection, "account", "id", &user).await,
+        0
+    );
+    assert_eq!(
+        count_rows(&context.db.connection, "account_session", "account", &user).await,
+        0
+    );
+    assert_eq!(
+        count_rows(&context.db.connection, "refresh_token", "account", &user).await,
+        0
+    );
+    assert_eq!(
+        count_rows(&context.db.connection, "account", "id", &admin.id).await,
+        1
+    );
+
+    // The audit trail is kept, the lockout counter keyed by email is not
+    assert_eq!(
+        count_rows(&context.db.connection, "account_audit", "account", &user).await,
+        1
+    );
+
+    let attempts: Vec<Thing> = context
+        .db
+        .connection
+        .query("SELECT VALUE id FROM type::thing('signin_attempt', 'account:other@email.com')")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    assert!(attempts.is_empty());
+
+    let cookie = request_cookie(&app, &admin.email, &admin.password).await;
+
+    let res = TestRequest::post()
+        .uri(&format!("/api/v1/admin/accounts/{user}/restore"))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,149 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+pub struct CreatedApiKey {
+    id: String,
+    key: String,
+    prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKey {
+    id: String,
+    prefix: String,
+    last_used_at: Option<i64>,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_api_key_lifecycle(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/api-keys")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({ "name": "deploy-bot" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let created: CreatedApiKey = test::read_body_json(res).await;
+
+    assert!(created.key.starts_with(&created.prefix));
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("X-API-Key", created.key.clone()))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", created.key)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // A key cannot be used to manage keys
+    let res = TestRequest::get()
+        .uri("/api/v1/me/api-keys")
+        .insert_header(("X-API-Key", created.key.clone()))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signout")
+        .insert_header(("X-API-Key", created.key.clone()))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me/api-keys")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let api_keys: Vec<ApiKey> = test::read_body_json(res).await;
+
+    assert_eq!(api_keys.len(), 1);
+    assert_eq!(api_keys[0].id, created.id);
+    assert_eq!(api_keys[0].prefix, created.prefix);
+    assert!(api_keys[0].last_used_at.is_some());
+
+    let res = TestRequest::delete()
+        .uri(&format!("/api/v1/me/api-keys/{}", created.id))
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("X-API-Key", created.key))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::delete()
+        .uri(&format!("/api/v1/me/api-keys/{}", created.id))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_api_key_scope_escalation(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/api-keys")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "name": "deploy-bot", "scopes": ["accounts:write"] }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,434 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::sql::Thing;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{Error, TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Account {
+    id: String,
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    token: String,
+    refresh_token: String,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_profile(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let acc: Account = test::read_body_json(res).await;
+
+    assert_eq!(acc.id, account.id);
+    assert_eq!(acc.name, account.name);
+    assert_eq!(acc.email, account.email);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_update_profile(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::patch()
+        .uri("/api/v1/me")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({ "name": "Updated Account" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let acc: Account = test::read_body_json(res).await;
+
+    assert_eq!(acc.name, "Updated Account");
+    assert_eq!(acc.email, account.email);
+
+    context
+        .db
+        .connection
+        .query("UPDATE type::thing('account', $id) SET email_verified_at = time::now()")
+        .bind(("id", account.id.clone()))
+        .await
+        .unwrap();
+
+    let res = TestRequest::patch()
+        .uri("/api/v1/me")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "email": "updated_account@email.com" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let acc: Account = test::read_body_json(res).await;
+
+    assert_eq!(acc.name, "Updated Account");
+    assert_eq!(acc.email, "updated_account@email.com");
+
+    // The new address has to be verified again
+    let verified: Vec<Thing> = context
+        .db
+        .connection
+        .query(
+            "SELECT VALUE id FROM type::thing('account', $id) WHERE email_verified_at IS NOT NONE",
+        )
+        .bind(("id", account.id))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    assert!(verified.is_empty());
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_update_profile_conflict(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signup")
+        .set_json(json!({
+            "name": "Other Account",
+            "email": "other_account@email.com",
+            "password": "stR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::patch()
+        .uri("/api/v1/me")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "email": "other_account@email.com" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 409);
+    assert_eq!(err.message, "Account already exists");
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_delete_profile(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::delete()
+        .uri("/api/v1/me")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_deactivate_profile(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/deactivate")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::GONE);
+
+    let error: Error = test::read_body_json(res).await;
+
+    assert_eq!(
+        error,
+        Error {
+            code: 410,
+            message: "The account has been deactivated".to_string(),
+        }
+    );
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_change_password(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let tokens: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/password")
+        .insert_header(("Authorization", format!("Bearer {}", tokens.token)))
+        .set_json(json!({
+            "current_password": account.password,
+            "new_password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let new_tokens: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", tokens.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": tokens.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", new_tokens.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_change_password_wrong_current(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/password")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({
+            "current_password": "wR0ngP4ssw0rd!",
+            "new_password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 403);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatedApiKey {
+    key: String,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_delegated_credentials_cannot_manage_account(#[future] context: TestContext) {
+    let jsonwebtoken_service = context.container.jsonwebtoken_service.clone();
+
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/api-keys")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({ "name": "deploy-bot" }))
+        .send_request(&app)
+        .await;
+
+    let api_key: CreatedApiKey = test::read_body_json(res).await;
+
+    let client_token = jsonwebtoken_service
+        .generate_client_token(&account.id, "client", None, vec![])
+        .unwrap();
+
+    let requests = || {
+        [
+            TestRequest::patch()
+                .uri("/api/v1/me")
+                .set_json(json!({ "name": "Renamed" })),
+            TestRequest::delete().uri("/api/v1/me"),
+            TestRequest::post()
+                .uri("/api/v1/me/password")
+                .set_json(json!({
+                    "current_password": account.password,
+                    "new_password": "n3wStR0ngP4ssw0rd!",
+                })),
+            TestRequest::post().uri("/api/v1/me/webauthn/register/options"),
+            TestRequest::post()
+                .uri("/api/v1/me/webauthn/register")
+                .set_json(json!({
+                    "id": "credential",
+                    "response": {
+                        "clientDataJSON": "e30",
+                        "attestationObject": "oA",
+                    },
+                })),
+            TestRequest::post().uri("/api/v1/me/mfa/totp"),
+            TestRequest::post()
+                .uri("/api/v1/me/mfa/totp/confirm")
+                .set_json(json!({ "code": "123456" })),
+        ]
+    };
+
+    for credential in [api_key.key, client_token.token] {
+        for req in requests() {
+            let res = req
+                .insert_header(("Authorization", format!("Bearer {credential}")))
+                .send_request(&app)
+                .await;
+
+            assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        }
+    }
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    let profile: Account = test::read_body_json(res).await;
+
+    assert_eq!(profile.name, account.name);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,239 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+use totp_rs::TOTP;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TotpEnrollment {
+    #[allow(dead_code)]
+    secret: String,
+    uri: String,
+    recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MfaPending {
+    mfa_token: String,
+    expires_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    token: String,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_signin_mfa(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/mfa/totp")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let enrollment: TotpEnrollment = test::read_body_json(res).await;
+    let totp = TOTP::from_url(&enrollment.uri).unwrap();
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/mfa/totp/confirm")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "code": totp.generate_current().unwrap() }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+    assert!(res.headers().get("set-cookie").is_none());
+
+    let pending: MfaPending = test::read_body_json(res).await;
+
+    assert!(pending.expires_at > 0);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", pending.mfa_token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin/mfa")
+        .set_json(json!({
+            "mfa_token": pending.mfa_token,
+            "code": enrollment.recovery_codes[0],
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let access_token: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin/mfa")
+        .set_json(json!({
+            "mfa_token": pending.mfa_token,
+            "code": enrollment.recovery_codes[1],
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_invalid_mfa_code(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    TestRequest::post()
+        .uri("/api/v1/me/mfa/totp")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/mfa/totp/confirm")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "code": "abcdef" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_mfa_lockout(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/mfa/totp")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    let enrollment: TotpEnrollment = test::read_body_json(res).await;
+    let totp = TOTP::from_url(&enrollment.uri).unwrap();
+
+    TestRequest::post()
+        .uri("/api/v1/me/mfa/totp/confirm")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({ "code": totp.generate_current().unwrap() }))
+        .send_request(&app)
+        .await;
+
+    let app = &app;
+
+    let signin = move || {
+        TestRequest::post()
+            .uri("/api/v1/signin")
+            .set_json(json!({
+                "email": account.email,
+                "password": account.password,
+            }))
+            .send_request(app)
+    };
+
+    let pending: MfaPending = test::read_body_json(signin().await).await;
+
+    let signin_mfa = move |mfa_token: String, code: String| {
+        TestRequest::post()
+            .uri("/api/v1/signin/mfa")
+            .set_json(json!({ "mfa_token": mfa_token, "code": code }))
+            .send_request(app)
+    };
+
+    for _ in 0..4 {
+        let res = signin_mfa(pending.mfa_token.clone(), "wrong-code".to_string()).await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let res = signin_mfa(pending.mfa_token.clone(), "wrong-code".to_string()).await;
+
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The pending token is revoked once the account is locked out
+    let res = signin_mfa(pending.mfa_token, enrollment.recovery_codes[0].clone()).await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // Signing in with the password again does not lift the lockout of the second factor
+    let pending: MfaPending = test::read_body_json(signin().await).await;
+
+    let res = signin_mfa(pending.mfa_token, enrollment.recovery_codes[0].clone()).await;
+
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let _ = context.db.container.stop().await;
+}
@@ -1,10 +1,23 @@
 mod account;
+mod admin;
+mod api_key;
+mod me;
+mod mfa;
+mod oauth;
+mod oidc;
+mod password;
+mod rate_limit;
+mod session;
+mod token;
+mod webauthn;
 
 pub mod utils;
 
 use ::surrealdb::{Surreal, engine::remote::ws::Client};
 
-use crate::tests::utils::crypto::generate_keypair;
+use crate::services::crypto::hash_token;
+use crate::services::password_policy::PasswordPolicy;
+use crate::tests::utils::crypto::{generate_keyring, password_hasher};
 use std::sync::Arc;
 
 use serde::Deserialize;
@@ -62,14 +75,20 @@ async fn context() -> TestContext {
         .up()
         .await;
 
-    let keys = generate_keypair();
+    let keys = generate_keyring();
 
     let db = Database {
         connection: db_connection.clone(),
         container: db_container,
     };
 
-    let container = Arc::new(Container::new(db_connection, keys));
+    let container = Arc::new(Container::new(
+        db_connection,
+        keys,
+        password_hasher(),
+        PasswordPolicy::new(&config.password_policy).unwrap(),
+        &config,
+    ));
 
     TestContext { db, container }
 }
@@ -95,6 +114,11 @@ where
     Cookie::parse_encoded(cookie.to_owned()).unwrap()
 }
 
+// What a browser client reads from the csrf_token cookie and echoes on state-changing requests
+fn csrf_header(cookie: &Cookie) -> (&'static str, String) {
+    ("X-CSRF-Token", hash_token(cookie.value()))
+}
+
 #[derive(Debug, PartialEq, Eq, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct Error {
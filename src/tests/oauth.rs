@@ -0,0 +1,445 @@
+use actix_web::http::StatusCode;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::Url;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::services::crypto::hash_token;
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+const REDIRECT_URI: &str = "https://dashboard.spacecraft.com/callback";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+#[derive(Debug, Deserialize)]
+pub struct RegisteredClient {
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Consent {
+    client_name: String,
+    scopes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthorizationRedirect {
+    redirect_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OAuthError {
+    error: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Token {
+    access_token: String,
+    token_type: String,
+    refresh_token: Option<String>,
+    scope: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Introspection {
+    active: bool,
+    client_id: Option<String>,
+    sub: Option<String>,
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    Url::parse(url)
+        .unwrap()
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+fn authorize_query(client_id: &str) -> String {
+    format!(
+        "response_type=code&client_id={client_id}&redirect_uri={REDIRECT_URI}&scope=accounts:read&state=xyz&code_challenge={}&code_challenge_method=S256",
+        hash_token(CODE_VERIFIER)
+    )
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_authorization_code_flow(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    context
+        .db
+        .connection
+        .query("UPDATE type::thing('account', $id) SET roles = ['admin']")
+        .bind(("id", account.id.clone()))
+        .await
+        .unwrap();
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/clients")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({
+            "name": "dashboard",
+            "confidential": false,
+            "redirect_uris": [REDIRECT_URI],
+            "grant_types": ["authorization_code", "refresh_token"],
+            "scopes": ["accounts:read"],
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let client: RegisteredClient = test::read_body_json(res).await;
+
+    assert!(client.client_secret.is_none());
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oauth/authorize?{}",
+            authorize_query(&client.client_id)
+        ))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let consent: Consent = test::read_body_json(res).await;
+
+    assert_eq!(consent.client_name, "dashboard");
+    assert_eq!(consent.scopes, vec!["accounts:read"]);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/authorize")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({
+            "response_type": "code",
+            "client_id": client.client_id,
+            "redirect_uri": REDIRECT_URI,
+            "scope": "accounts:read",
+            "state": "xyz",
+            "code_challenge": hash_token(CODE_VERIFIER),
+            "code_challenge_method": "S256",
+            "approved": true,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let redirect: AuthorizationRedirect = test::read_body_json(res).await;
+    let location = redirect.redirect_uri.as_str();
+
+    assert!(location.starts_with(REDIRECT_URI));
+    assert_eq!(query_param(location, "state").as_deref(), Some("xyz"));
+
+    let code = query_param(location, "code").unwrap();
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .set_form([
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+            ("client_id", client.client_id.as_str()),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("cache-control").unwrap(), "no-store");
+
+    let token: Token = test::read_body_json(res).await;
+
+    assert_eq!(token.token_type, "Bearer");
+    assert_eq!(token.scope, "accounts:read");
+
+    // The code is single use
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .set_form([
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+            ("client_id", client.client_id.as_str()),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let error: OAuthError = test::read_body_json(res).await;
+
+    assert_eq!(error.error, "invalid_grant");
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", token.access_token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // Delegated tokens cannot mint further credentials
+    let res = TestRequest::post()
+        .uri("/api/v1/me/api-keys")
+        .insert_header(("Authorization", format!("Bearer {}", token.access_token)))
+        .set_json(json!({ "name": "escalation" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let refresh_token = token.refresh_token.unwrap();
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .set_form([
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("client_id", client.client_id.as_str()),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let refreshed: Token = test::read_body_json(res).await;
+
+    // Public clients cannot authenticate, so they cannot introspect
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/introspect")
+        .set_form([
+            ("token", refreshed.access_token.as_str()),
+            ("client_id", client.client_id.as_str()),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/clients")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({
+            "name": "resource",
+            "confidential": true,
+            "redirect_uris": [],
+            "grant_types": ["client_credentials"],
+            "scopes": ["accounts:read"],
+        }))
+        .send_request(&app)
+        .await;
+
+    let resource: RegisteredClient = test::read_body_json(res).await;
+
+    let basic = format!(
+        "Basic {}",
+        STANDARD.encode(format!(
+            "{}:{}",
+            resource.client_id,
+            resource.client_secret.unwrap()
+        ))
+    );
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/introspect")
+        .insert_header(("Authorization", basic.clone()))
+        .set_form([("token", refreshed.access_token.as_str())])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let introspection: Introspection = test::read_body_json(res).await;
+
+    assert!(introspection.active);
+    assert_eq!(introspection.sub, Some(account.id.clone()));
+    assert_eq!(introspection.client_id, Some(client.client_id.clone()));
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/revoke")
+        .set_form([
+            ("token", refreshed.access_token.as_str()),
+            ("client_id", client.client_id.as_str()),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/introspect")
+        .insert_header(("Authorization", basic))
+        .set_form([("token", refreshed.access_token.as_str())])
+        .send_request(&app)
+        .await;
+
+    let introspection: Introspection = test::read_body_json(res).await;
+
+    assert!(!introspection.active);
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_client_credentials(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    context
+        .db
+        .connection
+        .query("UPDATE type::thing('account', $id) SET roles = ['admin']")
+        .bind(("id", account.id.clone()))
+        .await
+        .unwrap();
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/clients")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .set_json(json!({
+            "name": "reporting",
+            "confidential": true,
+            "redirect_uris": [],
+            "grant_types": ["client_credentials"],
+            "scopes": ["accounts:read"],
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let client: RegisteredClient = test::read_body_json(res).await;
+    let secret = client.client_secret.unwrap();
+
+    let basic = |secret: &str| {
+        format!(
+            "Basic {}",
+            STANDARD.encode(format!("{}:{}", client.client_id, secret))
+        )
+    };
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .insert_header(("Authorization", basic("wrong")))
+        .set_form([("grant_type", "client_credentials")])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(res.headers().get("www-authenticate").unwrap(), "Basic");
+
+    let error: OAuthError = test::read_body_json(res).await;
+
+    assert_eq!(error.error, "invalid_client");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .insert_header(("Authorization", basic(&secret)))
+        .set_form([("grant_type", "client_credentials")])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let token: Token = test::read_body_json(res).await;
+
+    assert!(token.refresh_token.is_none());
+    assert_eq!(token.scope, "accounts:read");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .insert_header(("Authorization", basic(&secret)))
+        .set_form([
+            ("grant_type", "client_credentials"),
+            ("scope", "accounts:write"),
+        ])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let error: OAuthError = test::read_body_json(res).await;
+
+    assert_eq!(error.error, "invalid_scope");
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oauth/clients")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let clients: Vec<serde_json::Value> = test::read_body_json(res).await;
+
+    assert_eq!(clients.len(), 1);
+    assert!(clients[0].get("client_secret").is_none());
+
+    let res = TestRequest::delete()
+        .uri(&format!("/api/v1/oauth/clients/{}", client.client_id))
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/token")
+        .insert_header(("Authorization", basic(&secret)))
+        .set_form([("grant_type", "client_credentials")])
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_register_client_requires_admin(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/oauth/clients")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({
+            "name": "dashboard",
+            "confidential": true,
+            "redirect_uris": [REDIRECT_URI],
+            "grant_types": ["authorization_code"],
+            "scopes": [],
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
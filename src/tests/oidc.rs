@@ -0,0 +1,315 @@
+use std::sync::Arc;
+
+use actix_web::cookie::Cookie;
+use actix_web::dev::ServiceResponse;
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+
+use crate::config::AppConfig;
+use crate::container::Container;
+use crate::tests::utils::crypto::{generate_keyring, password_hasher, password_policy};
+use crate::tests::utils::oidc::{AuthorizationRequest, MockOidcProvider};
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{Error, TestContext, context, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Account {
+    id: String,
+    email: String,
+}
+
+fn container(context: &TestContext, provider: &MockOidcProvider) -> Arc<Container> {
+    let mut config = AppConfig::load().unwrap();
+
+    config.oidc.providers = vec![provider.config("mock")];
+
+    Arc::new(Container::new(
+        context.db.connection.clone(),
+        generate_keyring(),
+        password_hasher(),
+        password_policy(),
+        &config,
+    ))
+}
+
+fn authorization<B>(res: &ServiceResponse<B>) -> (AuthorizationRequest, Cookie<'static>) {
+    let location = res.headers().get("location").unwrap().to_str().unwrap();
+
+    let cookie = res
+        .response()
+        .cookies()
+        .find(|cookie| cookie.name() == "oidc_state")
+        .unwrap()
+        .into_owned();
+
+    (AuthorizationRequest::parse(location), cookie)
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_oidc_login(#[future] context: TestContext) {
+    let provider = MockOidcProvider::start().await;
+
+    let app = test::init_service(app::create(container(&context, &provider))).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/mock/authorize")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FOUND);
+
+    let (request, state_cookie) = authorization(&res);
+
+    let claims = provider.claims("sub-1", &request.nonce, "new@email.com", true);
+    provider.expect_token("code", &request, &claims).await;
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oidc/mock/callback?code=code&state={}",
+            request.state
+        ))
+        .cookie(state_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("set-cookie").is_some());
+
+    let access_token: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let acc: Account = test::read_body_json(res).await;
+
+    assert_eq!(acc.email, "new@email.com");
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_oidc_link(#[future] context: TestContext) {
+    let provider = MockOidcProvider::start().await;
+
+    let app = test::init_service(app::create(container(&context, &provider))).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me/oidc/mock/link")
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::FOUND);
+
+    let (request, state_cookie) = authorization(&res);
+
+    let claims = provider.claims("sub-1", &request.nonce, "idp@email.com", true);
+    provider.expect_token("code-1", &request, &claims).await;
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oidc/mock/callback?code=code-1&state={}",
+            request.state
+        ))
+        .cookie(state_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // Later signins resolve the account through the linked identity
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/mock/authorize")
+        .send_request(&app)
+        .await;
+
+    let (request, state_cookie) = authorization(&res);
+
+    let claims = provider.claims("sub-1", &request.nonce, "idp@email.com", true);
+    provider.expect_token("code-2", &request, &claims).await;
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oidc/mock/callback?code=code-2&state={}",
+            request.state
+        ))
+        .cookie(state_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let access_token: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token.token)))
+        .send_request(&app)
+        .await;
+
+    let acc: Account = test::read_body_json(res).await;
+
+    assert_eq!(acc.id, account.id);
+    assert_eq!(acc.email, account.email);
+
+    let subjects: Vec<String> = context
+        .db
+        .connection
+        .query("SELECT VALUE subject FROM account_identity WHERE provider = 'mock'")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+
+    assert_eq!(subjects, vec!["sub-1".to_string()]);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_oidc_login_existing_email(#[future] context: TestContext) {
+    let provider = MockOidcProvider::start().await;
+
+    let app = test::init_service(app::create(container(&context, &provider))).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/mock/authorize")
+        .send_request(&app)
+        .await;
+
+    let (request, state_cookie) = authorization(&res);
+
+    let claims = provider.claims("sub-1", &request.nonce, &account.email, true);
+    provider.expect_token("code", &request, &claims).await;
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oidc/mock/callback?code=code&state={}",
+            request.state
+        ))
+        .cookie(state_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_oidc_login_missing_state_cookie(#[future] context: TestContext) {
+    let provider = MockOidcProvider::start().await;
+
+    let app = test::init_service(app::create(container(&context, &provider))).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/mock/authorize")
+        .send_request(&app)
+        .await;
+
+    let (request, _) = authorization(&res);
+
+    let res = TestRequest::get()
+        .uri(&format!(
+            "/api/v1/oidc/mock/callback?code=code&state={}",
+            request.state
+        ))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_oidc_login_replayed_state(#[future] context: TestContext) {
+    let provider = MockOidcProvider::start().await;
+
+    let app = test::init_service(app::create(container(&context, &provider))).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/mock/authorize")
+        .send_request(&app)
+        .await;
+
+    let (request, state_cookie) = authorization(&res);
+
+    let claims = provider.claims("sub-1", &request.nonce, "new@email.com", true);
+    provider.expect_token("code", &request, &claims).await;
+
+    let uri = format!(
+        "/api/v1/oidc/mock/callback?code=code&state={}",
+        request.state
+    );
+
+    let res = TestRequest::get()
+        .uri(&uri)
+        .cookie(state_cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::get()
+        .uri(&uri)
+        .cookie(state_cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_oidc_unknown_provider(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/oidc/unknown/authorize")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let err: Error = test::read_body_json(res).await;
+
+    assert_eq!(err.code, 404);
+    assert_eq!(err.message, "Identity provider not found");
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,182 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde_json::json;
+
+use crate::tests::utils::seed::{seed_account, seed_token};
+use crate::tests::{Error, TestContext, context};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[rstest]
+#[case::known_email("test_account@email.com")]
+#[case::unknown_email("fake_account@email.com")]
+#[awt]
+#[actix_web::test]
+async fn test_forgot_password(#[future] context: TestContext, #[case] email: String) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/forgot")
+        .set_json(json!({ "email": email }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_reset_password(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    seed_token(
+        &context.db.connection,
+        "password_reset_token",
+        &account,
+        "reset_token",
+    )
+    .await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/reset")
+        .set_json(json!({
+            "token": "reset_token",
+            "password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_reset_password_twice(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    seed_token(
+        &context.db.connection,
+        "password_reset_token",
+        &account,
+        "reset_token",
+    )
+    .await;
+
+    for status in [StatusCode::NO_CONTENT, StatusCode::UNAUTHORIZED] {
+        let res = TestRequest::post()
+            .uri("/api/v1/password/reset")
+            .set_json(json!({
+                "token": "reset_token",
+                "password": "n3wStR0ngP4ssw0rd!",
+            }))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), status);
+    }
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_reset_password_reuse(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    seed_token(
+        &context.db.connection,
+        "password_reset_token",
+        &account,
+        "reset_token",
+    )
+    .await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/reset")
+        .set_json(json!({
+            "token": "reset_token",
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let err: Error = test::read_body_json(res).await;
+    assert!(err.message.contains("Password has been used recently"));
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/reset")
+        .set_json(json!({
+            "token": "reset_token",
+            "password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_invalid_reset_token(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/reset")
+        .set_json(json!({
+            "token": "unknown_token",
+            "password": "n3wStR0ngP4ssw0rd!",
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 401);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use actix_web::http::StatusCode;
+use rstest::*;
+
+use crate::domain::repositories::rate_limit::RateLimitRepository;
+use crate::infrastructure::repositories::rate_limit::RateLimitRepositoryImpl;
+use crate::tests::{TestContext, context};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_rate_limit_headers(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/password/forgot")
+        .peer_addr("10.0.0.1:4000".parse().unwrap())
+        .set_json(serde_json::json!({ "email": "unknown@spacecraft.com" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+    assert!(res.headers().contains_key("ratelimit-limit"));
+    assert!(res.headers().contains_key("ratelimit-remaining"));
+    assert!(res.headers().contains_key("ratelimit-reset"));
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_surrealdb_bucket(#[future] context: TestContext) {
+    let repository = RateLimitRepositoryImpl::new(Arc::new(context.db.connection.clone()));
+
+    for remaining in (0..3).rev() {
+        let bucket = repository
+            .acquire("scope|ip:10.0.0.1", 3, 0.001)
+            .await
+            .unwrap();
+
+        assert!(bucket.allowed);
+        assert_eq!(bucket.tokens.floor() as u32, remaining);
+    }
+
+    let bucket = repository
+        .acquire("scope|ip:10.0.0.1", 3, 0.001)
+        .await
+        .unwrap();
+
+    assert!(!bucket.allowed);
+
+    let bucket = repository
+        .acquire("scope|ip:10.0.0.2", 3, 0.001)
+        .await
+        .unwrap();
+
+    assert!(bucket.allowed);
+
+    let _ = context.db.container.stop().await;
+}
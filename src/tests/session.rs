@@ -0,0 +1,175 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{Error, TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccessToken {
+    token: String,
+    #[allow(dead_code)]
+    expires_at: i64,
+    refresh_token: String,
+    #[allow(dead_code)]
+    refresh_expires_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Session {
+    id: String,
+    user_agent: Option<String>,
+    current: bool,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_session_lifecycle(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .insert_header(("User-Agent", "spacecraft-cli/1.0"))
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let other: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me/sessions")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let sessions: Vec<Session> = test::read_body_json(res).await;
+
+    assert_eq!(sessions.len(), 2);
+
+    let current: Vec<&Session> = sessions.iter().filter(|s| s.current).collect();
+    assert_eq!(current.len(), 1);
+
+    let other_session = sessions.iter().find(|s| !s.current).unwrap();
+    assert_eq!(
+        other_session.user_agent.as_deref(),
+        Some("spacecraft-cli/1.0")
+    );
+
+    let res = TestRequest::delete()
+        .uri(&format!("/api/v1/me/sessions/{}", other_session.id))
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    // Both the access token and the refresh token of the revoked session stop working
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", other.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": other.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me/sessions")
+        .cookie(cookie.clone())
+        .send_request(&app)
+        .await;
+
+    let sessions: Vec<Session> = test::read_body_json(res).await;
+
+    assert_eq!(sessions.len(), 1);
+    assert!(sessions[0].current);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_revoke_unknown_session(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let res = TestRequest::delete()
+        .uri("/api/v1/me/sessions/unknown")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let err: Error = test::read_body_json(res).await;
+    assert_eq!(err.code, 404);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_signout_revokes_session(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let signin: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signout")
+        .insert_header(("Authorization", format!("Bearer {}", signin.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": signin.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,117 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::{Error, TestContext, context};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccessToken {
+    token: String,
+    #[allow(dead_code)]
+    expires_at: i64,
+    refresh_token: String,
+    #[allow(dead_code)]
+    refresh_expires_at: i64,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_refresh(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let signin: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": signin.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let refreshed: AccessToken = test::read_body_json(res).await;
+
+    assert!(!refreshed.token.is_empty());
+    assert_ne!(refreshed.refresh_token, signin.refresh_token);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_refresh_token_reuse(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/signin")
+        .set_json(json!({
+            "email": account.email,
+            "password": account.password,
+        }))
+        .send_request(&app)
+        .await;
+
+    let signin: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": signin.refresh_token }))
+        .send_request(&app)
+        .await;
+
+    let refreshed: AccessToken = test::read_body_json(res).await;
+
+    for refresh_token in [signin.refresh_token, refreshed.refresh_token] {
+        let res = TestRequest::post()
+            .uri("/api/v1/token/refresh")
+            .set_json(json!({ "refresh_token": refresh_token }))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let err: Error = test::read_body_json(res).await;
+        assert_eq!(err.code, 401);
+    }
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_unknown_refresh_token(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let res = TestRequest::post()
+        .uri("/api/v1/token/refresh")
+        .set_json(json!({ "refresh_token": "unknown_refresh_token" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
@@ -0,0 +1,50 @@
+use jsonwebtoken::Algorithm;
+
+use crate::config::{
+    JsonWebTokenConfig, PasswordHashAlgorithm, PasswordHashConfig, PasswordPolicyConfig,
+    SigninLockoutConfig,
+};
+
+pub fn jsonwebtoken_config() -> JsonWebTokenConfig {
+    JsonWebTokenConfig {
+        algorithm: Algorithm::RS256,
+        signing_kid: "test".to_string(),
+        keys: vec![],
+        issuer: "test".to_string(),
+        audience: "test".to_string(),
+        ttl: 3600,
+        refresh_ttl: 2592000,
+        mfa_ttl: 300,
+        not_before: 0,
+        leeway: 0,
+    }
+}
+
+pub fn password_hash_config() -> PasswordHashConfig {
+    PasswordHashConfig {
+        algorithm: PasswordHashAlgorithm::Argon2id,
+        version: 19,
+        memory_cost: 19456,
+        time_cost: 2,
+        parallelism: 1,
+        pepper: None,
+    }
+}
+
+pub fn password_policy_config() -> PasswordPolicyConfig {
+    PasswordPolicyConfig {
+        min_entropy: 30.0,
+        reject_personal_info: true,
+        breached_passwords: None,
+    }
+}
+
+pub fn signin_lockout_config() -> SigninLockoutConfig {
+    SigninLockoutConfig {
+        account_threshold: 3,
+        ip_threshold: 5,
+        window: 900,
+        lockout: 60,
+        max_lockout: 3600,
+    }
+}
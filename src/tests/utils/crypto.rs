@@ -1,13 +1,63 @@
-use openssl::pkey::PKey;
+use std::sync::Arc;
+
+use jsonwebtoken::Algorithm;
+use openssl::ec::{EcGroup, EcKey};
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
 use openssl::rsa::Rsa;
+use tokio::sync::Mutex;
 
-use crate::services::jsonwebtoken::KeyPair;
+use crate::config::PasswordHistoryConfig;
+use crate::infrastructure::repositories::password_history::mock::PasswordHistoryRepositoryImpl;
+use crate::services::hasher::Argon2Hasher;
+use crate::services::keyring::{KeyPair, Keyring};
+use crate::services::password_history::PasswordHistory;
+use crate::services::password_policy::PasswordPolicy;
+use crate::tests::utils::config::{password_hash_config, password_policy_config};
 
-pub fn generate_keypair() -> KeyPair {
-    let rsa = Rsa::generate(2048).unwrap();
-    let pkey = PKey::from_rsa(rsa).unwrap();
+pub fn generate_pem(algorithm: Algorithm) -> (Vec<u8>, Vec<u8>) {
+    let pkey: PKey<Private> = match algorithm {
+        Algorithm::ES256 => {
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+            PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+        }
+        Algorithm::ES384 => {
+            let group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+            PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+        }
+        Algorithm::EdDSA => PKey::generate_ed25519().unwrap(),
+        _ => PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap(),
+    };
     let private_key = pkey.private_key_to_pem_pkcs8().unwrap();
     let public_key = pkey.public_key_to_pem().unwrap();
 
-    KeyPair::from_rsa_pem(private_key, public_key).unwrap()
+    (private_key, public_key)
+}
+
+pub fn generate_keypair(kid: &str, algorithm: Algorithm) -> KeyPair {
+    let (private_key, public_key) = generate_pem(algorithm);
+
+    KeyPair::from_pem(kid, algorithm, Some(private_key), public_key).unwrap()
+}
+
+pub fn generate_keyring() -> Keyring {
+    Keyring::new("test", vec![generate_keypair("test", Algorithm::RS256)]).unwrap()
+}
+
+pub fn password_hasher() -> Argon2Hasher {
+    Argon2Hasher::new(&password_hash_config()).unwrap()
+}
+
+pub fn password_policy() -> PasswordPolicy {
+    PasswordPolicy::new(&password_policy_config()).unwrap()
+}
+
+pub fn password_history(size: usize) -> PasswordHistory {
+    PasswordHistory::new(
+        &PasswordHistoryConfig { size },
+        Arc::new(password_hasher()),
+        Arc::new(PasswordHistoryRepositoryImpl {
+            entries: Mutex::new(vec![]),
+        }),
+    )
 }
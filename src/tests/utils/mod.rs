@@ -1,2 +1,5 @@
+pub mod config;
 pub mod crypto;
+pub mod oidc;
 pub mod seed;
+pub mod webauthn;
@@ -0,0 +1,150 @@
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, Header, encode};
+use reqwest::Url;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+use crate::config::OidcProviderConfig;
+use crate::services::crypto::hash_token;
+use crate::services::keyring::Keyring;
+use crate::tests::utils::crypto::generate_keypair;
+
+pub const CLIENT_ID: &str = "surreal-actix";
+
+// Local OpenID provider serving discovery, JWKS and token endpoints over a mock HTTP server
+pub struct MockOidcProvider {
+    server: MockServer,
+    keys: Keyring,
+}
+
+// Parameters the relying party sent along with the redirect to the provider
+#[derive(Debug)]
+pub struct AuthorizationRequest {
+    pub state: String,
+    pub nonce: String,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+}
+
+impl MockOidcProvider {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let keys = Keyring::new("oidc", vec![generate_keypair("oidc", Algorithm::RS256)]).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": server.uri(),
+                "authorization_endpoint": format!("{}/authorize", server.uri()),
+                "token_endpoint": format!("{}/token", server.uri()),
+                "jwks_uri": format!("{}/jwks", server.uri()),
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/jwks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(keys.jwks()))
+            .mount(&server)
+            .await;
+
+        Self { server, keys }
+    }
+
+    pub fn config(&self, name: &str) -> OidcProviderConfig {
+        OidcProviderConfig {
+            name: name.to_string(),
+            issuer: self.server.uri(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "secret".to_string(),
+            redirect_uri: format!("http://localhost:8080/api/v1/oidc/{name}/callback"),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+        }
+    }
+
+    pub fn claims(&self, subject: &str, nonce: &str, email: &str, email_verified: bool) -> Value {
+        let now = Utc::now().timestamp();
+
+        json!({
+            "iss": self.server.uri(),
+            "aud": CLIENT_ID,
+            "sub": subject,
+            "nonce": nonce,
+            "email": email,
+            "email_verified": email_verified,
+            "name": "Oidc Account",
+            "iat": now,
+            "exp": now + 300,
+        })
+    }
+
+    pub fn id_token(&self, claims: &Value) -> String {
+        let (kid, key) = self.keys.signing_key();
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_owned());
+
+        encode(&header, claims, key).unwrap()
+    }
+
+    // Answers a single exchange of `code` whose verifier matches the request's S256 challenge
+    pub async fn expect_token(&self, code: &str, request: &AuthorizationRequest, claims: &Value) {
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(TokenRequestMatcher {
+                code: code.to_owned(),
+                code_challenge: request.code_challenge.clone(),
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "provider-access-token",
+                "token_type": "Bearer",
+                "expires_in": 300,
+                "id_token": self.id_token(claims),
+            })))
+            .up_to_n_times(1)
+            .mount(&self.server)
+            .await;
+    }
+}
+
+impl AuthorizationRequest {
+    pub fn parse(url: &str) -> Self {
+        let url = Url::parse(url).unwrap();
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+                .unwrap()
+        };
+
+        Self {
+            state: param("state"),
+            nonce: param("nonce"),
+            code_challenge: param("code_challenge"),
+            code_challenge_method: param("code_challenge_method"),
+        }
+    }
+}
+
+struct TokenRequestMatcher {
+    code: String,
+    code_challenge: String,
+}
+
+impl Match for TokenRequestMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        let body = String::from_utf8_lossy(&request.body);
+        let form = Url::parse(&format!("http://localhost/?{body}")).unwrap();
+        let param = |name: &str| {
+            form.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        param("grant_type").as_deref() == Some("authorization_code")
+            && param("code").as_deref() == Some(self.code.as_str())
+            && param("code_verifier")
+                .is_some_and(|verifier| hash_token(&verifier) == self.code_challenge)
+    }
+}
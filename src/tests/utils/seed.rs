@@ -1,4 +1,5 @@
-use crate::domain::models::account::Account;
+use crate::domain::models::account::{Account, AccountStatus, Role};
+use crate::services::crypto::hash_token;
 use ::surrealdb::{Surreal, engine::remote::ws::Client, sql::Thing};
 use chrono;
 
@@ -22,5 +23,31 @@ pub async fn seed_account(conn: &Surreal<Client>) -> Account {
         name: "Test Account".to_string(),
         email: "test_account@email.com".to_string(),
         password: "stR0ngP4ssw0rd!".to_string(),
+        roles: vec![Role::User],
+        permissions: vec![],
+        password_changed_at: None,
+        email_verified_at: None,
+        status: AccountStatus::Active,
+        deleted_at: None,
+        created_at: chrono::Utc::now().timestamp(),
     }
 }
+
+pub async fn seed_token(conn: &Surreal<Client>, table: &str, account: &Account, token: &str) {
+    conn.query(
+        r#"
+        CREATE type::table($table) CONTENT {
+            account: type::thing('account', $account),
+            token_hash: $token_hash,
+            expires_at: time::now() + 15m
+        };
+        "#,
+    )
+    .bind(("table", table.to_owned()))
+    .bind(("account", account.id.clone()))
+    .bind(("token_hash", hash_token(token)))
+    .await
+    .unwrap()
+    .check()
+    .unwrap();
+}
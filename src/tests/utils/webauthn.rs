@@ -0,0 +1,127 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ciborium::Value;
+use p256::ecdsa::{Signature, SigningKey, signature::Signer};
+use sha2::{Digest, Sha256};
+
+use crate::domain::models::webauthn::{AssertCredential, RegisterCredential};
+
+// Software authenticator producing ES256 credentials with "none" attestation
+#[derive(Clone)]
+pub struct SoftAuthenticator {
+    rp_id: String,
+    origin: String,
+    key: SigningKey,
+    credential_id: Vec<u8>,
+    sign_count: u32,
+    // Whether the user passes the PIN or biometric check, presence is always confirmed
+    pub user_verified: bool,
+}
+
+impl SoftAuthenticator {
+    pub fn new(rp_id: &str, origin: &str) -> Self {
+        let mut credential_id = vec![0u8; 16];
+        OsRng.fill_bytes(&mut credential_id);
+
+        Self {
+            rp_id: rp_id.to_string(),
+            origin: origin.to_string(),
+            key: SigningKey::random(&mut OsRng),
+            credential_id,
+            sign_count: 0,
+            user_verified: true,
+        }
+    }
+
+    pub fn credential_id(&self) -> String {
+        URL_SAFE_NO_PAD.encode(&self.credential_id)
+    }
+
+    pub fn register(&mut self, challenge: &str) -> RegisterCredential {
+        let point = self.key.verifying_key().to_encoded_point(false);
+
+        let cose_key = Value::Map(vec![
+            (Value::Integer(1.into()), Value::Integer(2.into())),
+            (Value::Integer(3.into()), Value::Integer((-7).into())),
+            (Value::Integer((-1).into()), Value::Integer(1.into())),
+            (
+                Value::Integer((-2).into()),
+                Value::Bytes(point.x().unwrap().to_vec()),
+            ),
+            (
+                Value::Integer((-3).into()),
+                Value::Bytes(point.y().unwrap().to_vec()),
+            ),
+        ]);
+
+        let mut authenticator_data = self.authenticator_data(0x40);
+        authenticator_data.extend_from_slice(&[0u8; 16]);
+        authenticator_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        authenticator_data.extend_from_slice(&self.credential_id);
+        ciborium::into_writer(&cose_key, &mut authenticator_data).unwrap();
+
+        let attestation_object = Value::Map(vec![
+            (
+                Value::Text("fmt".to_string()),
+                Value::Text("none".to_string()),
+            ),
+            (Value::Text("attStmt".to_string()), Value::Map(vec![])),
+            (
+                Value::Text("authData".to_string()),
+                Value::Bytes(authenticator_data),
+            ),
+        ]);
+
+        let mut encoded = vec![];
+        ciborium::into_writer(&attestation_object, &mut encoded).unwrap();
+
+        RegisterCredential {
+            credential_id: self.credential_id(),
+            client_data_json: self.client_data_json("webauthn.create", challenge),
+            attestation_object: URL_SAFE_NO_PAD.encode(encoded),
+        }
+    }
+
+    pub fn assert(&mut self, challenge: &str, user_handle: Option<&str>) -> AssertCredential {
+        self.sign_count += 1;
+
+        let authenticator_data = self.authenticator_data(0x00);
+        let client_data_json = self.client_data_json("webauthn.get", challenge);
+
+        let mut signed = authenticator_data.clone();
+        signed.extend_from_slice(&Sha256::digest(
+            URL_SAFE_NO_PAD.decode(&client_data_json).unwrap(),
+        ));
+
+        let signature: Signature = self.key.sign(&signed);
+
+        AssertCredential {
+            credential_id: self.credential_id(),
+            client_data_json,
+            authenticator_data: URL_SAFE_NO_PAD.encode(authenticator_data),
+            signature: URL_SAFE_NO_PAD.encode(signature.to_der()),
+            user_handle: user_handle.map(|account| URL_SAFE_NO_PAD.encode(account)),
+        }
+    }
+
+    fn authenticator_data(&self, flags: u8) -> Vec<u8> {
+        let user_verified = if self.user_verified { 0x04 } else { 0x00 };
+
+        let mut data = Sha256::digest(self.rp_id.as_bytes()).to_vec();
+        data.push(flags | 0x01 | user_verified);
+        data.extend_from_slice(&self.sign_count.to_be_bytes());
+        data
+    }
+
+    fn client_data_json(&self, kind: &str, challenge: &str) -> String {
+        URL_SAFE_NO_PAD.encode(
+            serde_json::json!({
+                "type": kind,
+                "challenge": challenge,
+                "origin": self.origin,
+                "crossOrigin": false,
+            })
+            .to_string(),
+        )
+    }
+}
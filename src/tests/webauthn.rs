@@ -0,0 +1,182 @@
+use actix_web::http::StatusCode;
+use rstest::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::tests::utils::seed::seed_account;
+use crate::tests::utils::webauthn::SoftAuthenticator;
+use crate::tests::{TestContext, context, csrf_header, request_cookie};
+
+use crate::app;
+use actix_web::test;
+use actix_web::test::TestRequest;
+
+#[derive(Debug, Deserialize)]
+pub struct CredentialDescriptor {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialOptions {
+    challenge: String,
+    #[serde(default)]
+    exclude_credentials: Vec<CredentialDescriptor>,
+    #[serde(default)]
+    allow_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    token: String,
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_success_webauthn_login(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let account = seed_account(&context.db.connection).await;
+
+    let cookie = request_cookie(&app, &account.email, &account.password).await;
+
+    let mut authenticator = SoftAuthenticator::new("localhost", "http://localhost:8080");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/webauthn/register/options")
+        .cookie(cookie.clone())
+        .insert_header(csrf_header(&cookie))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let options: CredentialOptions = test::read_body_json(res).await;
+
+    assert!(options.exclude_credentials.is_empty());
+
+    let credential = authenticator.register(&options.challenge);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/me/webauthn/register")
+        .insert_header(csrf_header(&cookie))
+        .cookie(cookie)
+        .set_json(json!({
+            "id": credential.credential_id,
+            "rawId": credential.credential_id,
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": credential.client_data_json,
+                "attestationObject": credential.attestation_object,
+            },
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/webauthn/login/options")
+        .set_json(json!({ "email": account.email }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let options: CredentialOptions = test::read_body_json(res).await;
+
+    assert_eq!(options.allow_credentials.len(), 1);
+    assert_eq!(
+        options.allow_credentials[0].id,
+        authenticator.credential_id()
+    );
+
+    let assertion = authenticator.assert(&options.challenge, Some(&account.id));
+
+    let res = TestRequest::post()
+        .uri("/api/v1/webauthn/login")
+        .set_json(json!({
+            "id": assertion.credential_id,
+            "rawId": assertion.credential_id,
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": assertion.client_data_json,
+                "authenticatorData": assertion.authenticator_data,
+                "signature": assertion.signature,
+                "userHandle": assertion.user_handle,
+            },
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get("set-cookie").is_some());
+
+    let access_token: AccessToken = test::read_body_json(res).await;
+
+    let res = TestRequest::get()
+        .uri("/api/v1/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token.token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/webauthn/login")
+        .set_json(json!({
+            "id": assertion.credential_id,
+            "response": {
+                "clientDataJSON": assertion.client_data_json,
+                "authenticatorData": assertion.authenticator_data,
+                "signature": assertion.signature,
+            },
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}
+
+#[rstest]
+#[awt]
+#[actix_web::test]
+async fn test_webauthn_login_unknown_credential(#[future] context: TestContext) {
+    let app = test::init_service(app::create(context.container)).await;
+
+    let mut authenticator = SoftAuthenticator::new("localhost", "http://localhost:8080");
+
+    let res = TestRequest::post()
+        .uri("/api/v1/webauthn/login/options")
+        .set_json(json!({}))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let options: CredentialOptions = test::read_body_json(res).await;
+
+    assert!(options.allow_credentials.is_empty());
+
+    let assertion = authenticator.assert(&options.challenge, None);
+
+    let res = TestRequest::post()
+        .uri("/api/v1/webauthn/login")
+        .set_json(json!({
+            "id": assertion.credential_id,
+            "response": {
+                "clientDataJSON": assertion.client_data_json,
+                "authenticatorData": assertion.authenticator_data,
+                "signature": assertion.signature,
+            },
+        }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let _ = context.db.container.stop().await;
+}